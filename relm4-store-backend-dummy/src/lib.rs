@@ -155,10 +155,10 @@ where
         result
     }
 
-    fn inbox(&mut self, _msg: StoreMsg<Self::Record>) -> store::Replies<Self::Record> {
-        Replies{
+    fn inbox(&mut self, _msg: StoreMsg<Self::Record>) -> Result<store::Replies<Self::Record>, store::BackendError> {
+        Ok(Replies{
             replies: vec!()
-        }
+        })
     }
 
 }
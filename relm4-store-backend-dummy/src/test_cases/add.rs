@@ -193,6 +193,31 @@ impl TestCases {
         }
     }
 
+    /// Creates test case where `count` new records will be added at once at position `at` to the store of given `size`
+    pub fn add_range(at: usize, count: usize, size: usize) -> TestCase {
+        let TestCase{ mut configuration, data } = TestCases::with_initial_size(size);
+
+        let mut new_data = data.clone();
+        for idx in 0..count {
+            new_data.insert(at + idx, TestRecord::since(&format!("New record {}", idx), 0));
+        }
+
+        configuration.steps.push(Step{
+            data: new_data,
+            events: vec![
+                StoreViewMsg::NewRange{
+                    from: Position(at),
+                    to: Position(at + count),
+                }
+            ]
+        });
+
+        TestCase {
+            configuration,
+            data
+        }
+    }
+
     /// Reload an empty store
     /// 
     /// `[] ---[ Reload ]---> []`
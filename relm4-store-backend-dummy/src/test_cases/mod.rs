@@ -2,6 +2,7 @@
 mod add;
 mod add_multistep;
 mod remove;
+mod reorder;
 
 #[cfg(test)]
 mod tests;
@@ -15,6 +16,7 @@ use uuid::Uuid;
 
 use record::Id;
 use record::Record;
+use record::TreeRecord;
 use super::DummyBackendConfiguration;
 
 
@@ -114,6 +116,66 @@ impl PartialEq for TestRecord {
 
 impl Eq for TestRecord {}
 
+/// Sample record for the tree test cases
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestTreeRecord {
+    id: Id<Self>,
+    /// Human understandable description of this record
+    pub label: String,
+    /// Id of the parent of this record, [None] for the roots
+    pub parent: Option<Id<Self>>,
+}
+
+impl TestTreeRecord {
+    /// Creates root record
+    pub fn root(label: &str) -> Self {
+        TestTreeRecord{
+            id: Id::new(),
+            label: String::from(label),
+            parent: None,
+        }
+    }
+
+    /// Creates record which is a child of the `parent`
+    pub fn child(label: &str, parent: &TestTreeRecord) -> Self {
+        TestTreeRecord{
+            id: Id::new(),
+            label: String::from(label),
+            parent: Some(parent.get_id()),
+        }
+    }
+
+    /// Make record permanent
+    pub fn permanent(mut self) -> Self {
+        self.set_permanent_id(DefaultIdAllocator::new_id()).unwrap();
+        self
+    }
+}
+
+impl Record for TestTreeRecord {
+    type Allocator = DefaultIdAllocator;
+
+    fn get_id(&self) -> record::Id<Self> {
+        self.id
+    }
+
+    fn set_permanent_id(&mut self, value: Uuid) -> Result<(), record::IdentityError> {
+        if !self.id.is_new() {
+            Err(record::IdentityError("TestTreeRecord already has permanent id"))
+        }
+        else {
+            self.id = Id::from(value);
+            Ok( () )
+        }
+    }
+}
+
+impl TreeRecord for TestTreeRecord {
+    fn get_parent(&self) -> Option<Id<Self>> {
+        self.parent
+    }
+}
+
 type C = DummyBackendConfiguration<TestRecord>;
 
 /// Describes test case
@@ -58,4 +58,27 @@ impl TestCases {
             data
         }
     }
+
+    /// Creates test case where records in `[from, to)` will be removed from store of given `size` at once
+    pub fn remove_range(from: usize, to: usize, size: usize) -> TestCase {
+        let TestCase{ mut configuration, data } = TestCases::with_initial_size(size);
+
+        let mut new_data = data.clone();
+        new_data.drain(from..to);
+
+        configuration.steps.push(Step{
+            data: new_data,
+            events: vec![
+                StoreViewMsg::RemoveRange{
+                    from: Position(from),
+                    to: Position(to),
+                }
+            ]
+        });
+
+        TestCase {
+            configuration,
+            data
+        }
+    }
 }
\ No newline at end of file
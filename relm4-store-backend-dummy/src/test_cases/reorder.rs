@@ -0,0 +1,59 @@
+use store::Position;
+use store::StoreViewMsg;
+
+use crate::configuration::Step;
+
+use super::TestCase;
+use super::TestCases;
+
+/// Test cases where records change their order
+impl TestCases {
+    /// Creates test case where record at `from` is moved to `to` in the store of given `size`
+    /// 
+    /// `to` is the position of the record after the move
+    pub fn move_nth(from: usize, to: usize, size: usize) -> TestCase {
+        let TestCase{ mut configuration, data } = TestCases::with_initial_size(size);
+
+        let mut new_data = data.clone();
+        let record = new_data.remove(from);
+        new_data.insert(to, record);
+
+        configuration.steps.push(Step{
+            data: new_data,
+            events: vec![
+                StoreViewMsg::Move{
+                    from: Position(from),
+                    to: Position(to),
+                }
+            ]
+        });
+
+        TestCase {
+            configuration,
+            data
+        }
+    }
+
+    /// Creates test case where records in `[from, to)` are reversed in the store of given `size`
+    pub fn reverse_range(from: usize, to: usize, size: usize) -> TestCase {
+        let TestCase{ mut configuration, data } = TestCases::with_initial_size(size);
+
+        let mut new_data = data.clone();
+        new_data[from..to].reverse();
+
+        configuration.steps.push(Step{
+            data: new_data,
+            events: vec![
+                StoreViewMsg::Reorder{
+                    from: Position(from),
+                    to: Position(to),
+                }
+            ]
+        });
+
+        TestCase {
+            configuration,
+            data
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use reexport::log;
 
 use store::Backend;
+use store::BackendError;
 use store::Replies;
 use store::StoreViewMsg;
 
@@ -24,6 +25,20 @@ pub trait InMemoryBackendConfiguration {
     fn initial_data() -> Vec<Self::Record>;
 }
 
+/// Change made by the transaction, keeps what's needed to revert it
+#[derive(Debug)]
+enum Undo<R: Record> {
+    /// Record was added
+    Insert(Id<R>),
+    /// Record was replaced, value is the old record
+    Update(R),
+    /// Record was removed from the position
+    Delete{
+        record: R,
+        position: usize,
+    },
+}
+
 /// In memory implementation of the data store
 #[derive(Debug)]
 pub struct InMemoryBackend<Configuration> 
@@ -35,6 +50,9 @@ where
 
     /// profile storage
     data: HashMap<Id<Configuration::Record>, Configuration::Record>,
+
+    /// Changes made by the transaction in progress
+    undo: Option<Vec<Undo<Configuration::Record>>>,
 }
 
 impl<Configuration> InMemoryBackend<Configuration> 
@@ -46,6 +64,7 @@ where
         let mut backend = InMemoryBackend {
             order: VecDeque::new(),
             data: HashMap::new(),
+            undo: None,
         };
 
         for record in Configuration::initial_data() {
@@ -60,10 +79,133 @@ where
         {
             self.data.insert(id, record);
             self.order.push_back(id);
+            self.log(Undo::Insert(id));
 
             Position(self.order.len() -1)
         }
     }
+
+    /// Replaces the record which is already in the store
+    ///
+    /// Returns [BackendError::NotFound] if there is no record with the same id in the store
+    fn replace(&mut self, record: Configuration::Record) -> Result<(), BackendError> {
+        let id = record.get_id();
+        let slot = self.data.get_mut(&id)
+            .ok_or_else(|| BackendError::NotFound(format!("{:?}", id)))?;
+        let old = std::mem::replace(slot, record);
+        self.log(Undo::Update(old));
+        Ok(())
+    }
+
+    /// Remembers the change if there is a transaction in progress
+    fn log(&mut self, undo: Undo<Configuration::Record>) {
+        if let Some(log) = self.undo.as_mut() {
+            log.push(undo);
+        }
+    }
+
+    fn position(&self, id: &Id<Configuration::Record>) -> Option<usize> {
+        self.order.iter().position(|other| other == id)
+    }
+
+    fn apply(&mut self, msg: StoreMsg<Configuration::Record>) -> Result<Replies<Configuration::Record>, BackendError> {
+        let mut replies = vec!();
+
+        match msg {
+            StoreMsg::Commit(record) => {
+                let id = record.get_id();
+                {
+                    if id.is_new() {
+                        let position = self.add(record);
+                        replies.push(StoreViewMsg::NewAt(position));
+                    }
+                    else {
+                        self.replace(record)?;
+                        replies.push(StoreViewMsg::Update(id));
+                    }
+                }
+
+            },
+            StoreMsg::Restore{record, position} => {
+                let id = record.get_id();
+                if self.data.contains_key(&id) {
+                    self.replace(record)?;
+                    replies.push(StoreViewMsg::Update(id));
+                }
+                else {
+                    let position = min(position.0, self.order.len());
+                    self.data.insert(id, record);
+                    self.order.insert(position, id);
+                    self.log(Undo::Insert(id));
+                    replies.push(StoreViewMsg::NewAt(Position(position)));
+                }
+            },
+            StoreMsg::Delete(id) => {
+                let record = self.data.remove(&id)
+                    .ok_or_else(|| BackendError::NotFound(format!("{:?}", id)))?;
+
+                if let Some(idx) = self.position(&id) {
+                    self.order.remove(idx);
+                    self.log(Undo::Delete{
+                        record,
+                        position: idx,
+                    });
+                    replies.push(StoreViewMsg::Remove(Position(idx)));
+                }
+            },
+            StoreMsg::Reload => {
+                //it's in memory store so nothing to do...
+            }, 
+            StoreMsg::Transaction(messages) => {
+                // nested transaction is reverted together with the outermost one
+                let outermost = self.undo.is_none();
+                if outermost {
+                    self.undo = Some(vec![]);
+                }
+
+                let result = messages.into_iter()
+                    .try_for_each(|msg| {
+                        replies.append(&mut self.apply(msg)?.replies);
+                        Ok(())
+                    });
+
+                if outermost {
+                    let undo = self.undo.take().unwrap_or_default();
+                    if result.is_err() {
+                        self.rollback(undo);
+                    }
+                }
+                result?;
+                replies = StoreViewMsg::merge_ranges(replies);
+            },
+        };
+
+        Ok(Replies{
+            replies
+        })
+    }
+
+    /// Reverts the changes in the reverse order
+    fn rollback(&mut self, undo: Vec<Undo<Configuration::Record>>) {
+        for entry in undo.into_iter().rev() {
+            match entry {
+                Undo::Insert(id) => {
+                    if let Some(position) = self.position(&id) {
+                        self.order.remove(position);
+                    }
+                    self.data.remove(&id);
+                },
+                Undo::Update(record) => {
+                    self.data.insert(record.get_id(), record);
+                },
+                Undo::Delete{record, position} => {
+                    let id = record.get_id();
+                    self.order.insert(position, id);
+                    self.data.insert(id, record);
+                },
+            }
+        }
+    }
 }
 
 impl<Configuration> Backend for InMemoryBackend<Configuration>
@@ -106,53 +248,9 @@ where
         data.get(id).cloned()
     }
 
-    fn inbox(&mut self, msg: StoreMsg<Configuration::Record>) -> Replies<Configuration::Record> {
+    fn inbox(&mut self, msg: StoreMsg<Configuration::Record>) -> Result<Replies<Configuration::Record>, BackendError> {
         log::info!("Received message: {:?}", &msg);
-
-        let mut replies = vec!();
-
-        match msg {
-            StoreMsg::Commit(record) => {
-                let id = record.get_id();
-                {
-                    if id.is_new() {
-                        let position = self.add(record);
-                        replies.push(StoreViewMsg::NewAt(position));
-                    }
-                    else {
-                        self.data.insert(id, record);
-                        replies.push(StoreViewMsg::Update(id));
-                    }
-                }
-
-            },
-            StoreMsg::Delete(id) => {
-                if self.data.contains_key(&id) {
-                    self.data.remove(&id);
-                    
-                    let mut order_idx = None;
-
-                    for (idx, oid) in self.order.iter().enumerate() {
-                        if *oid == id {
-                            order_idx = Some(idx);
-                        }
-                    }
-
-                    if let Some(idx) = order_idx {
-                        self.order.remove(idx);
-                        replies.push(StoreViewMsg::Remove(Position(idx)));
-                    }
-
-                }
-            },
-            StoreMsg::Reload => {
-                //it's in memory store so nothing to do...
-            }, 
-        };
-
-        Replies{
-            replies
-        }
+        self.apply(msg)
     }
 }
 
@@ -1,15 +1,21 @@
+use collections::Tree;
+use collections::TreeConfiguration;
 use reexport::log;
 use store::Backend;
+use store::BackendError;
 use store::OrderedBackend;
 use store::Replies;
 use store::Sorter;
 use store::StoreViewMsg;
 
 use std::cmp::Ordering;
-use std::cmp::min;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
 use record::Id;
+use record::Identity;
 use record::Record;
 use record::TemporaryIdAllocator;
 use store::Position;
@@ -30,19 +36,36 @@ pub trait SortedInMemoryBackendConfiguration {
     fn initial_order() -> Self::OrderBy;
 }
 
+/// Change made by the transaction, keeps what's needed to revert it
+#[derive(Debug)]
+enum Undo<R: Record> {
+    /// Record was added
+    Insert(Id<R>),
+    /// Record was replaced, value is the old record
+    Update(Rc<R>),
+    /// Record was removed
+    Delete(Rc<R>),
+}
+
 /// In memory implementation of the data store
+/// 
+/// Records are kept in the B-tree with order statistics, so adding, removing, updating and finding position of
+/// the record is `O(log n)`. Records which are equal according to the ordering are sorted by their id's.
 #[derive(Debug)]
 pub struct SortedInMemoryBackend<Config> 
 where 
     Config: SortedInMemoryBackendConfiguration,
 {
-    /// Order of profiles
-    order: Vec<Id<Config::Record>>,
+    /// Order of records
+    order: Tree<SortKey<Config>, ()>,
+
+    /// record storage
+    data: HashMap<Id<Config::Record>, Rc<Config::Record>>,
 
-    /// profile storage
-    data: HashMap<Id<Config::Record>, Config::Record>,
+    ordering: Config::OrderBy,
 
-    ordering: Config::OrderBy
+    /// Changes made by the transaction in progress
+    undo: Option<Vec<Undo<Config::Record>>>,
 }
 
 impl<Config> SortedInMemoryBackend<Config> 
@@ -52,26 +75,33 @@ where
     /// Creates new instance of the InMemoryBackend
     pub fn new() -> Self {
         let mut backend = SortedInMemoryBackend{
-            order: Vec::new(),
+            order: Tree::new(TreeConfiguration::default()),
             data: HashMap::new(),
             ordering: Config::initial_order(),
+            undo: None,
         };
         
         let initial_data = Config::initial_data();
         for record in initial_data {
-            backend.add(record);
+            if let Err(err) = backend.add(record) {
+                log::warn!("Skipping initial record: {}", err);
+            }
         }
 
         backend
     }
 
-    fn add(&mut self, mut record: Config::Record) -> Replies<Config::Record> {
+    fn add(&mut self, mut record: Config::Record) -> Result<Replies<Config::Record>, BackendError> {
         let mut replies = vec!();
         let id = record.get_id();
         if id.is_new() {
-            record.set_permanent_id(<<Config::Record as Record>::Allocator as TemporaryIdAllocator>::new_id()).unwrap_or_else(|_| panic!("Unable to set the permanent id for record `{:#?}`", record));
+            record.set_permanent_id(<<Config::Record as Record>::Allocator as TemporaryIdAllocator>::new_id())
+                .map_err(|err| BackendError::Identity(String::from(err.0)))?;
+            let new = record.get_id();
             let position = self.insert(record);
             replies.push(StoreViewMsg::NewAt(position));
+            // whoever sent the record knows it only by the temporary id
+            replies.push(StoreViewMsg::IdChanged{old: id, new});
         }
         else if !self.data.contains_key(&id) {
             let position = self.insert(record);
@@ -81,8 +111,29 @@ where
             replies.push(self.update(record));
         }
 
-        Replies{
+        Ok(Replies{
             replies
+        })
+    }
+
+    /// Remembers the change if there is a transaction in progress
+    fn log(&mut self, undo: Undo<Config::Record>) {
+        if let Some(log) = self.undo.as_mut() {
+            log.push(undo);
+        }
+    }
+
+    /// Ids of the records in the current order
+    fn ids(&self) -> Vec<Id<Config::Record>> {
+        self.order.iter()
+            .map(|(key, _)| key.record.get_id())
+            .collect()
+    }
+
+    fn key(&self, record: Rc<Config::Record>) -> SortKey<Config> {
+        SortKey{
+            record,
+            ordering: self.ordering,
         }
     }
 
@@ -91,28 +142,15 @@ where
     /// Running it for existing record is undefined and might happily destroy your data. 
     fn insert(&mut self, record: Config::Record) -> Position {
         let id = record.get_id();
+        let record = Rc::new(record);
         self.data.insert(id, record.clone());
 
-        let position = {
-            let ordering = self.ordering;
-            let r = record;
-            self.order.binary_search_by(|other_id| {
-                let other = self.data.get(other_id).unwrap();
-                ordering.cmp(&r, other).reverse()
-            })
-        };
+        let key = self.key(record);
+        let position = self.order.position(&key).unwrap_or_else(|position| position);
+        self.order.insert(key, ());
+        self.log(Undo::Insert(id));
 
-        match position {
-            // if two elements are equal it doesn't matter which one is first
-            Ok(p) => {
-                self.order.insert(p, id);
-                Position(p)
-            },
-            Err(p) => {
-                self.order.insert(p, id);
-                Position(p)
-            }
-        }
+        Position(position)
     }
 
     /// Calls to this method are allowed only if record is already in the store
@@ -121,78 +159,117 @@ where
     /// killing your cat, or flooding, or erupting volcanos, or whatever other disaster you might think of. 
     fn update(&mut self, record: Config::Record) -> StoreViewMsg<Config::Record> {
         let id = record.get_id();
+        let record = Rc::new(record);
         // record is already in store => it's safe to unwrap
         let old_record = self.data.insert(id, record.clone()).unwrap();
 
-        let old_position: usize = {
-            let ordering = self.ordering;
-            let position = self.order.binary_search_by(|other_id| {
-                let other = self.data.get(other_id).unwrap();
-                ordering.cmp(&old_record, other).reverse()
-            });
-
-            match position {
-                Ok(p) => p,
-                Err(_) => panic!("Record doesn't exist in order while it's in the data! Performing seppuku!")
-            }
+        // old record is still in the order so we can find where it is
+        let from = match self.order.rank(&self.key(old_record.clone())) {
+            Some(position) => position,
+            None => panic!("Record doesn't exist in order while it's in the data! Performing seppuku!")
         };
+        self.order.remove_nth(from);
+        self.log(Undo::Update(old_record));
 
+        let key = self.key(record);
+        let to = self.order.position(&key).unwrap_or_else(|position| position);
+        self.order.insert(key, ());
 
-        let position = {
-            let ordering = self.ordering;
-            let r = record;
-            self.order.binary_search_by(|other_id| {
-                let other = self.data.get(other_id).unwrap();
-                ordering.cmp(&r, other).reverse()
-            })
-        };
+        if from == to {
+            StoreViewMsg::Update(id)
+        }
+        else {
+            StoreViewMsg::Move{from: Position(from), to: Position(to)}
+        }
+    }
+
+    /// Applies the message
+    /// 
+    /// Rejected transaction leaves the data untouched
+    fn apply(&mut self, msg: StoreMsg<Config::Record>) -> Result<Replies<Config::Record>, BackendError> {
+        match msg {
+            StoreMsg::Commit(record) => {
+                self.add(record)
+            },
+            // position is decided by the ordering
+            StoreMsg::Restore{record, ..} => {
+                self.add(record)
+            },
+            StoreMsg::Reload => {
+                //it's in memory store so nothing to do...
+                Ok(Replies{
+                    replies: vec!()
+                })
+            },
+            StoreMsg::Transaction(messages) => {
+                // nested transaction is reverted together with the outermost one
+                let outermost = self.undo.is_none();
+                if outermost {
+                    self.undo = Some(vec![]);
+                }
 
-        let (to, mut from, p) = match position {
-            Ok(p) => {
-                match p.cmp(&old_position) {
-                    Ordering::Equal => (p, old_position, StoreViewMsg::Update(id)),
-                    Ordering::Greater => (p, old_position, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} ),
-                    Ordering::Less => (old_position, p, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} ),
+                let mut replies = vec![];
+                let result = messages.into_iter()
+                    .try_for_each(|msg| {
+                        replies.append(&mut self.apply(msg)?.replies);
+                        Ok(())
+                    });
+
+                if outermost {
+                    let undo = self.undo.take().unwrap_or_default();
+                    if result.is_err() {
+                        self.rollback(undo);
+                    }
                 }
-                // // if two elements are equal it doesn't matter which one is first
-                // if p == old_position {
-                //     (p, old_position, StoreViewMsg::Update(id))
-                // }
-                // else if p < old_position {
-                //     (old_position, p, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} )
-                // }
-                // else {
-                //     (p, old_position, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} )
-                // }
+                result?;
+
+                Ok(Replies{
+                    replies: StoreViewMsg::merge_ranges(replies),
+                })
             },
-            Err(p) => {
-                match p.cmp(&old_position) {
-                    Ordering::Equal => (p, old_position, StoreViewMsg::Update(id)),
-                    Ordering::Greater => (p, old_position, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} ),
-                    Ordering::Less => (old_position, p, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} ),
+            StoreMsg::Delete(id) => {
+                match self.data.remove(&id) {
+                    Some(record) => {
+                        let position = self.order.rank(&self.key(record.clone()))
+                            .expect("Record doesn't exist in order while it's in the data!");
+                        self.order.remove_nth(position);
+                        self.log(Undo::Delete(record));
+
+                        Ok(Replies{
+                            replies: vec![StoreViewMsg::Remove(Position(position))],
+                        })
+                    },
+                    None => Err(BackendError::NotFound(format!("{:?}", id))),
                 }
-                // if p == old_position {
-                //     (p, old_position, StoreViewMsg::Update(id))
-                // }
-                // else if p < old_position {
-                //     (old_position, p, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} )
-                // }
-                // else {
-                //     (p, old_position, StoreViewMsg::Move{from: Position(old_position), to: Position(p)} )
-                // }
-            }
-        };
+            },
+        }
+    }
 
-        if from != to {
-            // perform minimum reorder of view for the case when records where updated
-            while to >= from {
-                self.order[from] = self.order[from-1];
-                from -= 1;
+    /// Reverts the changes in the reverse order
+    fn rollback(&mut self, undo: Vec<Undo<Config::Record>>) {
+        for entry in undo.into_iter().rev() {
+            match entry {
+                Undo::Insert(id) => {
+                    if let Some(record) = self.data.remove(&id) {
+                        if let Some(position) = self.order.rank(&self.key(record)) {
+                            self.order.remove_nth(position);
+                        }
+                    }
+                },
+                Undo::Update(record) => {
+                    if let Some(current) = self.data.insert(record.get_id(), record.clone()) {
+                        if let Some(position) = self.order.rank(&self.key(current)) {
+                            self.order.remove_nth(position);
+                        }
+                    }
+                    self.order.insert(self.key(record), ());
+                },
+                Undo::Delete(record) => {
+                    self.data.insert(record.get_id(), record.clone());
+                    self.order.insert(self.key(record), ());
+                },
             }
-            self.order[to] = id;
         }
-
-        p
     }
 }
 
@@ -211,62 +288,22 @@ where
     }
 
     fn get_range(&self, range: &Range) -> Vec<Self::Record> {
-        let count = self.len();
-
-        let start = min(*range.start(), count);
-        let length = min(*range.end(), count) - start;
+        let start = *range.start();
+        let length = range.end().saturating_sub(start);
 
-        let mut result: Vec<Self::Record> = Vec::new();
-
-        for idx in start..(start+length) {
-            let id = &self.order[idx];
-            let record = self.data.get(id).unwrap().clone();
-            result.push(record);
-        }
-
-        result
+        self.order.iter_from(start)
+            .take(length)
+            .map(|(key, _)| key.record.as_ref().clone())
+            .collect()
     }
 
     fn get(&self, id: &Id<Configuration::Record>) -> Option<Configuration::Record> {
-        self.data.get(id).cloned()
+        self.data.get(id).map(|record| record.as_ref().clone())
     }
 
-    fn inbox(&mut self, msg: StoreMsg<Configuration::Record>) -> Replies<Configuration::Record> {
+    fn inbox(&mut self, msg: StoreMsg<Configuration::Record>) -> Result<Replies<Configuration::Record>, BackendError> {
         log::info!("Received message: {:?}", &msg);
-        match msg {
-            StoreMsg::Commit(record) => {
-                self.add(record)
-            },
-            StoreMsg::Reload => {
-                //it's in memory store so nothing to do...
-                Replies{
-                    replies: vec!()
-                }
-            }, 
-            StoreMsg::Delete(id) => {
-                let mut replies = vec![];
-                if self.data.contains_key(&id) {
-                    self.data.remove(&id);
-                    
-                    let mut order_idx = None;
-
-                    for (idx, oid) in self.order.iter().enumerate() {
-                        if *oid == id {
-                            order_idx = Some(idx);
-                        }
-                    }
-
-                    if let Some(idx) = order_idx {
-                        self.order.remove(idx);
-                        replies.push(StoreViewMsg::Remove(Position(idx)));
-                    }
-                }
-
-                Replies{
-                    replies,
-                }
-            },
-        }
+        self.apply(msg)
     }
 }
 
@@ -275,27 +312,27 @@ where
     Config: SortedInMemoryBackendConfiguration + 'static,
 {
     fn set_order(&mut self, ordering: Config::OrderBy) -> Replies<Config::Record> {
-        let mut ordered_data_before = vec![];
-        
-        for idx in self.order.iter() {
-            ordered_data_before.push(self.data[idx].clone())
-        }
-        
+        let before = self.ids();
         self.ordering = ordering;
 
-        self.order.sort_by(|lhs, rhs| {
-            let l = &self.data[lhs];
-            let r = &self.data[rhs];
-            ordering.cmp(l, r)
-        });
-
-        let mut ordered_data_after = vec![];
-        for idx in self.order.iter() {
-            ordered_data_after.push(self.data[idx].clone())
+        let mut order = Tree::new(TreeConfiguration::default());
+        for record in self.data.values() {
+            order.insert(self.key(record.clone()), ());
+        }
+        self.order = order;
+
+        // records outside of the changed range keep their positions, so views don't need to touch them
+        let changed = Range::changed(&before, &self.ids());
+        let mut replies = vec![];
+        if !changed.is_empty() {
+            replies.push(StoreViewMsg::Reorder{
+                from: Position(*changed.start()),
+                to: Position(*changed.end()),
+            });
         }
 
         Replies{
-            replies: vec!(StoreViewMsg::Reload)
+            replies
         }
     }
 }
@@ -307,4 +344,72 @@ where
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Key of the record in the order
+/// 
+/// Tree requires total order of the keys, so key knows the ordering and if ordering can't decide it compares the ids
+struct SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    record: Rc<Config::Record>,
+    ordering: Config::OrderBy,
+}
+
+impl<Config> Clone for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    fn clone(&self) -> Self {
+        SortKey{
+            record: self.record.clone(),
+            ordering: self.ordering,
+        }
+    }
+}
+
+impl<Config> Ord for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.ordering.cmp(&self.record, &other.record)
+            .then_with(|| self.record.get_id().get_value().cmp(&other.record.get_id().get_value()))
+    }
+}
+
+impl<Config> PartialOrd for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Config> PartialEq for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<Config> Eq for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{}
+
+/// Formats [`SortKey`] for debug format `{:?}`
+impl<Config> Debug for SortKey<Config>
+where
+    Config: SortedInMemoryBackendConfiguration,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SortKey")
+            .field("record", &self.record)
+            .finish()
+    }
+}
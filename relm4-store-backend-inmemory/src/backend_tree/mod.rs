@@ -0,0 +1,423 @@
+use reexport::log;
+
+use store::Backend;
+use store::BackendError;
+use store::Replies;
+use store::StoreViewMsg;
+use store::TreeBackend;
+
+use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use record::Id;
+use record::Record;
+use record::TreeRecord;
+use store::Position;
+use store::StoreMsg;
+use store::math::Range;
+
+/// Children of each node, roots are kept under `None`
+type Children<R> = HashMap<Option<Id<R>>, Vec<Id<R>>>;
+
+/// Change made by the transaction, keeps what's needed to revert it
+#[derive(Debug)]
+enum Undo<R: TreeRecord> {
+    /// Record was added as the last child of it's parent
+    Add(Id<R>),
+    /// Record was replaced without leaving it's place in the tree, value is the old record
+    Update(R),
+    /// Subtree was removed
+    Delete{
+        /// Position of the subtree root in the order
+        position: usize,
+        /// Position of the subtree root among it's siblings
+        sibling: usize,
+        /// Removed records in depth first pre-order
+        records: Vec<R>,
+        /// Children of the removed records
+        children: Vec<(Id<R>, Vec<Id<R>>)>,
+    },
+    /// Record was moved with it's subtree under another parent
+    Reparent{
+        /// Old version of the record
+        record: R,
+        /// Old position of the record in the order
+        position: usize,
+        /// Old position of the record among it's siblings
+        sibling: usize,
+    },
+}
+
+/// Configuration trait for the TreeInMemoryBackend
+pub trait TreeInMemoryBackendConfiguration {
+    /// Type of data in the in memory store
+    type Record: 'static + TreeRecord + Debug + Clone;
+
+    /// Returns initial dataset for the store
+    /// 
+    /// Parent must be in the initial data before any of it's children
+    fn initial_data() -> Vec<Self::Record>;
+}
+
+/// In memory implementation of the data store keeping records in a tree
+/// 
+/// Records are kept in depth first pre-order. New child is added as the last child of it's parent.
+/// Removing the record removes it's whole subtree.
+#[derive(Debug)]
+pub struct TreeInMemoryBackend<Config> 
+where 
+    Config: TreeInMemoryBackendConfiguration,
+{
+    /// Depth first pre-order of the records
+    order: Vec<Id<Config::Record>>,
+
+    /// Children of the node in the order of insertion
+    children: Children<Config::Record>,
+
+    /// record storage
+    data: HashMap<Id<Config::Record>, Config::Record>,
+
+    /// Changes made by the transaction in progress
+    undo: Option<Vec<Undo<Config::Record>>>,
+}
+
+impl<Config> TreeInMemoryBackend<Config> 
+where 
+    Config: TreeInMemoryBackendConfiguration + 'static,
+{
+    /// Creates new instance of the TreeInMemoryBackend
+    pub fn new() -> Self {
+        let mut backend = TreeInMemoryBackend {
+            order: Vec::new(),
+            children: HashMap::new(),
+            data: HashMap::new(),
+            undo: None,
+        };
+
+        for record in Config::initial_data() {
+            if let Err(err) = backend.add(record) {
+                log::warn!("Skipping initial record: {}", err);
+            }
+        }
+
+        backend
+    }
+
+    fn position(&self, id: &Id<Config::Record>) -> Option<usize> {
+        self.order.iter().position(|other| other == id)
+    }
+
+    /// Returns ids of the node and all of it's descendants in depth first pre-order
+    fn subtree(&self, id: Id<Config::Record>) -> Vec<Id<Config::Record>> {
+        let mut result = vec![id];
+        if let Some(children) = self.children.get(&Some(id)) {
+            for child in children {
+                result.append(&mut self.subtree(*child));
+            }
+        }
+        result
+    }
+
+    /// Position at which new child of the `parent` should be inserted
+    /// 
+    /// Returns `None` if parent is unknown
+    fn insert_position(&self, parent: Option<Id<Config::Record>>) -> Option<usize> {
+        match parent {
+            None => Some(self.order.len()),
+            Some(parent) => {
+                let position = self.position(&parent)?;
+                Some(position + self.subtree(parent).len())
+            }
+        }
+    }
+
+    /// Position of the node among it's siblings
+    fn sibling_position(&self, id: &Id<Config::Record>) -> usize {
+        let parent = self.data.get(id).and_then(|record| record.get_parent());
+        self.children.get(&parent)
+            .and_then(|siblings| siblings.iter().position(|other| other == id))
+            .unwrap_or(0)
+    }
+
+    /// Remembers the change if there is a transaction in progress
+    fn log(&mut self, undo: impl FnOnce(&Self) -> Undo<Config::Record>) {
+        if self.undo.is_some() {
+            let entry = undo(self);
+            if let Some(log) = self.undo.as_mut() {
+                log.push(entry);
+            }
+        }
+    }
+
+    fn add(&mut self, record: Config::Record) -> Result<Replies<Config::Record>, BackendError> {
+        let id = record.get_id();
+        let parent = record.get_parent();
+
+        let position = self.insert_position(parent)
+            .ok_or_else(|| BackendError::NotFound(format!("{:?}", parent)))?;
+        self.order.insert(position, id);
+        self.children.entry(parent).or_default().push(id);
+        self.data.insert(id, record);
+        self.log(|_| Undo::Add(id));
+
+        Ok(Replies{
+            replies: vec![StoreViewMsg::NewAt(Position(position))]
+        })
+    }
+
+    /// Removes node with all of it's descendants from the order
+    /// 
+    /// Returns removed ids in the depth first pre-order
+    fn detach(&mut self, id: Id<Config::Record>, replies: &mut Vec<StoreViewMsg<Config::Record>>) -> Vec<Id<Config::Record>> {
+        let subtree = self.subtree(id);
+        if let Some(position) = self.position(&id) {
+            self.order.drain(position..position+subtree.len());
+            for _ in 0..subtree.len() {
+                // every removal moves next record of the subtree to the same position
+                replies.push(StoreViewMsg::Remove(Position(position)));
+            }
+        }
+
+        let parent = self.data.get(&id).and_then(|record| record.get_parent());
+        if let Some(siblings) = self.children.get_mut(&parent) {
+            siblings.retain(|other| *other != id);
+        }
+
+        subtree
+    }
+
+    fn delete(&mut self, id: Id<Config::Record>) -> Result<Replies<Config::Record>, BackendError> {
+        let mut replies = vec![];
+
+        if !self.data.contains_key(&id) {
+            return Err(BackendError::NotFound(format!("{:?}", id)))
+        }
+
+        self.log(|backend| {
+            let subtree = backend.subtree(id);
+            Undo::Delete{
+                position: backend.position(&id).unwrap_or(0),
+                sibling: backend.sibling_position(&id),
+                records: subtree.iter()
+                    .map(|removed| backend.data[removed].clone())
+                    .collect(),
+                children: subtree.iter()
+                    .filter_map(|removed| backend.children.get(&Some(*removed)).map(|children| (*removed, children.clone())))
+                    .collect(),
+            }
+        });
+
+        for removed in self.detach(id, &mut replies) {
+            self.data.remove(&removed);
+            self.children.remove(&Some(removed));
+        }
+
+        Ok(Replies{
+            replies
+        })
+    }
+
+    fn update(&mut self, record: Config::Record) -> Result<Replies<Config::Record>, BackendError> {
+        let id = record.get_id();
+        let old_parent = self.data.get(&id).and_then(|old| old.get_parent());
+        let new_parent = record.get_parent();
+
+        if old_parent == new_parent {
+            if let Some(old) = self.data.insert(id, record) {
+                self.log(|_| Undo::Update(old));
+            }
+            return Ok(Replies{
+                replies: vec![StoreViewMsg::Update(id)]
+            })
+        }
+
+        if let Some(new_parent) = new_parent {
+            if !self.data.contains_key(&new_parent) {
+                return Err(BackendError::NotFound(format!("{:?}", new_parent)))
+            }
+            if self.subtree(id).contains(&new_parent) {
+                return Err(BackendError::Rejected(format!("{:?} can't be moved into it's own subtree", id)))
+            }
+        }
+
+        self.log(|backend| Undo::Reparent{
+            record: backend.data[&id].clone(),
+            position: backend.position(&id).unwrap_or(0),
+            sibling: backend.sibling_position(&id),
+        });
+
+        let mut replies = vec![];
+        let subtree = self.detach(id, &mut replies);
+        self.data.insert(id, record);
+
+        let position = self.insert_position(new_parent).expect("Parent was checked before detaching the record");
+        self.children.entry(new_parent).or_default().push(id);
+        for (idx, moved) in subtree.into_iter().enumerate() {
+            self.order.insert(position + idx, moved);
+            replies.push(StoreViewMsg::NewAt(Position(position + idx)));
+        }
+
+        Ok(Replies{
+            replies
+        })
+    }
+
+    /// Applies the message
+    /// 
+    /// Rejected transaction leaves the data untouched
+    fn apply(&mut self, msg: StoreMsg<Config::Record>) -> Result<Replies<Config::Record>, BackendError> {
+        match msg {
+            // records are always added as the last child of their parent
+            StoreMsg::Commit(record) | StoreMsg::Restore{record, ..} => {
+                if self.data.contains_key(&record.get_id()) {
+                    self.update(record)
+                }
+                else {
+                    self.add(record)
+                }
+            },
+            StoreMsg::Delete(id) => self.delete(id),
+            StoreMsg::Reload => {
+                //it's in memory store so nothing to do...
+                Ok(Replies{
+                    replies: vec![]
+                })
+            },
+            StoreMsg::Transaction(messages) => {
+                // nested transaction is reverted together with the outermost one
+                let outermost = self.undo.is_none();
+                if outermost {
+                    self.undo = Some(vec![]);
+                }
+
+                let mut replies = vec![];
+                let result = messages.into_iter()
+                    .try_for_each(|msg| {
+                        replies.append(&mut self.apply(msg)?.replies);
+                        Ok(())
+                    });
+
+                if outermost {
+                    let undo = self.undo.take().unwrap_or_default();
+                    if result.is_err() {
+                        self.rollback(undo);
+                    }
+                }
+                result?;
+
+                Ok(Replies{
+                    replies: StoreViewMsg::merge_ranges(replies),
+                })
+            },
+        }
+    }
+
+    /// Reverts the changes in the reverse order
+    fn rollback(&mut self, undo: Vec<Undo<Config::Record>>) {
+        for entry in undo.into_iter().rev() {
+            match entry {
+                Undo::Add(id) => {
+                    if let Some(position) = self.position(&id) {
+                        self.order.remove(position);
+                    }
+                    let parent = self.data.remove(&id).and_then(|record| record.get_parent());
+                    if let Some(siblings) = self.children.get_mut(&parent) {
+                        siblings.retain(|other| *other != id);
+                    }
+                },
+                Undo::Update(record) => {
+                    self.data.insert(record.get_id(), record);
+                },
+                Undo::Delete{position, sibling, records, children} => {
+                    if let Some(root) = records.first() {
+                        self.children.entry(root.get_parent()).or_default().insert(sibling, root.get_id());
+                    }
+                    for (id, children) in children {
+                        self.children.insert(Some(id), children);
+                    }
+                    self.order.splice(position..position, records.iter().map(|record| record.get_id()));
+                    for record in records {
+                        self.data.insert(record.get_id(), record);
+                    }
+                },
+                Undo::Reparent{record, position, sibling} => {
+                    let id = record.get_id();
+                    let mut replies = vec![];
+                    let subtree = self.detach(id, &mut replies);
+                    self.children.entry(record.get_parent()).or_default().insert(sibling, id);
+                    self.data.insert(id, record);
+                    self.order.splice(position..position, subtree);
+                },
+            }
+        }
+    }
+}
+
+impl<Config> Backend for TreeInMemoryBackend<Config>
+where 
+    Config: 'static + TreeInMemoryBackendConfiguration,
+{
+    type Record = Config::Record;
+
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        let count = self.len();
+        let start = min(*range.start(), count);
+        let end = min(*range.end(), count);
+
+        self.order[start..end].iter()
+            .map(|id| self.data.get(id).unwrap().clone())
+            .collect()
+    }
+
+    fn get(&self, id: &Id<Config::Record>) -> Option<Config::Record> {
+        self.data.get(id).cloned()
+    }
+
+    fn inbox(&mut self, msg: StoreMsg<Config::Record>) -> Result<Replies<Config::Record>, BackendError> {
+        log::info!("Received message: {:?}", &msg);
+        self.apply(msg)
+    }
+}
+
+impl<Config> TreeBackend for TreeInMemoryBackend<Config>
+where 
+    Config: 'static + TreeInMemoryBackendConfiguration,
+{
+    fn children_len(&self, node: Option<&Id<Self::Record>>) -> usize {
+        self.children.get(&node.copied())
+            .map(|children| children.len())
+            .unwrap_or(0)
+    }
+
+    fn children(&self, node: Option<&Id<Self::Record>>, range: &Range) -> Vec<Self::Record> {
+        match self.children.get(&node.copied()) {
+            Some(children) => {
+                let start = min(*range.start(), children.len());
+                let end = min(*range.end(), children.len());
+
+                children[start..end].iter()
+                    .map(|id| self.data.get(id).unwrap().clone())
+                    .collect()
+            },
+            None => vec![],
+        }
+    }
+}
+
+impl<Config> Default for TreeInMemoryBackend<Config> 
+where 
+    Config: TreeInMemoryBackendConfiguration + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -9,9 +9,13 @@
 
 mod backend;
 mod backend_sorted;
+mod backend_tree;
 
 pub use backend::InMemoryBackend;
 pub use backend::InMemoryBackendConfiguration;
 
 pub use backend_sorted::SortedInMemoryBackend;
 pub use backend_sorted::SortedInMemoryBackendConfiguration;
+
+pub use backend_tree::TreeInMemoryBackend;
+pub use backend_tree::TreeInMemoryBackendConfiguration;
@@ -1,3 +1,4 @@
 
 mod insert_ascending;
-mod insert_descending;
\ No newline at end of file
+mod insert_descending;
+mod positions;
\ No newline at end of file
@@ -0,0 +1,172 @@
+use dummy::test_cases::TestRecord;
+use record::Identity;
+use record::Record;
+use store::Backend;
+use store::OrderedBackend;
+use store::Position;
+use store::StoreMsg;
+use store::StoreViewMsg;
+use store::math::Range;
+
+use crate::common::OrderTestRecordsBy;
+use crate::common::TestRecordsBase;
+use crate::common::TestRecordsConfigAsc8;
+use crate::common::TestRecordsConfigAscEmpty;
+
+type TestRecords = TestRecordsBase<TestRecordsConfigAsc8>;
+
+fn labels(backend: &impl Backend<Record=TestRecord>) -> Vec<String> {
+    backend.get_range(&Range::new(0, backend.len()))
+        .into_iter()
+        .map(|r| r.label)
+        .collect()
+}
+
+fn find(backend: &TestRecords, label: &str) -> TestRecord {
+    backend.get_range(&Range::new(0, backend.len()))
+        .into_iter()
+        .find(|r| r.label == label)
+        .unwrap()
+}
+
+#[test]
+fn equal_records_are_sorted_by_id() {
+    let mut backend = TestRecordsBase::<TestRecordsConfigAscEmpty>::new();
+    let mut records: Vec<TestRecord> = (0..10).map(|_| TestRecord::constant("same").permanent()).collect();
+
+    for record in &records {
+        backend.inbox(StoreMsg::Commit(record.clone())).unwrap();
+    }
+
+    records.sort_by_key(|r| r.get_id().get_value());
+    let expected: Vec<_> = records.iter().map(|r| r.get_id()).collect();
+    let ids: Vec<_> = backend.get_range(&Range::new(0, 10)).iter().map(|r| r.get_id()).collect();
+    assert_eq!(ids, expected, "Records which are equal for the ordering must be sorted by id");
+}
+
+#[test]
+fn update_keeps_place_among_equal_records() {
+    let mut backend = TestRecordsBase::<TestRecordsConfigAscEmpty>::new();
+    let records: Vec<TestRecord> = (0..3).map(|_| TestRecord::constant("same").permanent()).collect();
+
+    for record in &records {
+        backend.inbox(StoreMsg::Commit(record.clone())).unwrap();
+    }
+    let before: Vec<_> = backend.get_range(&Range::new(0, 3)).iter().map(|r| r.get_id()).collect();
+
+    let replies = backend.inbox(StoreMsg::Commit(records[0].clone())).unwrap().replies;
+
+    let ids: Vec<_> = backend.get_range(&Range::new(0, 3)).iter().map(|r| r.get_id()).collect();
+    assert_eq!(ids, before);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Update(_)));
+}
+
+#[test]
+fn restored_record_returns_to_its_place_among_equal_records() {
+    let mut backend = TestRecordsBase::<TestRecordsConfigAscEmpty>::new();
+    let records: Vec<TestRecord> = (0..5).map(|_| TestRecord::constant("same").permanent()).collect();
+
+    for record in &records {
+        backend.inbox(StoreMsg::Commit(record.clone())).unwrap();
+    }
+    let before = backend.get_range(&Range::new(0, 5));
+    let removed = before[2].clone();
+
+    backend.inbox(StoreMsg::Delete(removed.get_id())).unwrap();
+    let replies = backend.inbox(StoreMsg::Restore{record: removed, position: Position(0)}).unwrap().replies;
+
+    let expected: Vec<_> = before.iter().map(|r| r.get_id()).collect();
+    let ids: Vec<_> = backend.get_range(&Range::new(0, 5)).iter().map(|r| r.get_id()).collect();
+    assert_eq!(ids, expected, "Undo of the delete must put the record back in the same place");
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::NewAt(Position(2))));
+}
+
+#[test]
+fn delete_reports_position() {
+    let mut backend = TestRecords::new();
+    let o = find(&backend, "o");
+
+    let replies = backend.inbox(StoreMsg::Delete(o.get_id())).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["c", "f", "i", "l", "r", "u", "y"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Remove(Position(4))));
+}
+
+#[test]
+fn update_moves_record() {
+    let mut backend = TestRecords::new();
+    let mut c = find(&backend, "c");
+    c.label = String::from("s");
+
+    let replies = backend.inbox(StoreMsg::Commit(c)).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["f", "i", "l", "o", "r", "s", "u", "y"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Move{from: Position(0), to: Position(5)}));
+}
+
+#[test]
+fn update_in_place() {
+    let mut backend = TestRecords::new();
+    let mut l = find(&backend, "l");
+    l.label = String::from("m");
+    let id = l.get_id();
+
+    let replies = backend.inbox(StoreMsg::Commit(l)).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["c", "f", "i", "m", "o", "r", "u", "y"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Update(updated) if updated == id));
+}
+
+#[test]
+fn get_range_from_the_middle() {
+    let backend = TestRecords::new();
+
+    let labels: Vec<String> = backend.get_range(&Range::new(3, 6)).into_iter().map(|r| r.label).collect();
+    assert_eq!(labels, vec!["l", "o", "r"]);
+
+    assert!(backend.get_range(&Range::new(8, 10)).is_empty());
+}
+
+#[test]
+fn set_order_reports_reordered_range() {
+    let mut backend = TestRecords::new();
+
+    let replies = backend.set_order(OrderTestRecordsBy::Name{ascending: false}).replies;
+
+    assert_eq!(labels(&backend), vec!["y", "u", "r", "o", "l", "i", "f", "c"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Reorder{from: Position(0), to: Position(8)}));
+}
+
+#[test]
+fn set_order_without_changes_is_silent() {
+    let mut backend = TestRecords::new();
+
+    let replies = backend.set_order(OrderTestRecordsBy::Name{ascending: true}).replies;
+
+    assert_eq!(labels(&backend), vec!["c", "f", "i", "l", "o", "r", "u", "y"]);
+    assert!(replies.is_empty());
+}
+
+#[test]
+fn set_order_reports_only_moved_records() {
+    let mut backend = TestRecordsBase::<TestRecordsConfigAscEmpty>::new();
+    for label in ["a", "m", "m", "m", "z"] {
+        backend.inbox(StoreMsg::Commit(TestRecord::constant(label).permanent())).unwrap();
+    }
+    let middle: Vec<_> = backend.get_range(&Range::new(1, 4)).iter().map(|r| r.get_id()).collect();
+
+    let replies = backend.set_order(OrderTestRecordsBy::Name{ascending: false}).replies;
+
+    // equal records are ordered by id no matter the direction
+    let ids: Vec<_> = backend.get_range(&Range::new(1, 4)).iter().map(|r| r.get_id()).collect();
+    assert_eq!(ids, middle);
+    assert_eq!(labels(&backend), vec!["z", "m", "m", "m", "a"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::Reorder{from: Position(0), to: Position(5)}));
+}
@@ -0,0 +1,165 @@
+use dummy::test_cases::TestTreeRecord;
+use record::Record;
+use relm4_store_backend_inmemory::TreeInMemoryBackend;
+use store::Backend;
+use store::BackendError;
+use store::Position;
+use store::StoreMsg;
+use store::StoreViewMsg;
+use store::TreeBackend;
+use store::math::Range;
+
+use crate::common::TestTreeConfig;
+
+type TestBackend = TreeInMemoryBackend<TestTreeConfig>;
+
+fn labels(backend: &TestBackend) -> Vec<String> {
+    backend.get_range(&Range::new(0, backend.len()))
+        .into_iter()
+        .map(|r| r.label)
+        .collect()
+}
+
+fn find(backend: &TestBackend, label: &str) -> TestTreeRecord {
+    backend.get_range(&Range::new(0, backend.len()))
+        .into_iter()
+        .find(|r| r.label == label)
+        .unwrap()
+}
+
+#[test]
+fn initial_data_in_pre_order() {
+    let backend = TestBackend::new();
+
+    assert_eq!(labels(&backend), vec!["1", "1.1", "1.1.1", "1.2", "2", "2.1"]);
+    assert_eq!(backend.children_len(None), 2);
+
+    let r1 = find(&backend, "1");
+    assert_eq!(backend.children_len(Some(&r1.get_id())), 2);
+    let children: Vec<String> = backend.children(Some(&r1.get_id()), &Range::new(0, 10))
+        .into_iter()
+        .map(|r| r.label)
+        .collect();
+    assert_eq!(children, vec!["1.1", "1.2"]);
+}
+
+#[test]
+fn add_child_at_the_end_of_subtree() {
+    let mut backend = TestBackend::new();
+    let r1 = find(&backend, "1");
+
+    let replies = backend.inbox(StoreMsg::Commit(TestTreeRecord::child("1.3", &r1))).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["1", "1.1", "1.1.1", "1.2", "1.3", "2", "2.1"]);
+    assert_eq!(replies.len(), 1);
+    assert!(matches!(replies[0], StoreViewMsg::NewAt(Position(4))));
+}
+
+#[test]
+fn add_child_of_unknown_parent_is_rejected() {
+    let mut backend = TestBackend::new();
+    let orphan = TestTreeRecord::child("orphan", &TestTreeRecord::root("not in store"));
+
+    let result = backend.inbox(StoreMsg::Commit(orphan));
+
+    assert!(matches!(result, Err(BackendError::NotFound(_))));
+    assert_eq!(backend.len(), 6);
+}
+
+#[test]
+fn delete_removes_subtree() {
+    let mut backend = TestBackend::new();
+    let r11 = find(&backend, "1.1");
+
+    let replies = backend.inbox(StoreMsg::Delete(r11.get_id())).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["1", "1.2", "2", "2.1"]);
+    assert_eq!(replies.len(), 2);
+    assert!(replies.iter().all(|m| matches!(m, StoreViewMsg::Remove(Position(1)))));
+    assert_eq!(backend.children_len(Some(&find(&backend, "1").get_id())), 1);
+}
+
+#[test]
+fn reparent_moves_subtree() {
+    let mut backend = TestBackend::new();
+    let r2 = find(&backend, "2");
+    let mut r11 = find(&backend, "1.1");
+    r11.parent = Some(r2.get_id());
+
+    let replies = backend.inbox(StoreMsg::Commit(r11)).unwrap().replies;
+
+    assert_eq!(labels(&backend), vec!["1", "1.2", "2", "2.1", "1.1", "1.1.1"]);
+    assert_eq!(replies.len(), 4);
+    assert!(matches!(replies[0], StoreViewMsg::Remove(Position(1))));
+    assert!(matches!(replies[1], StoreViewMsg::Remove(Position(1))));
+    assert!(matches!(replies[2], StoreViewMsg::NewAt(Position(4))));
+    assert!(matches!(replies[3], StoreViewMsg::NewAt(Position(5))));
+}
+
+#[test]
+fn reparent_under_own_descendant_is_rejected() {
+    let mut backend = TestBackend::new();
+    let r111 = find(&backend, "1.1.1");
+    let mut r1 = find(&backend, "1");
+    r1.parent = Some(r111.get_id());
+
+    let result = backend.inbox(StoreMsg::Commit(r1));
+
+    assert!(matches!(result, Err(BackendError::Rejected(_))), "Tree can't have cycles");
+    assert_eq!(labels(&backend), vec!["1", "1.1", "1.1.1", "1.2", "2", "2.1"]);
+}
+
+fn children(backend: &TestBackend, node: Option<&TestTreeRecord>) -> Vec<String> {
+    let node = node.map(|node| node.get_id());
+    backend.children(node.as_ref(), &Range::new(0, 10))
+        .into_iter()
+        .map(|r| r.label)
+        .collect()
+}
+
+#[test]
+fn rejected_transaction_is_rolled_back() {
+    let mut backend = TestBackend::new();
+    let r1 = find(&backend, "1");
+    let r2 = find(&backend, "2");
+    let mut r11 = find(&backend, "1.1");
+    r11.parent = Some(r2.get_id());
+    let mut r12 = find(&backend, "1.2");
+    r12.label = String::from("1.2 - edited");
+    let orphan = TestTreeRecord::child("orphan", &TestTreeRecord::root("not in store"));
+
+    let result = backend.inbox(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(r12),
+        StoreMsg::Commit(TestTreeRecord::child("1.3", &r1)),
+        StoreMsg::Commit(r11),
+        StoreMsg::Delete(r2.get_id()),
+        StoreMsg::Commit(orphan),
+    ]));
+
+    assert!(matches!(result, Err(BackendError::NotFound(_))));
+    assert_eq!(labels(&backend), vec!["1", "1.1", "1.1.1", "1.2", "2", "2.1"]);
+    assert_eq!(children(&backend, None), vec!["1", "2"]);
+    assert_eq!(children(&backend, Some(&r1)), vec!["1.1", "1.2"]);
+    assert_eq!(children(&backend, Some(&r2)), vec!["2.1"]);
+    assert_eq!(children(&backend, Some(&find(&backend, "1.1"))), vec!["1.1.1"]);
+}
+
+#[test]
+fn nested_transaction_is_rolled_back_with_outer_one() {
+    let mut backend = TestBackend::new();
+    let r1 = find(&backend, "1");
+    let r111 = find(&backend, "1.1.1");
+    let mut r1_cycle = r1.clone();
+    r1_cycle.parent = Some(r111.get_id());
+
+    let result = backend.inbox(StoreMsg::Transaction(vec![
+        StoreMsg::Transaction(vec![
+            StoreMsg::Commit(TestTreeRecord::child("1.3", &r1)),
+        ]),
+        StoreMsg::Commit(r1_cycle),
+    ]));
+
+    assert!(matches!(result, Err(BackendError::Rejected(_))));
+    assert_eq!(labels(&backend), vec!["1", "1.1", "1.1.1", "1.2", "2", "2.1"]);
+    assert_eq!(children(&backend, Some(&r1)), vec!["1.1", "1.2"]);
+}
@@ -1,7 +1,9 @@
 use dummy::test_cases::TestRecord;
+use dummy::test_cases::TestTreeRecord;
 
 use relm4_store_backend_inmemory::SortedInMemoryBackend;
 use relm4_store_backend_inmemory::SortedInMemoryBackendConfiguration;
+use relm4_store_backend_inmemory::TreeInMemoryBackendConfiguration;
 use store::Sorter;
 
 
@@ -105,6 +107,35 @@ impl SortedInMemoryBackendConfiguration for TestRecordsConfigAsc8
     }
 }
 
+pub struct TestTreeConfig {}
+
+/// Tree used by the tests
+///
+/// ```text
+/// 1
+/// ├ 1.1
+/// │ └ 1.1.1
+/// └ 1.2
+/// 2
+/// └ 2.1
+/// ```
+///
+/// Initial data is not in pre-order on purpose, backend must sort it out
+impl TreeInMemoryBackendConfiguration for TestTreeConfig {
+    type Record = TestTreeRecord;
+
+    fn initial_data() -> Vec<Self::Record> {
+        let r1 = TestTreeRecord::root("1").permanent();
+        let r2 = TestTreeRecord::root("2").permanent();
+        let r11 = TestTreeRecord::child("1.1", &r1).permanent();
+        let r12 = TestTreeRecord::child("1.2", &r1).permanent();
+        let r21 = TestTreeRecord::child("2.1", &r2).permanent();
+        let r111 = TestTreeRecord::child("1.1.1", &r11).permanent();
+
+        vec![r1, r2, r11, r12, r21, r111]
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
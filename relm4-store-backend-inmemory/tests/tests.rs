@@ -1,3 +1,4 @@
 mod common;
 
-mod backend_sorted;
\ No newline at end of file
+mod backend_sorted;
+mod backend_tree;
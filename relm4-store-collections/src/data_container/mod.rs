@@ -15,6 +15,7 @@
 mod tests;
 
 mod window_changeset;
+mod window_patch;
 
 use reexport::log;
 
@@ -28,6 +29,7 @@ use std::ops::Range;
 use record::Id;
 
 pub use window_changeset::WindowChangeset;
+pub use window_patch::WindowPatch;
 
 /// Data container for the store view implementation
 /// 
@@ -97,6 +99,8 @@ where
     order: Vec<Id<Record>>,
     /// Maximum number of elements in the data container
     max_size: usize,
+    /// Order of records reported by the last [DataContainer::patch]
+    rendered: Vec<Id<Record>>,
 }
 
 impl<Record> DataContainer<Record>
@@ -109,6 +113,7 @@ where
             data: HashMap::default(),
             order: Vec::default(),
             max_size,
+            rendered: Vec::default(),
         };
 
         dc.invariants();
@@ -498,18 +503,14 @@ where
             }
         }
 
-        // we copy from the back of the left_records_len, so if it's larger first records on the list are ignored
-        if left_records_len > 0 {
-            let last_idx_in_left = left_records_len - 1;
-            for idx in 0..left_move_size {
-                let left_idx = last_idx_in_left - idx;
-
-                let record = left_records[left_idx].clone();
-                let id = record.get_id();
-                self.order[idx] = id;
-                self.data.insert(id, record);
-                changeset.add(id);
-            }
+        // we copy from the back of the left_records, so if it's larger first records on the list are ignored
+        let first_idx_in_left = left_records_len - left_move_size;
+        for idx in 0..left_move_size {
+            let record = left_records[first_idx_in_left + idx].clone();
+            let id = record.get_id();
+            self.order[idx] = id;
+            self.data.insert(id, record);
+            changeset.add(id);
         }
 
         //we perform move of values being to the right of removed range 
@@ -572,6 +573,50 @@ where
         self.invariants();
     }
 
+    /// Changes the maximum number of records in the container
+    /// 
+    /// First `drop_left` records are removed, `left` records are put in front of the remaining ones and `right`
+    /// records are appended. Records which don't fit into the new `max_size` are removed from the end, `left` records
+    /// taking precedence over the ones already in the container. Records which stay in the container are left alone.
+    /// 
+    /// - **changeset** structure holding information which elements of the view require update
+    /// - **max_size** new maximum number of records in the container
+    /// - **drop_left** number of records to be removed from the beginning of the container
+    /// - **left** ordered vector of records to be put in front of the container
+    /// - **right** ordered vector of records to be appended at the end of the container
+    pub fn resize(
+        &mut self,
+        changeset: &mut WindowChangeset<Record>,
+        max_size: usize,
+        drop_left: usize,
+        left: Vec<Record>,
+        right: Vec<Record>,
+    ) {
+        let drop_left = min(drop_left, self.order.len());
+        self.mark_removed(changeset, 0..drop_left);
+        self.order.drain(0..drop_left);
+
+        let left_len = min(left.len(), max_size);
+        let kept = min(self.order.len(), max_size - left_len);
+        self.mark_removed(changeset, kept..self.order.len());
+        self.order.truncate(kept);
+
+        self.max_size = max_size;
+
+        let kept = std::mem::take(&mut self.order);
+        for record in left.into_iter().take(left_len) {
+            self.push(changeset, record);
+        }
+        self.order.extend(kept);
+
+        let free_space = max_size - self.order.len();
+        for record in right.into_iter().take(free_space) {
+            self.push(changeset, record);
+        }
+
+        self.invariants();
+    }
+
     /// Returns current length of the container
     pub fn len(&self) -> usize {
         self.order.len()
@@ -590,6 +635,72 @@ where
         }
     }
 
+    /// Replaces the record known as `old` with the `record` which has a new id
+    /// 
+    /// Record keeps it's position. If `old` id is not in the container nothing happens. Change of the id is added to
+    /// the `changeset` so the view can keep the widgets of the record.
+    pub fn change_id(&mut self, changeset: &mut WindowChangeset<Record>, old: &Id<Record>, record: Record) {
+        if self.data.remove(old).is_none() {
+            return
+        }
+
+        let new = record.get_id();
+        self.data.insert(new, record);
+        for id in self.order.iter_mut().chain(self.rendered.iter_mut()).filter(|id| *id == old) {
+            *id = new;
+        }
+        changeset.change_id(*old, new);
+
+        self.invariants();
+    }
+
+    /// Replaces records starting at `position` with `records` after the store changed their order
+    /// 
+    /// Length of the container doesn't change. Records which kept their place are left alone, records which moved
+    /// are marked as updated so their widgets are reused, records new to the container are marked as added and
+    /// records which left the container are marked as removed.
+    pub fn reorder(&mut self, changeset: &mut WindowChangeset<Record>, position: usize, records: Vec<Record>) {
+        let end = min(position + records.len(), self.order.len());
+        if position >= end {
+            return
+        }
+
+        let old: HashSet<Id<Record>> = self.order[position..end].iter().copied().collect();
+        let mut left = old.clone();
+
+        for (idx, record) in (position..end).zip(records) {
+            let id = record.get_id();
+            left.remove(&id);
+
+            if self.order[idx] != id {
+                if old.contains(&id) {
+                    changeset.update(id);
+                }
+                else {
+                    changeset.add(id);
+                }
+                self.order[idx] = id;
+            }
+            self.data.insert(id, record);
+        }
+
+        for id in left {
+            changeset.remove(id);
+            self.data.remove(&id);
+        }
+
+        self.invariants();
+    }
+
+    /// Adds to the `changeset` operations turning the order reported by the previous call into the current one
+    /// 
+    /// View applies them to the existing widgets, so records which moved within the container keep their widgets.
+    /// See [WindowPatch] for details.
+    pub fn patch(&mut self, changeset: &mut WindowChangeset<Record>) {
+        changeset.patch = window_patch::diff(&self.rendered, &self.order);
+        self.rendered.clone_from(&self.order);
+    }
+
     /// Returns `nth` record id as data are ordered
     pub fn get_record_id_at(&self, nth: usize) -> &Id<Record>{
         &self.order[nth]
@@ -600,6 +711,15 @@ where
         self.data.get(id)
     }
 
+    /// adds record at the end of the container and marks it as added in changeset
+    #[inline]
+    fn push(&mut self, changeset: &mut WindowChangeset<Record>, record: Record) {
+        let id = record.get_id();
+        changeset.add(id);
+        self.order.push(id);
+        self.data.insert(id, record);
+    }
+
     /// marks records as removed in changeset
     /// removes record from data
     /// doesn't remove record from order
@@ -0,0 +1,63 @@
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+
+use crate::WindowChangeset;
+
+use super::test_data::TestData;
+
+#[test]
+fn change_id_keeps_position() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    let old = records[1].get_id();
+    let record = records[1].clone().permanent();
+    let new = record.get_id();
+
+    container.change_id(&mut changeset, &old, record);
+
+    assert_eq!(container.len(), 3);
+    assert_eq!(*container.get_record_id_at(1), new);
+    assert!(container.get_record(&old).is_none());
+    assert!(container.get_record(&new).is_some());
+
+    assert_eq!(changeset.ids_changed.get(&old), Some(&new));
+    assert!(changeset.update_contains(&new));
+    assert!(!changeset.add_contains(&new));
+    assert!(!changeset.remove_contains(&old));
+}
+
+#[test]
+fn change_id_of_added_record_doesnt_rekey_widgets() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+    changeset.add(records[2].get_id());
+
+    let old = records[2].get_id();
+    let record = records[2].clone().permanent();
+    let new = record.get_id();
+
+    container.change_id(&mut changeset, &old, record);
+
+    assert!(changeset.add_contains(&new));
+    assert!(!changeset.add_contains(&old));
+    assert!(changeset.ids_changed.is_empty(), "Widget for the record doesn't exist yet");
+}
+
+#[test]
+fn change_id_of_unknown_record_is_ignored() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    let stranger = TestRecord::constant("Not in the container");
+    let old = stranger.get_id();
+
+    container.change_id(&mut changeset, &old, stranger.permanent());
+
+    assert_eq!(container.len(), 3);
+    for (idx, record) in records.iter().enumerate() {
+        assert_eq!(*container.get_record_id_at(idx), record.get_id());
+    }
+    assert!(changeset.ids_changed.is_empty());
+    assert!(changeset.ids_to_update.is_empty());
+}
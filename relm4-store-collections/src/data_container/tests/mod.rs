@@ -1,10 +1,18 @@
 //! Data container tests module
 
+mod change_id;
+
 mod insert_left;
 mod insert_right;
 
 mod remove_left;
 mod remove_right;
 
+mod patch;
+
+mod reorder;
+
+mod resize;
+
 ///helper crate
 mod test_data;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+
+use crate::WindowChangeset;
+use crate::WindowPatch;
+
+use super::test_data::TestData;
+
+/// Returns the container with all records reported by the patch already
+fn rendered(records_cnt: usize) -> TestData {
+    let mut data = TestData::new(records_cnt, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+    data.container.patch(&mut changeset);
+    data
+}
+
+#[test]
+fn first_patch_inserts_all_records() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.patch(&mut changeset);
+
+    let expected: Vec<_> = records.iter()
+        .enumerate()
+        .map(|(at, record)| WindowPatch::Insert{at, id: record.get_id()})
+        .collect();
+    assert_eq!(changeset.patch, expected);
+}
+
+#[test]
+fn patch_without_changes_is_empty() {
+    let TestData{ mut container, .. } = rendered(3);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.patch(&mut changeset);
+
+    assert!(changeset.patch.is_empty());
+}
+
+#[test]
+fn record_moved_within_container_is_moved() {
+    let TestData{ records, mut container } = rendered(8);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    // [0, 1, 2, 3, 4, 5, 6, 7] => [0, 1, 3, 4, 5, 6, 7, 2]
+    let mut moved: Vec<TestRecord> = records[3..8].to_vec();
+    moved.push(records[2].clone());
+    container.reorder(&mut changeset, 2, moved);
+    container.patch(&mut changeset);
+
+    assert_eq!(changeset.patch, vec![WindowPatch::Move{from: 2, to: 7, id: records[2].get_id()}]);
+}
+
+#[test]
+fn removed_and_added_records_are_reported_with_indexes() {
+    let TestData{ records, mut container } = rendered(5);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    let stranger = TestRecord::constant("Moved into the window");
+
+    // [0, 1, 2, 3, 4] => [0, 2, stranger, 4]
+    container.remove_right(&mut changeset, 3, 1, vec![]);
+    container.reorder(&mut changeset, 1, vec![records[2].clone(), stranger.clone()]);
+    container.patch(&mut changeset);
+
+    assert_eq!(
+        changeset.patch,
+        vec![
+            WindowPatch::Remove{at: 3, id: records[3].get_id()},
+            WindowPatch::Remove{at: 1, id: records[1].get_id()},
+            WindowPatch::Insert{at: 2, id: stranger.get_id()},
+        ]
+    );
+}
+
+#[test]
+fn reversed_records_move_all_but_one() {
+    let TestData{ records, mut container } = rendered(4);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.reorder(&mut changeset, 0, records.iter().rev().cloned().collect());
+    container.patch(&mut changeset);
+
+    let moves = changeset.patch.iter()
+        .filter(|patch| matches!(patch, WindowPatch::Move{..}))
+        .count();
+    assert_eq!(moves, 3, "Only one record can keep its widget in place, got {:?}", changeset.patch);
+    assert_eq!(changeset.patch.len(), 3);
+}
+
+#[test]
+fn changed_id_is_not_reported_as_remove_and_insert() {
+    let TestData{ records, mut container } = rendered(3);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    let record = records[1].clone().permanent();
+    container.change_id(&mut changeset, &records[1].get_id(), record);
+    container.patch(&mut changeset);
+
+    assert!(changeset.patch.is_empty());
+}
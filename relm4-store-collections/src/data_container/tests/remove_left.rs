@@ -839,6 +839,28 @@ mod records_3 {
         assert!(changeset.add_contains(&right_records[1].get_id()));
     }
 
+    #[test]
+    fn remove_two_last_elements_insert_2_0() {
+        let TestData{ records, mut container } = TestData::new(RECORDS_CNT, MAX_SIZE);
+        let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+        let left_records = vec![
+            TestRecord::since("Added record 1", 1),
+            TestRecord::since("Added record 2", 1),
+        ];
+        let right_records = vec![];
+
+        container.remove_left(&mut changeset, 3, 2, left_records.clone(), right_records);
+
+        assert_eq!(container.len(), 3);
+        assert_eq!(container.order[0], left_records[0].get_id(), "Left records must keep their order");
+        assert_eq!(container.order[1], left_records[1].get_id(), "Left records must keep their order");
+        assert_eq!(container.order[2], records[0].get_id());
+        assert!(changeset.remove_contains(&records[1].get_id()));
+        assert!(changeset.remove_contains(&records[2].get_id()));
+        assert!(changeset.add_contains(&left_records[0].get_id()));
+        assert!(changeset.add_contains(&left_records[1].get_id()));
+    }
+
     #[test]
     fn remove_all_non_full() {
         let TestData{ records: _, mut container } = TestData::new(RECORDS_CNT, MAX_SIZE);
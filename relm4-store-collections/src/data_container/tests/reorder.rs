@@ -0,0 +1,60 @@
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+
+use crate::WindowChangeset;
+
+use super::test_data::TestData;
+
+#[test]
+fn reorder_moves_records_in_place() {
+    let TestData{ records, mut container } = TestData::new(5, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    // [0, 1, 2, 3, 4] => [0, 3, 2, 1, 4]
+    container.reorder(&mut changeset, 1, vec![records[3].clone(), records[2].clone(), records[1].clone()]);
+
+    let order: Vec<_> = container.ordered_record_ids().copied().collect();
+    let expected: Vec<_> = [0, 3, 2, 1, 4].iter().map(|idx| records[*idx].get_id()).collect();
+    assert_eq!(order, expected);
+
+    assert!(changeset.update_contains(&records[1].get_id()));
+    assert!(changeset.update_contains(&records[3].get_id()));
+    assert!(!changeset.update_contains(&records[2].get_id()), "Record which kept its place doesn't need update");
+    assert!(changeset.ids_to_add.is_empty());
+    assert!(changeset.ids_to_remove.is_empty());
+}
+
+#[test]
+fn reorder_brings_records_from_outside() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    let stranger = TestRecord::constant("Moved into the window");
+
+    // [0, 1, 2] => [0, stranger, 1]
+    container.reorder(&mut changeset, 1, vec![stranger.clone(), records[1].clone()]);
+
+    let order: Vec<_> = container.ordered_record_ids().copied().collect();
+    assert_eq!(order, vec![records[0].get_id(), stranger.get_id(), records[1].get_id()]);
+
+    assert!(changeset.add_contains(&stranger.get_id()));
+    assert!(changeset.update_contains(&records[1].get_id()));
+    assert!(changeset.remove_contains(&records[2].get_id()));
+    assert!(container.get_record(&records[2].get_id()).is_none());
+}
+
+#[test]
+fn reorder_outside_of_container_is_ignored() {
+    let TestData{ records, mut container } = TestData::new(3, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.reorder(&mut changeset, 3, vec![TestRecord::constant("Outside")]);
+
+    assert_eq!(container.len(), 3);
+    for (idx, record) in records.iter().enumerate() {
+        assert_eq!(*container.get_record_id_at(idx), record.get_id());
+    }
+    assert!(changeset.ids_to_add.is_empty());
+    assert!(changeset.ids_to_update.is_empty());
+    assert!(changeset.ids_to_remove.is_empty());
+}
@@ -0,0 +1,94 @@
+//! Contains tests for `[DataContainer::resize]`
+
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+
+use crate::WindowChangeset;
+
+use super::test_data::TestData;
+
+/// Returns new records, one for every record in `records`
+fn fresh(records: &[TestRecord]) -> Vec<TestRecord> {
+    records.iter()
+        .map(|r| TestRecord::constant(&format!("New {}", r.label)))
+        .collect()
+}
+
+#[test]
+fn grow_appends_records() {
+    let TestData{ records, mut container } = TestData::new(5, 5);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+    let right = fresh(&records[0..3]);
+
+    container.resize(&mut changeset, 8, 0, vec![], right.clone());
+
+    container.invariants();
+    assert_eq!(container.len(), 8);
+    for (idx, record) in records.iter().chain(right.iter()).enumerate() {
+        assert_eq!(container.order[idx], record.get_id());
+    }
+    assert_eq!(changeset.ids_to_add.len(), 3);
+    assert!(changeset.ids_to_remove.is_empty());
+}
+
+#[test]
+fn grow_is_limited_by_max_size() {
+    let TestData{ records, mut container } = TestData::new(5, 5);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+    let right = fresh(&records);
+
+    container.resize(&mut changeset, 7, 0, vec![], right.clone());
+
+    container.invariants();
+    assert_eq!(container.len(), 7);
+    assert_eq!(container.order[6], right[1].get_id());
+    assert_eq!(changeset.ids_to_add.len(), 2);
+}
+
+#[test]
+fn shrink_removes_records_from_the_end() {
+    let TestData{ records, mut container } = TestData::new(10, 10);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.resize(&mut changeset, 4, 0, vec![], vec![]);
+
+    container.invariants();
+    assert_eq!(container.len(), 4);
+    for (idx, record) in records.iter().take(4).enumerate() {
+        assert_eq!(container.order[idx], record.get_id());
+    }
+    assert_eq!(changeset.ids_to_remove.len(), 6);
+    assert!(changeset.ids_to_remove.contains(&records[9].get_id()));
+    assert!(changeset.ids_to_add.is_empty());
+}
+
+#[test]
+fn drop_left_and_prepend() {
+    let TestData{ records, mut container } = TestData::new(6, 6);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+    let left = fresh(&records[0..2]);
+
+    // [0, 1, 2, 3, 4, 5] => [n0, n1, 3, 4]
+    container.resize(&mut changeset, 4, 3, left.clone(), vec![]);
+
+    container.invariants();
+    let expected = vec![left[0].get_id(), left[1].get_id(), records[3].get_id(), records[4].get_id()];
+    assert_eq!(container.order, expected);
+    assert_eq!(changeset.ids_to_remove.len(), 4, "Three records from the left and one from the right must be removed");
+    assert_eq!(changeset.ids_to_add.len(), 2);
+}
+
+#[test]
+fn kept_records_are_not_reported() {
+    let TestData{ records, mut container } = TestData::new(5, 5);
+    let mut changeset: WindowChangeset<TestRecord> = WindowChangeset::default();
+
+    container.resize(&mut changeset, 3, 1, vec![], vec![]);
+
+    for record in &records[1..4] {
+        let id = record.get_id();
+        assert!(!changeset.ids_to_add.contains(&id));
+        assert!(!changeset.ids_to_remove.contains(&id));
+        assert!(!changeset.ids_to_update.contains(&id));
+    }
+}
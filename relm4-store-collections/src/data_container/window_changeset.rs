@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 use record::Id;
 
+use super::WindowPatch;
+
 /// WindowChangeset describes how the store view window has changed in response to the changes in the store
 /// 
 /// Altho fields are public you should use methods as much as possible
@@ -16,8 +19,12 @@ where
     pub ids_to_add: HashSet<Id<Record>>,
     /// Set of record id's of widgets which needs to be updated
     pub ids_to_update: HashSet<Id<Record>>,
+    /// Widgets which must be found under the new id, maps id used when widget was created to the current one
+    pub ids_changed: HashMap<Id<Record>, Id<Record>>,
     /// Marks changeset as reload
     pub reload: bool,
+    /// Ordered operations on the widgets of the view, see [WindowPatch]
+    pub patch: Vec<WindowPatch<Record>>,
 }
 
 impl<Record> WindowChangeset<Record> 
@@ -42,6 +49,31 @@ where
         self.ids_to_remove.insert(id);
     }
 
+    /// marks that record known as `old` is now known as `new`
+    /// 
+    /// Pending changes of the `old` id are moved to the `new` one. If the record has a widget already, widget is
+    /// marked to be updated so it can show the record with the new id.
+    pub fn change_id(&mut self, old: Id<Record>, new: Id<Record>) {
+        if self.ids_to_add.remove(&old) {
+            // widget doesn't exist yet so there is nothing to re-key
+            self.ids_to_add.insert(new);
+            return
+        }
+
+        if self.ids_to_remove.remove(&old) {
+            self.ids_to_remove.insert(new);
+        }
+        self.ids_to_update.remove(&old);
+        self.ids_to_update.insert(new);
+
+        // id might have been changed already in this changeset
+        let original = self.ids_changed.iter()
+            .find(|(_, current)| **current == old)
+            .map(|(original, _)| *original)
+            .unwrap_or(old);
+        self.ids_changed.insert(original, new);
+    }
+
     /// returns `true` if id is marked as added
     pub fn add_contains(&self, id: &Id<Record>) -> bool {
         self.ids_to_add.contains(id)
@@ -67,7 +99,9 @@ where
             ids_to_remove: HashSet::new(),
             ids_to_add: HashSet::new(),
             ids_to_update: HashSet::new(),
+            ids_changed: HashMap::new(),
             reload: false,
+            patch: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use record::Id;
+
+/// Single operation on the widgets of the view
+///
+/// List of patches describes how to go from the order of widgets shown so far to the current order of records. Patches
+/// are ordered so they can be applied one by one:
+///
+/// 1. [WindowPatch::Remove] in descending order of `at`. Indexes are in the old order.
+/// 2. [WindowPatch::Insert] and [WindowPatch::Move] in ascending order of the target index. Target index is in the new
+///    order, so widget must be put right after the widget of the record at the `target - 1` or in front of the view for `0`
+///
+/// Records which are not mentioned keep their widgets where they are.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowPatch<Record>
+where
+    Record: 'static + record::Record,
+{
+    /// Widget of the record at the `at` in the old order must be removed
+    Remove{
+        /// Index in the old order
+        at: usize,
+        /// Id of the removed record
+        id: Id<Record>,
+    },
+    /// Widget of the record must be created and put at the `at`
+    Insert{
+        /// Index in the new order
+        at: usize,
+        /// Id of the added record
+        id: Id<Record>,
+    },
+    /// Existing widget of the record must be moved
+    Move{
+        /// Index in the old order
+        from: usize,
+        /// Index in the new order
+        to: usize,
+        /// Id of the moved record
+        id: Id<Record>,
+    },
+}
+
+/// Returns patches turning `old` order into the `new` one
+///
+/// Records present in both orders which form the longest increasing subsequence of old indexes keep their place, all
+/// other kept records are moved. This way the number of moved widgets is minimal.
+pub(crate) fn diff<Record>(old: &[Id<Record>], new: &[Id<Record>]) -> Vec<WindowPatch<Record>>
+where
+    Record: 'static + record::Record,
+{
+    let old_index: HashMap<Id<Record>, usize> = old.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
+    let new_ids: HashSet<Id<Record>> = new.iter().copied().collect();
+
+    let mut patches = Vec::new();
+
+    for (at, id) in old.iter().enumerate().rev() {
+        if !new_ids.contains(id) {
+            patches.push(WindowPatch::Remove{at, id: *id});
+        }
+    }
+
+    // (position in the new order, position in the old order) of the records present in both orders
+    let kept: Vec<(usize, usize)> = new.iter()
+        .enumerate()
+        .filter_map(|(to, id)| old_index.get(id).map(|from| (to, *from)))
+        .collect();
+    let stable = longest_increasing_subsequence(&kept);
+
+    let mut kept = kept.into_iter().peekable();
+    for (at, id) in new.iter().enumerate() {
+        match kept.peek() {
+            Some((to, from)) if *to == at => {
+                if !stable.contains(to) {
+                    patches.push(WindowPatch::Move{from: *from, to: at, id: *id});
+                }
+                kept.next();
+            },
+            _ => patches.push(WindowPatch::Insert{at, id: *id}),
+        }
+    }
+
+    patches
+}
+
+/// Returns set of the first elements of pairs for which second elements are the longest increasing subsequence
+fn longest_increasing_subsequence(pairs: &[(usize, usize)]) -> HashSet<usize> {
+    // tails[len] is index in `pairs` of the smallest tail of increasing subsequence of length `len + 1`
+    let mut tails: Vec<usize> = Vec::with_capacity(pairs.len());
+    let mut previous: Vec<Option<usize>> = vec![None; pairs.len()];
+
+    for (idx, (_, value)) in pairs.iter().enumerate() {
+        let len = tails.partition_point(|tail| pairs[*tail].1 < *value);
+        if len > 0 {
+            previous[idx] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(idx);
+        }
+        else {
+            tails[len] = idx;
+        }
+    }
+
+    let mut result = HashSet::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(idx) = current {
+        result.insert(pairs[idx].0);
+        current = previous[idx];
+    }
+
+    result
+}
@@ -15,15 +15,12 @@
 )]
 
 mod data_container;
+mod tree;
 
 pub use data_container::DataContainer;
 pub use data_container::WindowChangeset;
+pub use data_container::WindowPatch;
 
-// mod tree;
-
-// pub use tree::Tree;
-// pub use tree::TreeConfiguration;
-
-
-// // REMOVE FROM EXPORTS
-// pub use tree::TreeImpl;
\ No newline at end of file
+pub use tree::Iter as TreeIter;
+pub use tree::Tree;
+pub use tree::TreeConfiguration;
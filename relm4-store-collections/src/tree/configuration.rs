@@ -15,13 +15,12 @@ impl TreeConfiguration {
     /// Creates new instance of this structure
     /// 
     /// [Tree] using this configuration will be of the given `order`
+    /// 
+    /// Panics if `order` is less then `3`
     pub fn new(order: usize) -> Self {
-        let half = if order % 2 == 0 {
-            order / 2
-        }
-        else {
-            (order + 1) / 2
-        };
+        assert!(order >= 3, "Order of the tree must be at least 3");
+
+        let half = order.div_ceil(2);
 
         Self{
             split_at: half,
@@ -1,109 +1,61 @@
-use super::*;
+//! Inserting into the tree
 
+use super::*;
 
-impl<Value, Comparator> TreeImpl<Value, Comparator> {
+use crate::Tree;
+use crate::TreeConfiguration;
 
-    // fn add_internal(&mut self, e: Value, index: Option<usize>) -> &Value {
-    //     let mut n: *mut Node<Value, Comparator>;
-    //     let mut np: *mut *mut Node<Value, Comparator>;
-    //     let mut left: *mut Node<Value, Comparator>;
-    //     let mut right: *mut Node<Value, Comparator>;
-    //     let mut orig_e: *const Value = &e;
-    //     let c: usize;
-    //     let lcount: usize;
-    //     let rcount: usize;
+impl<K: Ord, V> Node<K, V> {
+    /// Inserts new element into the subtree
+    ///
+    /// Node must not be full and key must not be present in the subtree
+    fn insert_non_full(&mut self, key: K, value: V, configuration: &TreeConfiguration) {
+        let mut node = self;
 
-    //     let node_layout = Layout::new::<Node<Value, Comparator>>();
+        loop {
+            node.size += 1;
 
-        
-    //     if self.count == 0 {
-    //         assert!(node_layout.size() <= isize::MAX as usize, "Unable to allocate root node. Node size is too big");
-    //         // root is empty, no data in the tree yet!
-    //         let root_ptr = unsafe {
-    //             alloc(node_layout) as *mut Node<Value, Comparator>
-    //         };
+            let mut idx = match node.keys.binary_search(&key) {
+                Ok(_) => unreachable!("key is already present in the tree"),
+                Err(idx) => idx,
+            };
 
-    //         let mut root = unsafe { NonNull::new_unchecked(root_ptr) };
-            
-    //         let result = unsafe{
-    //             let r = root.as_mut();
-    //             r.leaf = false;
-    //             r.elems.push_front(e);
-    //             r.elems.get(0).unwrap()
-    //         };
+            if node.is_leaf() {
+                node.keys.insert(idx, key);
+                node.values.insert(idx, value);
+                return
+            }
 
-    //         self.root = root;
-    //         self.count += 1;
+            if node.kids[idx].keys.len() == configuration.capacity() {
+                node.split_kid(idx, configuration.kv_idx_center());
 
-    //         return result
-    //     }
-    //     else {
-    //         unsafe{
-    //             self.root.as_mut().insert_non_full(e, index)
-    //         }
-    //     }
-    // }
+                if node.keys[idx] < key {
+                    idx += 1;
+                }
+            }
 
+            node = &mut node.kids[idx];
+        }
+    }
 }
 
-impl<Value, Comparator> Node<Value, Comparator> {
-
-    // Finds index in the vec of kids
-    // 
-    // If this is leaf node it will be an insertion index
-    // If this is not a leaf node then it will be index of the kid to perform insert
-    // fn find_index_in_node(&self, e: &Value) -> usize {
-    //     self.elems.
-    // }
-
-    // A utility function to insert a new key in this node
-    // The assumption is, the node must be non-full when this
-    // function is called
-    // fn insert_non_full(&mut self, e: Value, index: Option<usize>) -> &Value {
-        
-    // }
-
-    /* void BTreeNode::insertNonFull(int k)
-    {
-        # Initialize index as index of rightmost element
-        int i = n-1;
-    
-        # If this is a leaf node
-        if (leaf == true)
-        {
-            # The following loop does two things
-            #  a) Finds the location of new key to be inserted
-            #  b) Moves all greater keys to one place ahead
-            while (i >= 0 && keys[i] > k)
-            {
-                keys[i+1] = keys[i];
-                i--;
-            }
-    
-            # Insert the new key at found location
-            keys[i+1] = k;
-            n = n+1;
+impl<K: Ord, V> Tree<K, V> {
+    /// Inserts the value under the key
+    ///
+    /// If the key was already present in the tree, value is replaced and old value is returned
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(std::mem::replace(old, value))
         }
-        else # If this node is not leaf
-        {
-            # Find the child which is going to have the new key
-            while (i >= 0 && keys[i] > k)
-                i--;
-    
-            # See if the found child is full
-            if (C[i+1]->n == 2*t-1)
-            {
-                # If the child is full, then split it
-                splitChild(i+1, C[i+1]);
-    
-                # After split, the middle key of C[i] goes up and
-                # C[i] is split into two.  See which of the two
-                # is going to have the new key
-                if (keys[i+1] < k)
-                    i++;
-            }
-            C[i+1]->insertNonFull(k);
+
+        if self.root.keys.len() == self.configuration.capacity() {
+            let old_root = std::mem::replace(&mut self.root, Node::new(&self.configuration));
+            self.root.size = old_root.size;
+            self.root.kids.push(old_root);
+            self.root.split_kid(0, self.configuration.kv_idx_center());
         }
-    }*/
 
+        self.root.insert_non_full(key, value, &self.configuration);
+        None
+    }
 }
@@ -0,0 +1,130 @@
+//! Iterating over the tree in order of the keys
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::iter::FusedIterator;
+
+use super::*;
+
+use crate::Tree;
+
+/// Iterator over elements of the [Tree] in order of the keys
+///
+/// Created by [Tree::iter] and [Tree::iter_from]
+pub struct Iter<'t, K, V> {
+    /// Path from the root to the next element
+    ///
+    /// Each entry is a node and index of the next element to return from this node
+    stack: Vec<(&'t Node<K, V>, usize)>,
+    remaining: usize,
+}
+
+impl<'t, K, V> Iter<'t, K, V> {
+    fn new(root: &'t Node<K, V>, mut nth: usize) -> Self {
+        let mut stack = Vec::new();
+        let remaining = root.size.saturating_sub(nth);
+
+        if remaining > 0 {
+            let mut node = root;
+
+            'descend: loop {
+                if node.is_leaf() {
+                    stack.push((node, nth));
+                    break;
+                }
+
+                for (idx, kid) in node.kids.iter().enumerate() {
+                    if nth <= kid.size {
+                        stack.push((node, idx));
+                        if nth == kid.size {
+                            break 'descend;
+                        }
+                        node = kid;
+                        continue 'descend;
+                    }
+                    nth -= kid.size + 1;
+                }
+
+                unreachable!("position is out of bounds of the subtree")
+            }
+        }
+
+        Self{
+            stack,
+            remaining,
+        }
+    }
+
+    /// Pushes path to the smallest element in the subtree
+    fn descend_left(&mut self, mut node: &'t Node<K, V>) {
+        loop {
+            self.stack.push((node, 0));
+            match node.kids.first() {
+                Some(kid) => node = kid,
+                None => return,
+            }
+        }
+    }
+}
+
+impl<'t, K, V> Iterator for Iter<'t, K, V> {
+    type Item = (&'t K, &'t V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, idx)) = self.stack.pop() {
+            if idx < node.keys.len() {
+                self.stack.push((node, idx+1));
+                if let Some(kid) = node.kids.get(idx+1) {
+                    self.descend_left(kid);
+                }
+
+                self.remaining -= 1;
+                return Some((&node.keys[idx], &node.values[idx]))
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'t, K, V> ExactSizeIterator for Iter<'t, K, V> {}
+
+impl<'t, K, V> FusedIterator for Iter<'t, K, V> {}
+
+/// Formats [`Iter`] for debug format `{:?}`
+impl<'t, K, V> Debug for Iter<'t, K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Iter")
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<K, V> Tree<K, V> {
+    /// Returns iterator over all elements of the tree in order of the keys
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(&self.root, 0)
+    }
+
+    /// Returns iterator over elements of the tree starting at given position
+    ///
+    /// Finding the starting point is `O(log n)`, so showing the page of the data is cheap no matter how far from
+    /// the start the page is
+    pub fn iter_from(&self, nth: usize) -> Iter<'_, K, V> {
+        Iter::new(&self.root, nth)
+    }
+}
+
+impl<'t, K, V> IntoIterator for &'t Tree<K, V> {
+    type Item = (&'t K, &'t V);
+    type IntoIter = Iter<'t, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -1,59 +1,182 @@
 //! Craziness is here
-//! 
-//! Here we have an implementation of our tree. It's unsafe as c can be
-//! 
-//! This implementation is based of `tree234` by Simon Tatham. Original can
-//! be found on [Simon Tatham's home page](https://www.chiark.greenend.org.uk/~sgtatham/algorithms/tree234.c)
-//! 
-//! Some ideas was taken from "Introduction to algorithms, 2nd edition" by Cormen, Leiserson, Rivest, Stein
-//! 
+//!
+//! Here we have an implementation of our tree. Every node knows the number of elements in its subtree, which
+//! allows to find the element by position and position of the element in `O(log n)`.
+//!
+//! Idea of counted tree is based of `tree234` by Simon Tatham. Original can be found on
+//! [Simon Tatham's home page](https://www.chiark.greenend.org.uk/~sgtatham/algorithms/tree234.c)
+//!
+//! Insertion and deletion are done in single pass from the root down as described in "Introduction to algorithms,
+//! 2nd edition" by Cormen, Leiserson, Rivest, Stein. Before we step into a node we make sure it can take one more
+//! element (insertion) or it can lose one (deletion).
+//!
 //! Internally whatever is below this module is part of the implementation of this tree
 //! I've split it up so I can understand it in parts
 
-mod drop;
 mod insert;
+mod iter;
 mod len;
 mod new;
+mod remove;
+mod search;
 
-// use std::alloc::alloc;
-// use std::alloc::Layout;
-use std::collections::VecDeque;
-use std::marker::PhantomData;
-use std::ptr::NonNull;
-
-use crate::TreeConfiguration;
-
-/// Implementation of the B+Tree
-#[derive(Debug)]
-pub struct TreeImpl<Value, Comparator> {
-    root: NonNull<Node<Value, Comparator>>,
-    _configuration: TreeConfiguration,
-    count: usize,
-    _comparator: PhantomData<*mut Comparator>, //invariance in comparator
-    _value: PhantomData<Value>, // forces covariance over Value
+pub use iter::Iter;
+
+/// Node of the tree
+///
+/// Node is a leaf if it has no kids. Inner node with `n` keys has `n+1` kids.
+#[derive(Clone, Debug)]
+pub(crate) struct Node<K, V> {
+    /// Number of elements in the subtree starting at this node
+    size: usize,
+    keys: Vec<K>,
+    values: Vec<V>,
+    kids: Vec<Node<K, V>>,
 }
 
-struct Node<Value, Comparator> {
-    _parent: *const Node<Value, Comparator>,
+impl<K, V> Node<K, V> {
+    fn is_leaf(&self) -> bool {
+        self.kids.is_empty()
+    }
 
-    _leaf: bool,
+    /// Splits full kid at `idx` into two, moving it's center element into this node
+    ///
+    /// `center` is the index of the element going up
+    fn split_kid(&mut self, idx: usize, center: usize) {
+        let kid = &mut self.kids[idx];
 
-    kids: VecDeque<CountedNode<Value, Comparator>>,
+        let keys = kid.keys.split_off(center + 1);
+        let values = kid.values.split_off(center + 1);
+        let kids = if kid.is_leaf() {
+            Vec::new()
+        }
+        else {
+            kid.kids.split_off(center + 1)
+        };
 
-    elems: VecDeque<Value>,
-    _comparator: PhantomData<*mut Comparator>, //invariance in comparator
-    _value: PhantomData<Value>, // forces covariance over Value
-}
+        let key = kid.keys.pop().expect("center element must exist");
+        let value = kid.values.pop().expect("center element must exist");
+
+        let right_size = keys.len() + kids.iter().map(|kid| kid.size).sum::<usize>();
+        kid.size -= right_size + 1;
+
+        let right = Node{
+            size: right_size,
+            keys,
+            values,
+            kids,
+        };
+
+        self.keys.insert(idx, key);
+        self.values.insert(idx, value);
+        self.kids.insert(idx+1, right);
+    }
+
+    /// Merges kid at `idx + 1` and element at `idx` into kid at `idx`
+    fn merge_kids(&mut self, idx: usize) {
+        let right = self.kids.remove(idx+1);
+        let key = self.keys.remove(idx);
+        let value = self.values.remove(idx);
+
+        let left = &mut self.kids[idx];
+        left.size += right.size + 1;
+        left.keys.push(key);
+        left.keys.extend(right.keys);
+        left.values.push(value);
+        left.values.extend(right.values);
+        left.kids.extend(right.kids);
+    }
 
-struct CountedNode<Value, Comparator> {
-    node: NonNull<Node<Value, Comparator>>,
-    _count: usize,
-    _comparator: PhantomData<*mut Comparator>, //invariance in comparator
-    _value: PhantomData<Value>, // forces covariance over Value
+    /// Moves last element of the kid at `idx` up and element at `idx` down to the kid at `idx+1`
+    fn rotate_right(&mut self, idx: usize) {
+        let left = &mut self.kids[idx];
+        let key = left.keys.pop().expect("left kid can't be empty");
+        let value = left.values.pop().expect("left kid can't be empty");
+        let kid = left.kids.pop();
+        let moved = 1 + kid.as_ref().map(|kid| kid.size).unwrap_or(0);
+        left.size -= moved;
+
+        let key = std::mem::replace(&mut self.keys[idx], key);
+        let value = std::mem::replace(&mut self.values[idx], value);
+
+        let right = &mut self.kids[idx+1];
+        right.keys.insert(0, key);
+        right.values.insert(0, value);
+        if let Some(kid) = kid {
+            right.kids.insert(0, kid);
+        }
+        right.size += moved;
+    }
+
+    /// Moves first element of the kid at `idx+1` up and element at `idx` down to the kid at `idx`
+    fn rotate_left(&mut self, idx: usize) {
+        let right = &mut self.kids[idx+1];
+        let key = right.keys.remove(0);
+        let value = right.values.remove(0);
+        let kid = if right.is_leaf() {
+            None
+        }
+        else {
+            Some(right.kids.remove(0))
+        };
+        let moved = 1 + kid.as_ref().map(|kid| kid.size).unwrap_or(0);
+        right.size -= moved;
+
+        let key = std::mem::replace(&mut self.keys[idx], key);
+        let value = std::mem::replace(&mut self.values[idx], value);
+
+        let left = &mut self.kids[idx];
+        left.keys.push(key);
+        left.values.push(value);
+        if let Some(kid) = kid {
+            left.kids.push(kid);
+        }
+        left.size += moved;
+    }
 }
 
+#[cfg(test)]
+impl<K: Ord, V> crate::Tree<K, V> {
+    /// Panics if structure of the tree is broken
+    pub(crate) fn assert_valid(&self) {
+        let depth = self.root.assert_valid(&self.configuration, true, None, None);
+        assert!(depth > 0);
+    }
+}
 
+#[cfg(test)]
+impl<K: Ord, V> Node<K, V> {
+    /// Checks the subtree returning it's depth
+    fn assert_valid(&self, configuration: &crate::TreeConfiguration, root: bool, min: Option<&K>, max: Option<&K>) -> usize {
+        assert_eq!(self.keys.len(), self.values.len(), "Every key must have a value");
+        assert!(self.keys.len() <= configuration.capacity(), "Node is overfull");
+        if !root {
+            assert!(self.keys.len() >= configuration.min_len_after_split(), "Node is underfull");
+        }
+        assert!(self.keys.windows(2).all(|w| w[0] < w[1]), "Keys in the node must be sorted");
+        assert!(min.map(|min| self.keys.iter().all(|key| min < key)).unwrap_or(true), "Key smaller then parent key");
+        assert!(max.map(|max| self.keys.iter().all(|key| key < max)).unwrap_or(true), "Key bigger then parent key");
 
+        if self.is_leaf() {
+            assert_eq!(self.size, self.keys.len(), "Size of the leaf");
+            return 1
+        }
 
+        assert_eq!(self.kids.len(), self.keys.len() + 1, "Inner node must have one kid more then keys");
+        assert_eq!(
+            self.size,
+            self.keys.len() + self.kids.iter().map(|kid| kid.size).sum::<usize>(),
+            "Size of the inner node"
+        );
 
+        let mut depth = None;
+        for (idx, kid) in self.kids.iter().enumerate() {
+            let min = if idx == 0 { min } else { Some(&self.keys[idx-1]) };
+            let max = self.keys.get(idx).or(max);
+            let kid_depth = kid.assert_valid(configuration, false, min, max);
+            assert_eq!(*depth.get_or_insert(kid_depth), kid_depth, "All leafs must be at the same depth");
+        }
 
+        depth.unwrap_or(0) + 1
+    }
+}
@@ -1,18 +1,37 @@
-use std::mem;
-
 use super::*;
 
-impl<Value, Comparator> TreeImpl<Value, Comparator> {
-    /// Creates new instance of TreeImpl
-    pub fn new(configuration: TreeConfiguration) -> Self {
-        assert!(mem::size_of::<Value>() != 0, "We're not ready to handle ZSTs");
+use crate::Tree;
+use crate::TreeConfiguration;
 
+impl<K, V> Node<K, V> {
+    /// Creates new empty leaf
+    pub(crate) fn new(configuration: &TreeConfiguration) -> Self {
         Self{
-            root: NonNull::dangling(),
-            count: 0,
-            _configuration: configuration,
-            _comparator: PhantomData,
-            _value: PhantomData,
+            size: 0,
+            keys: Vec::with_capacity(configuration.capacity()),
+            values: Vec::with_capacity(configuration.capacity()),
+            kids: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl<K, V> Tree<K, V> {
+    /// Creates new empty tree
+    pub fn new(configuration: TreeConfiguration) -> Self {
+        Self{
+            root: Node::new(&configuration),
+            configuration,
+        }
+    }
+
+    /// Removes all elements from the tree
+    pub fn clear(&mut self) {
+        self.root = Node::new(&self.configuration);
+    }
+}
+
+impl<K, V> Default for Tree<K, V> {
+    fn default() -> Self {
+        Tree::new(TreeConfiguration::default())
+    }
+}
@@ -0,0 +1,109 @@
+//! Removing from the tree
+
+use super::*;
+
+use crate::Tree;
+use crate::TreeConfiguration;
+
+impl<K, V> Node<K, V> {
+    /// Removes element at position `nth` from the subtree
+    ///
+    /// `nth` must be smaller then size of this node. Node must be a root or have more then minimal number of
+    /// elements, so it can lose one.
+    fn remove_nth(&mut self, mut nth: usize, configuration: &TreeConfiguration) -> (K, V) {
+        let min_len = configuration.min_len_after_split();
+        self.size -= 1;
+
+        if self.is_leaf() {
+            return (self.keys.remove(nth), self.values.remove(nth))
+        }
+
+        // find the kid containing the element or the element in this node
+        let mut idx = 0;
+        let mut found = false;
+        for kid in &self.kids {
+            if nth < kid.size {
+                break;
+            }
+            if nth == kid.size {
+                found = true;
+                break;
+            }
+            nth -= kid.size + 1;
+            idx += 1;
+        }
+
+        if found {
+            if self.kids[idx].keys.len() > min_len {
+                let last = self.kids[idx].size - 1;
+                let (key, value) = self.kids[idx].remove_nth(last, configuration);
+                return self.replace(idx, key, value)
+            }
+
+            if self.kids[idx+1].keys.len() > min_len {
+                let (key, value) = self.kids[idx+1].remove_nth(0, configuration);
+                return self.replace(idx, key, value)
+            }
+
+            // element will be at the end of the left kid
+            let nth = self.kids[idx].size;
+            self.merge_kids(idx);
+            return self.kids[idx].remove_nth(nth, configuration)
+        }
+
+        if self.kids[idx].keys.len() <= min_len {
+            if idx > 0 && self.kids[idx-1].keys.len() > min_len {
+                let before = self.kids[idx].size;
+                self.rotate_right(idx-1);
+                nth += self.kids[idx].size - before;
+            }
+            else if idx < self.keys.len() && self.kids[idx+1].keys.len() > min_len {
+                self.rotate_left(idx);
+            }
+            else if idx < self.keys.len() {
+                self.merge_kids(idx);
+            }
+            else {
+                idx -= 1;
+                nth += self.kids[idx].size + 1;
+                self.merge_kids(idx);
+            }
+        }
+
+        self.kids[idx].remove_nth(nth, configuration)
+    }
+
+    /// Replaces element at `idx` returning the old one
+    fn replace(&mut self, idx: usize, key: K, value: V) -> (K, V) {
+        (
+            std::mem::replace(&mut self.keys[idx], key),
+            std::mem::replace(&mut self.values[idx], value),
+        )
+    }
+}
+
+impl<K, V> Tree<K, V> {
+    /// Removes element at the given position
+    pub fn remove_nth(&mut self, nth: usize) -> Option<(K, V)> {
+        if nth >= self.len() {
+            return None
+        }
+
+        let removed = self.root.remove_nth(nth, &self.configuration);
+
+        // root lost its last element, tree gets shorter
+        if self.root.keys.is_empty() && !self.root.is_leaf() {
+            self.root = self.root.kids.pop().expect("root has a kid");
+        }
+
+        Some(removed)
+    }
+}
+
+impl<K: Ord, V> Tree<K, V> {
+    /// Removes element with the given key returning its value
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let nth = self.rank(key)?;
+        self.remove_nth(nth).map(|(_, value)| value)
+    }
+}
@@ -0,0 +1,136 @@
+//! Finding elements by key and by position
+
+use super::*;
+
+use crate::Tree;
+
+impl<K, V> Node<K, V> {
+    /// Finds node and index of the element at position `nth` of this subtree
+    ///
+    /// `nth` must be smaller then size of this node
+    fn select(&self, mut nth: usize) -> (&Self, usize) {
+        let mut node = self;
+
+        'descend: loop {
+            if node.is_leaf() {
+                return (node, nth)
+            }
+
+            for (idx, kid) in node.kids.iter().enumerate() {
+                if nth < kid.size {
+                    node = kid;
+                    continue 'descend;
+                }
+                if nth == kid.size {
+                    return (node, idx)
+                }
+                nth -= kid.size + 1;
+            }
+
+            unreachable!("position is out of bounds of the subtree")
+        }
+    }
+}
+
+impl<K: Ord, V> Node<K, V> {
+    /// Finds node and index of the element with given key
+    fn find(&self, key: &K) -> Option<(&Self, usize)> {
+        let mut node = self;
+
+        loop {
+            match node.keys.binary_search(key) {
+                Ok(idx) => return Some((node, idx)),
+                Err(idx) => {
+                    if node.is_leaf() {
+                        return None
+                    }
+                    node = &node.kids[idx];
+                },
+            }
+        }
+    }
+
+    /// Mutable version of [Node::find]
+    fn find_mut(&mut self, key: &K) -> Option<(&mut Self, usize)> {
+        let mut node = self;
+
+        loop {
+            match node.keys.binary_search(key) {
+                Ok(idx) => return Some((node, idx)),
+                Err(idx) => {
+                    if node.is_leaf() {
+                        return None
+                    }
+                    node = &mut node.kids[idx];
+                },
+            }
+        }
+    }
+}
+
+impl<K, V> Tree<K, V> {
+    /// Returns element at the given position
+    ///
+    /// Elements are counted from `0` in order of their keys
+    pub fn select(&self, nth: usize) -> Option<(&K, &V)> {
+        if nth >= self.len() {
+            return None
+        }
+
+        let (node, idx) = self.root.select(nth);
+        Some((&node.keys[idx], &node.values[idx]))
+    }
+}
+
+impl<K: Ord, V> Tree<K, V> {
+    /// Returns reference to the value kept under the key
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.root.find(key).map(|(node, idx)| &node.values[idx])
+    }
+
+    /// Returns mutable reference to the value kept under the key
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.root.find_mut(key).map(|(node, idx)| &mut node.values[idx])
+    }
+
+    /// Returns true if there is an element with the given key
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.root.find(key).is_some()
+    }
+
+    /// Finds position of the key in the tree
+    ///
+    /// Works like [slice::binary_search]. If the key is present returns `Ok(position)`, otherwise `Err(position)`
+    /// where `position` is the place where element would be inserted
+    pub fn position(&self, key: &K) -> Result<usize, usize> {
+        let mut node = &self.root;
+        let mut position = 0;
+
+        loop {
+            let (found, idx) = match node.keys.binary_search(key) {
+                Ok(idx) => (true, idx),
+                Err(idx) => (false, idx),
+            };
+
+            // all elements of the kids on the left side of `idx` and `idx` elements in this node
+            position += idx + node.kids.iter().take(idx).map(|kid| kid.size).sum::<usize>();
+
+            if found {
+                if let Some(kid) = node.kids.get(idx) {
+                    position += kid.size;
+                }
+                return Ok(position)
+            }
+
+            match node.kids.get(idx) {
+                Some(kid) => node = kid,
+                None => return Err(position),
+            }
+        }
+    }
+
+    /// Returns position of the element with the given key
+    pub fn rank(&self, key: &K) -> Option<usize> {
+        self.position(key).ok()
+    }
+}
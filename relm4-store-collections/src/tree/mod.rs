@@ -2,7 +2,7 @@
 //! 
 //! ## Rationale for custom implementation
 //! 
-//! In [std::collections::BTree] every operation is expressed in terms of key which implements
+//! In [std::collections::BTreeMap] every operation is expressed in terms of key which implements
 //! total order. This is not efficient or useful when you need to deal with user interface.
 //!
 //! For example, if you have a long list of todo tasks looking like
@@ -49,24 +49,28 @@
 //! 1. Description is the text.
 //! 2. Description can be arbitrarily long.
 //! 3. For simplicity case I assume that only small letters are allowed in description (no spaces, 
-//! tabs, etc..). This simplification won't affect the conclusion. It will make some examples easier
-//! to show.
+//!    tabs, etc..). This simplification won't affect the conclusion. It will make some examples easier
+//!    to show.
 //! 
 //! So let's compute distance between some examples
 //! 
 //! * `dist("a", "a") = 0`, by definition
 //! * `dist("a", "aa") = 1`, there is nothing which can go between `a` and `aa` in lexicographical
-//! order
+//!   order
 //! * `dist("a", "b") = ∞`, there is infinite number of texts starting with letter `a`
 //! 
 //! This last property is reason why we use a tree to keep data and also one of the reasons why
-//! BTree implementation doesn't work for ui. To be exact [`std::collections::BTree::range`]
+//! BTree implementation doesn't work for ui. To be exact [`std::collections::BTreeMap::range`]
 //! function is useless if you can't predict distance between elements if you need to provide
 //! page of data.
 //! 
-//! ## Differences between [std::collections::BTree] and [Tree]
+//! ## Differences between [std::collections::BTreeMap] and [Tree]
 //! 
-//! TODO: Write list of differences between this types both internal and usage
+//! - Every node of the [Tree] knows how many elements are in its subtree. Thanks to that [Tree::select],
+//!   [Tree::rank] and [Tree::iter_from] are `O(log n)`. Showing any page of the data is as fast as showing the
+//!   first one.
+//! - Order of the tree is set at runtime by [TreeConfiguration]
+//! - [Tree] implements only the small subset of the map api which is required by the stores
 
 mod configuration;
 mod impls;
@@ -74,95 +78,16 @@ mod impls;
 #[cfg(test)]
 mod test;
 
-use std::marker::PhantomData;
-
 pub use configuration::TreeConfiguration;
+pub use impls::Iter;
 
-pub use impls::TreeImpl;
+use impls::Node;
 
 /// Implementation of B-tree with order statistics
 /// 
 /// For more details check [crate::tree] description
-#[derive(Debug)]
-pub struct Tree<K, V> 
-where
-K: 'static,
-V: 'static,
-{
-    _marker: PhantomData<(K, V)>,
-    // // root: Option<Node<K,V>>,
-    // configuration: TreeConfiguration,
-    // size: usize,
+#[derive(Clone, Debug)]
+pub struct Tree<K, V> {
+    root: Node<K, V>,
+    configuration: TreeConfiguration,
 }
-
-// impl<K, V> Tree<K, V> 
-// where
-//     K: 'static,
-//     V: 'static,
-// {
-//     pub fn new(configuration: TreeConfiguration) -> Tree<K,V> {
-//         Self{
-//             _marker: PhantomData,
-//             // root: None,
-//             configuration,
-//             size: 0,
-//         }
-//     }
-
-//     pub fn clear(&mut self) {
-//         *self = Tree::new(self.configuration)
-//     }
-
-//     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-//         None
-//     }
-
-//     pub fn len(&self) -> usize {
-//         self.size
-//     } 
-
-//     pub fn is_empty(&self) -> bool {
-//         self.size == 0
-//     }
-// }
-
-// impl<K, V> Default for Tree<K, V>
-// where
-//     K: 'static,
-//     V: 'static,
-// {
-//     fn default() -> Self {
-//         Tree::new(TreeConfiguration::default())
-//     }
-// }
-
-// enum Node<K, V> 
-// where
-//     V: 'static,
-//     K: 'static,
-// {
-//     Data(&'static DataNode<K, V>),
-//     Inner(&'static InnerNode<K,V>)
-// }
-
-// /// Node in the tree
-// struct InnerNode<K, V> 
-// where
-//     V: 'static,
-//     K: 'static,
-// {
-//     /// Size of the whole subtree
-//     size: usize,
-//     data: Vec<(K, V)>,
-//     link: Vec<Node<K,V>>,
-// }
-
-// struct DataNode<K, V> 
-// where
-//     K: 'static,
-//     V: 'static,
-// {
-//     data: Vec<(K, V)>,
-//     next: &'static DataNode<K,V>,
-// }
-
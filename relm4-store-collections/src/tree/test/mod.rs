@@ -1,14 +1,81 @@
-
+mod random;
 
 mod tree {
-    // use super::super::Tree;
+    use super::super::Tree;
+    use super::super::TreeConfiguration;
+
     /// Test creation with default configuration
     #[test]
     fn default() {
-        // let tree = Tree::<usize, usize>::default();
+        let tree = Tree::<usize, usize>::default();
+
+        assert_eq!(tree.len(), 0, "Length of freshly created tree should be `0`");
+        assert!(tree.is_empty(), "Freshly created tree should be empty");
+        assert_eq!(tree.iter().next(), None, "Freshly created tree should have nothing to iterate over");
+    }
+
+    #[test]
+    fn insert_replaces_value() {
+        let mut tree = Tree::new(TreeConfiguration::new(3));
 
-        // assert_eq!(tree.len(), 0, "Length of freshly created tree should be `0`");
-        // assert_eq!(tree.is_empty(), true, "Freshly created tree should be empty");
+        assert_eq!(tree.insert(1, "a"), None);
+        assert_eq!(tree.insert(1, "b"), Some("a"), "Old value should be returned");
+        assert_eq!(tree.len(), 1, "Replacing value should not change the length");
+        assert_eq!(tree.get(&1), Some(&"b"));
+    }
+
+    #[test]
+    fn rank_and_select() {
+        let mut tree = Tree::new(TreeConfiguration::new(3));
+        for i in 0..100 {
+            tree.insert(i*2, i);
+        }
+
+        for i in 0..100 {
+            assert_eq!(tree.rank(&(i*2)), Some(i));
+            assert_eq!(tree.position(&(i*2+1)), Err(i+1), "Odd keys should be inserted after their predecessor");
+            assert_eq!(tree.select(i), Some((&(i*2), &i)));
+        }
+
+        assert_eq!(tree.select(100), None);
+    }
+
+    #[test]
+    fn iter_from() {
+        let mut tree = Tree::new(TreeConfiguration::new(4));
+        for i in (0..50).rev() {
+            tree.insert(i, i);
+        }
+
+        for start in 0..=50 {
+            let keys: Vec<usize> = tree.iter_from(start).map(|(k, _)| *k).collect();
+            let expected: Vec<usize> = (start..50).collect();
+            assert_eq!(keys, expected, "Iteration should start at {}", start);
+            assert_eq!(tree.iter_from(start).len(), 50 - start);
+        }
+
+        assert_eq!(tree.iter_from(60).next(), None);
+    }
+
+    #[test]
+    fn remove() {
+        let mut tree = Tree::new(TreeConfiguration::new(3));
+        for i in 0..20 {
+            tree.insert(i, i);
+        }
+
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_eq!(tree.remove(&5), None, "Element can be removed only once");
+        assert_eq!(tree.remove_nth(0), Some((0, 0)));
+        assert_eq!(tree.remove_nth(17), Some((19, 19)));
+        assert_eq!(tree.remove_nth(17), None);
+        assert_eq!(tree.len(), 17);
+
+        for i in 1..19 {
+            tree.remove(&i);
+        }
+
+        assert!(tree.is_empty());
     }
 }
 
@@ -28,4 +95,10 @@ mod tree_configuration {
 
         assert_eq!(configuration.capacity(), 19, "Maximum number of elements in node for configuration")
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[should_panic]
+    fn order_too_small() {
+        TreeConfiguration::new(2);
+    }
+}
@@ -0,0 +1,107 @@
+//! Randomized tests comparing [Tree] against [BTreeMap]
+
+use std::collections::BTreeMap;
+
+use super::super::Tree;
+use super::super::TreeConfiguration;
+
+/// Simple xorshift so the tests are reproducible without extra dependencies
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound as u64) as usize
+    }
+}
+
+fn compare(tree: &Tree<usize, usize>, expected: &BTreeMap<usize, usize>) {
+    tree.assert_valid();
+    assert_eq!(tree.len(), expected.len());
+
+    let elements: Vec<(usize, usize)> = tree.iter().map(|(k, v)| (*k, *v)).collect();
+    let expected_elements: Vec<(usize, usize)> = expected.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(elements, expected_elements);
+}
+
+fn run(order: usize, seed: u64, operations: usize, key_range: usize) {
+    let mut random = Random(seed);
+    let mut tree = Tree::new(TreeConfiguration::new(order));
+    let mut expected = BTreeMap::new();
+
+    for step in 0..operations {
+        let key = random.next(key_range);
+        match random.next(6) {
+            0..=2 => {
+                assert_eq!(tree.insert(key, step), expected.insert(key, step), "insert {}", key);
+            },
+            3 => {
+                assert_eq!(tree.remove(&key), expected.remove(&key), "remove {}", key);
+            },
+            4 => {
+                if !expected.is_empty() {
+                    let nth = random.next(expected.len());
+                    let expected_key = *expected.keys().nth(nth).unwrap();
+                    let expected_value = expected.remove(&expected_key).unwrap();
+                    assert_eq!(tree.remove_nth(nth), Some((expected_key, expected_value)), "remove nth {}", nth);
+                }
+            },
+            _ => {
+                let expected_position = expected.range(..key).count();
+                let position = tree.position(&key);
+                if expected.contains_key(&key) {
+                    assert_eq!(position, Ok(expected_position));
+                    assert_eq!(tree.select(expected_position), Some((&key, &expected[&key])));
+                }
+                else {
+                    assert_eq!(position, Err(expected_position));
+                }
+
+                let from: Vec<&usize> = tree.iter_from(expected_position).map(|(k, _)| k).take(10).collect();
+                let expected_from: Vec<&usize> = expected.range(key..).map(|(k, _)| k).take(10).collect();
+                assert_eq!(from, expected_from, "iter from {}", expected_position);
+            },
+        }
+
+        if step % 64 == 0 {
+            compare(&tree, &expected);
+        }
+    }
+
+    compare(&tree, &expected);
+
+    // drain the tree in random order
+    while !expected.is_empty() {
+        let nth = random.next(expected.len());
+        let key = *expected.keys().nth(nth).unwrap();
+        assert_eq!(tree.remove(&key), expected.remove(&key));
+        tree.assert_valid();
+    }
+
+    assert!(tree.is_empty());
+}
+
+#[test]
+fn order_3() {
+    run(3, 0x2545_f491_4f6c_dd1d, 5_000, 500);
+}
+
+#[test]
+fn order_4() {
+    run(4, 0x9e37_79b9_7f4a_7c15, 5_000, 500);
+}
+
+#[test]
+fn order_5() {
+    run(5, 0x1234_5678_9abc_def1, 5_000, 2_000);
+}
+
+#[test]
+fn default_order() {
+    let mut random = Random(0xdead_beef_cafe_f00d);
+    for _ in 0..5 {
+        run(12, random.next(usize::MAX) as u64 | 1, 10_000, 3_000);
+    }
+}
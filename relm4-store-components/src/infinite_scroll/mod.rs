@@ -0,0 +1,350 @@
+//! Infinite scroll component for store view
+//!
+//! Component grows the window of the store view when user scrolls close to the end of the records, so the list
+//! looks like a feed. Next chunk of records is read from the store a bit earlier, when user passes
+//! [InfiniteScrollConfiguration::prefetch_threshold], so stores which load the data lazily have it ready when the
+//! window grows and the store view reads it. Component shows a "load more" button as a fallback and an indicator
+//! when there are no more records.
+//!
+//! Window grows until [InfiniteScrollConfiguration::max_size] is reached. After that it slides towards the end of
+//! the store, dropping records from the top.
+//!
+//! Growing the window keeps its start where the window behavior allows it, see
+//! [WindowBehavior::resize][store::window::WindowBehavior::resize]. It works with windows which keep the start in
+//! place like [KeepOnTop][store::window::KeepOnTop] and [ValueTrackingWindow][store::window::ValueTrackingWindow].
+//!
+//! To load records while scrolling call [connect_scrolled_window] with the scrolled window holding the store view
+//!
+//! ```text
+//! fn connect_parent(&mut self, parent_widgets: &TasksListViewWidgets) {
+//!     infinite_scroll::connect_scrolled_window(&parent_widgets.scrolled_window, self.infinite_scroll.sender());
+//! }
+//! ```
+
+use reexport::gtk;
+use reexport::relm4;
+use reexport::relm4_macros;
+use reexport::tracker;
+
+use std::cmp::min;
+
+use gtk::prelude::AdjustmentExt;
+use gtk::prelude::BoxExt;
+use gtk::prelude::ButtonExt;
+use gtk::prelude::OrientableExt;
+use gtk::prelude::WidgetExt;
+
+use relm4::ComponentUpdate;
+use relm4::Model as ViewModel;
+use relm4::send;
+use relm4::Sender;
+use relm4::Widgets;
+use relm4::WidgetPlus;
+
+use relm4_macros::widget;
+
+use store::DataStore;
+use store::StoreSize;
+use store::StoreView;
+use store::StoreViewPrototype;
+use store::math::Range;
+use store_view::View;
+
+#[cfg(test)]
+mod tests;
+
+/// Messages sent to infinite scroll component
+#[derive(Debug)]
+pub enum InfiniteScrollMsg {
+    /// User scrolled the records
+    ///
+    /// Value is the fraction of the records content above the bottom edge of the visible area, `1.0` means user
+    /// is at the end of the loaded records
+    Scrolled(f64),
+    /// Load next chunk of records
+    LoadMore,
+    /// Event to be sent when store was updated
+    StoreUpdated,
+}
+
+/// Configuration of the infinite scroll component
+pub trait InfiniteScrollConfiguration
+{
+    /// Type of parent view model
+    ///
+    /// Type of model used by component which holds infinite scroll component
+    type StoreViewPrototype: StoreViewPrototype;
+
+    /// Returns a view which will be grown by the infinite scroll component
+    fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel)
+        -> View<Self::StoreViewPrototype>;
+
+    /// Returns number of records loaded at once
+    ///
+    /// Default implementation returns `20`
+    fn chunk_size() -> usize {
+        20
+    }
+
+    /// Returns the largest size of the window
+    ///
+    /// When window reaches this size, loading more records drops the records from the top
+    ///
+    /// Default implementation returns `StoreSize::Items(200)`
+    fn max_size() -> StoreSize {
+        StoreSize::Items(200)
+    }
+
+    /// Returns scroll position after which the next chunk of records is read from the store
+    ///
+    /// Default implementation returns `0.5`
+    fn prefetch_threshold() -> f64 {
+        0.5
+    }
+
+    /// Returns scroll position after which the next chunk of records is added to the window
+    ///
+    /// Default implementation returns `0.9`
+    fn load_threshold() -> f64 {
+        0.9
+    }
+
+    /// Returns text of the button loading more records
+    fn load_more_label() -> &'static str {
+        "Load more"
+    }
+
+    /// Returns text shown when all records of the store are loaded
+    fn end_of_data_label() -> &'static str {
+        "No more records"
+    }
+}
+
+/// View model of the infinite scroll component
+#[tracker::track]
+#[derive(Debug)]
+pub struct InfiniteScrollViewModel<Config>
+where
+    Config: InfiniteScrollConfiguration + 'static,
+{
+    #[do_not_track]
+    view: View<Config::StoreViewPrototype>,
+    /// Range of records which were read ahead of time
+    #[do_not_track]
+    prefetched: Option<Range>,
+    /// Window and it's size before the load which wasn't redrawn yet
+    #[do_not_track]
+    loading: Option<(Range, usize)>,
+    at_end: bool,
+}
+
+impl<Config> InfiniteScrollViewModel<Config>
+where
+    Config: InfiniteScrollConfiguration,
+{
+    /// Reads next chunk of records from the store if it wasn't read yet
+    fn prefetch(&mut self) {
+        let chunk = next_chunk(&self.view.get_window(), self.view.len(), Config::chunk_size());
+        if let Some(range) = prefetch_range(&chunk, self.prefetched) {
+            self.view.get_range(&range);
+            self.prefetched = Some(range);
+        }
+    }
+
+    /// Adds next chunk of records to the window
+    fn load_more(&mut self) {
+        let window = self.view.get_window();
+        let size = self.view.window_size();
+        if let Some(before) = self.loading {
+            // window behavior can refuse the change, then there is no redraw to wait for
+            if self.view.inbox_queue_size() > 0 || before != (window, size) {
+                return
+            }
+        }
+
+        let chunk = next_chunk(&window, self.view.len(), Config::chunk_size());
+        match load(&window, size, Config::max_size().items(), &chunk) {
+            Load::Nothing => return,
+            Load::Grow(size) => self.view.set_window_size(size),
+            Load::Slide(range) => self.view.set_window(range),
+        }
+
+        self.loading = Some((window, size));
+    }
+}
+
+/// Reaction to the scroll position
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Scroll {
+    /// User is far from the end of the window
+    Nothing,
+    /// Next chunk of records should be read from the store
+    Prefetch,
+    /// Next chunk of records should be added to the window
+    Load,
+}
+
+/// Returns what to do when user scrolled to the `position`
+fn on_scroll(position: f64, prefetch_threshold: f64, load_threshold: f64) -> Scroll {
+    if position >= load_threshold {
+        Scroll::Load
+    }
+    else if position >= prefetch_threshold {
+        Scroll::Prefetch
+    }
+    else {
+        Scroll::Nothing
+    }
+}
+
+/// Returns range of records to read ahead of time
+///
+/// Returns `None` if there is nothing to read or the `chunk` was already `prefetched`
+fn prefetch_range(chunk: &Range, prefetched: Option<Range>) -> Option<Range> {
+    if chunk.is_empty() || prefetched == Some(*chunk) {
+        None
+    }
+    else {
+        Some(*chunk)
+    }
+}
+
+/// Change of the window loading more records
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Load {
+    /// There is nothing to load
+    Nothing,
+    /// Window grows to the given size
+    Grow(usize),
+    /// Window slides to the given range
+    Slide(Range),
+}
+
+/// Returns range of records which will be added to the `window` by the next load
+///
+/// - `len` is the number of records in the store
+/// - `chunk_size` is the number of records loaded at once
+fn next_chunk(window: &Range, len: usize, chunk_size: usize) -> Range {
+    let end = *window.end();
+    if end >= len {
+        Range::new(end, end)
+    }
+    else {
+        Range::new(end, min(end + chunk_size, len))
+    }
+}
+
+/// Returns how the `window` of the `size` changes to show the `chunk`
+///
+/// Window grows until it reaches `max_size`, after that it slides by the length of the chunk
+fn load(window: &Range, size: usize, max_size: usize, chunk: &Range) -> Load {
+    if chunk.is_empty() {
+        Load::Nothing
+    }
+    else if size < max_size {
+        Load::Grow(min(size + chunk.len(), max_size))
+    }
+    else {
+        Load::Slide(window.slide(window.start() + chunk.len()))
+    }
+}
+
+/// Checks if the `window` reached the end of the store with `len` records
+fn is_at_end(window: &Range, len: usize) -> bool {
+    *window.end() >= len
+}
+
+impl<Config> ViewModel for InfiniteScrollViewModel<Config>
+where
+    Config: InfiniteScrollConfiguration,
+{
+    type Msg = InfiniteScrollMsg;
+    type Widgets = InfiniteScrollWidgets;
+    type Components = ();
+}
+
+impl<Config> ComponentUpdate<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for InfiniteScrollViewModel<Config>
+where
+    Config: InfiniteScrollConfiguration,
+{
+    fn init_model(parent_model: &<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> Self {
+        let view = Config::get_view(parent_model);
+        let at_end = is_at_end(&view.get_window(), view.len());
+
+        Self{
+            view,
+            prefetched: None,
+            loading: None,
+            at_end,
+            tracker: 0,
+        }
+    }
+
+    fn update(
+        &mut self,
+        msg: Self::Msg,
+        _components: &Self::Components,
+        _sender: Sender<Self::Msg>,
+        _parent_sender: Sender<<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel as ViewModel>::Msg>
+    ) {
+        self.reset();
+
+        match msg {
+            InfiniteScrollMsg::Scrolled(position) => {
+                match on_scroll(position, Config::prefetch_threshold(), Config::load_threshold()) {
+                    Scroll::Nothing => {},
+                    Scroll::Prefetch => self.prefetch(),
+                    Scroll::Load => self.load_more(),
+                }
+            },
+            InfiniteScrollMsg::LoadMore => self.load_more(),
+            InfiniteScrollMsg::StoreUpdated => {
+                self.loading = None;
+                let at_end = is_at_end(&self.view.get_window(), self.view.len());
+                self.set_at_end(at_end);
+            },
+        }
+    }
+}
+
+/// Widgets for infinite scroll component
+#[widget(visibility=pub, relm4=relm4)]
+impl<Config> Widgets<InfiniteScrollViewModel<Config>, <Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for InfiniteScrollWidgets
+where
+    Config: InfiniteScrollConfiguration,
+{
+    view! {
+        root = &gtk::Box {
+            set_margin_all: 12,
+            set_orientation: gtk::Orientation::Vertical,
+            append: load_more = &gtk::Button {
+                set_label: Config::load_more_label(),
+                set_visible: track!(
+                    model.changed(InfiniteScrollViewModel::<Config>::at_end()),
+                    !model.at_end
+                ),
+                connect_clicked(sender) => move |_| {
+                    send!(sender, InfiniteScrollMsg::LoadMore)
+                },
+            },
+            append: end_of_data = &gtk::Label {
+                set_text: Config::end_of_data_label(),
+                set_visible: track!(
+                    model.changed(InfiniteScrollViewModel::<Config>::at_end()),
+                    model.at_end
+                ),
+            },
+        }
+    }
+}
+
+/// Sends [InfiniteScrollMsg::Scrolled] every time `scrolled_window` is scrolled vertically
+pub fn connect_scrolled_window(scrolled_window: &gtk::ScrolledWindow, sender: Sender<InfiniteScrollMsg>) {
+    scrolled_window.vadjustment().connect_value_changed(move |adjustment| {
+        let upper = adjustment.upper();
+        if upper > 0.0 {
+            let position = (adjustment.value() + adjustment.page_size()) / upper;
+            send!(sender, InfiniteScrollMsg::Scrolled(position));
+        }
+    });
+}
@@ -0,0 +1,142 @@
+use store::math::Range;
+
+use super::Load;
+use super::Scroll;
+use super::is_at_end;
+use super::load;
+use super::next_chunk;
+use super::on_scroll;
+use super::prefetch_range;
+
+#[test]
+fn next_chunk_follows_the_window() {
+    assert_eq!(next_chunk(&Range::new(0, 20), 100, 20), Range::new(20, 40));
+}
+
+#[test]
+fn next_chunk_is_cut_at_the_end_of_store() {
+    assert_eq!(next_chunk(&Range::new(0, 90), 100, 20), Range::new(90, 100));
+}
+
+#[test]
+fn next_chunk_is_empty_at_the_end_of_store() {
+    assert!(next_chunk(&Range::new(80, 100), 100, 20).is_empty());
+    assert!(next_chunk(&Range::new(0, 20), 10, 20).is_empty(), "Window larger than the store must not load more");
+}
+
+#[test]
+fn load_grows_the_window() {
+    let window = Range::new(0, 20);
+    let chunk = next_chunk(&window, 100, 20);
+
+    assert_eq!(load(&window, 20, 50, &chunk), Load::Grow(40));
+}
+
+#[test]
+fn load_grows_the_window_up_to_max_size() {
+    let window = Range::new(0, 40);
+    let chunk = next_chunk(&window, 100, 20);
+
+    assert_eq!(load(&window, 40, 50, &chunk), Load::Grow(50));
+}
+
+#[test]
+fn load_slides_the_window_of_max_size() {
+    let window = Range::new(0, 50);
+    let chunk = next_chunk(&window, 100, 20);
+
+    assert_eq!(load(&window, 50, 50, &chunk), Load::Slide(Range::new(20, 70)));
+}
+
+#[test]
+fn load_slides_by_the_rest_of_the_store() {
+    let window = Range::new(40, 90);
+    let chunk = next_chunk(&window, 100, 20);
+
+    assert_eq!(load(&window, 50, 50, &chunk), Load::Slide(Range::new(50, 100)));
+}
+
+#[test]
+fn load_at_the_end_of_store_does_nothing() {
+    let window = Range::new(50, 100);
+    let chunk = next_chunk(&window, 100, 20);
+
+    assert_eq!(load(&window, 50, 50, &chunk), Load::Nothing);
+}
+
+#[test]
+fn repeated_loads_grow_then_slide() {
+    let len = 100;
+    let max_size = 50;
+    let mut window = Range::new(0, 20);
+    let mut windows = vec![];
+
+    loop {
+        let chunk = next_chunk(&window, len, 20);
+        window = match load(&window, window.len(), max_size, &chunk) {
+            Load::Nothing => break,
+            Load::Grow(size) => Range::new(*window.start(), window.start() + size),
+            Load::Slide(range) => range,
+        };
+        windows.push(window);
+    }
+
+    assert_eq!(windows, vec![
+        Range::new(0, 40),
+        Range::new(0, 50),
+        Range::new(20, 70),
+        Range::new(40, 90),
+        Range::new(50, 100),
+    ]);
+    assert!(is_at_end(&window, len));
+}
+
+#[test]
+fn window_before_the_end_of_store() {
+    assert!(!is_at_end(&Range::new(0, 20), 100));
+    assert!(!is_at_end(&Range::new(79, 99), 100));
+}
+
+#[test]
+fn window_at_the_end_of_store() {
+    assert!(is_at_end(&Range::new(80, 100), 100));
+    assert!(is_at_end(&Range::new(0, 20), 10), "Window larger than the store is at the end");
+    assert!(is_at_end(&Range::new(0, 20), 0), "Empty store is at the end");
+}
+
+#[test]
+fn scrolling_past_thresholds() {
+    assert_eq!(on_scroll(0.3, 0.5, 0.9), Scroll::Nothing);
+    assert_eq!(on_scroll(0.5, 0.5, 0.9), Scroll::Prefetch);
+    assert_eq!(on_scroll(0.8, 0.5, 0.9), Scroll::Prefetch);
+    assert_eq!(on_scroll(0.9, 0.5, 0.9), Scroll::Load);
+    assert_eq!(on_scroll(1.0, 0.5, 0.9), Scroll::Load);
+}
+
+#[test]
+fn prefetch_threshold_is_configurable() {
+    assert_eq!(on_scroll(0.3, 0.2, 0.9), Scroll::Prefetch);
+    assert_eq!(on_scroll(0.3, 0.5, 0.9), Scroll::Nothing);
+}
+
+#[test]
+fn prefetch_reads_next_chunk() {
+    let chunk = next_chunk(&Range::new(0, 20), 100, 20);
+
+    assert_eq!(prefetch_range(&chunk, None), Some(Range::new(20, 40)));
+}
+
+#[test]
+fn prefetch_reads_chunk_once() {
+    let chunk = next_chunk(&Range::new(0, 20), 100, 20);
+
+    assert_eq!(prefetch_range(&chunk, Some(Range::new(20, 40))), None, "Chunk must not be read again");
+    assert_eq!(prefetch_range(&chunk, Some(Range::new(0, 20))), Some(Range::new(20, 40)), "Chunk after the load must be read");
+}
+
+#[test]
+fn prefetch_at_the_end_of_store_reads_nothing() {
+    let chunk = next_chunk(&Range::new(80, 100), 100, 20);
+
+    assert_eq!(prefetch_range(&chunk, None), None);
+}
@@ -7,4 +7,8 @@
     unreachable_pub
 )]
 
+pub mod infinite_scroll;
+pub mod list_model;
 pub mod pagination;
+pub mod rows_per_page;
+pub mod scrollbar;
@@ -0,0 +1,73 @@
+//! GObject side of the list model and it's items
+use reexport::gio;
+use reexport::glib;
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gio::subclass::prelude::ListModelImpl;
+use glib::subclass::prelude::*;
+use glib::Cast;
+use glib::StaticType;
+
+use super::ListModelSource;
+
+/// Instance data of the [`super::StoreListModel`]
+#[derive(Debug, Default)]
+pub struct StoreListModel {
+    /// Store behind the model, `None` after the model was disposed
+    pub(super) source: RefCell<Option<Rc<dyn ListModelSource>>>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for StoreListModel {
+    const NAME: &'static str = "Relm4StoreListModel";
+    type Type = super::StoreListModel;
+    type ParentType = glib::Object;
+    type Interfaces = (gio::ListModel,);
+}
+
+impl ObjectImpl for StoreListModel {
+    fn dispose(&self, _obj: &Self::Type) {
+        if let Some(source) = self.source.borrow_mut().take() {
+            source.disconnect();
+        }
+    }
+}
+
+impl ListModelImpl for StoreListModel {
+    fn item_type(&self, _list_model: &Self::Type) -> glib::Type {
+        super::StoreListItem::static_type()
+    }
+
+    fn n_items(&self, _list_model: &Self::Type) -> u32 {
+        self.source.borrow()
+            .as_ref()
+            .map(|source| source.n_items())
+            .unwrap_or(0)
+    }
+
+    fn item(&self, _list_model: &Self::Type, position: u32) -> Option<glib::Object> {
+        self.source.borrow()
+            .as_ref()
+            .and_then(|source| source.item(position))
+            .map(|item| item.upcast())
+    }
+}
+
+/// Instance data of the [`super::StoreListItem`]
+#[derive(Debug, Default)]
+pub struct StoreListItem {
+    /// Copy of the record taken when item was created
+    pub(super) record: RefCell<Option<Box<dyn Any>>>,
+}
+
+#[glib::object_subclass]
+impl ObjectSubclass for StoreListItem {
+    const NAME: &'static str = "Relm4StoreListItem";
+    type Type = super::StoreListItem;
+    type ParentType = glib::Object;
+}
+
+impl ObjectImpl for StoreListItem {}
@@ -0,0 +1,313 @@
+//! Exposes data stores as [`gio::ListModel`] so they can be used with `gtk::ListView`, `gtk::GridView` and
+//! `gtk::ColumnView`
+//!
+//! Store view component creates widgets for every record in the window using relm4 factories. GTK4 list widgets
+//! recycle a small number of rows instead, so they can handle stores with millions of records. [`StoreListModel`]
+//! is the glue between the two worlds.
+//!
+//! ```text
+//! let model = StoreListModel::new(store.clone());
+//! let selection = gtk::SingleSelection::new(Some(&model));
+//!
+//! let factory = gtk::SignalListItemFactory::new();
+//! factory.connect_bind(|_, list_item| {
+//!     let item = list_item.item().unwrap().downcast::<StoreListItem>().unwrap();
+//!     let task: Task = item.record().unwrap();
+//!     ...
+//! });
+//!
+//! let list_view = gtk::ListView::new(Some(&selection), Some(&factory));
+//! ```
+//!
+//! ## Sorting
+//!
+//! Wrapping the model in `gtk::SortListModel` works, but it fetches every record from the store to sort them. If
+//! your store implements [`store::OrderedStore`] (like the sorted backends and [`store::SortedStore`]) it's much
+//! cheaper to connect to the `changed` signal of the `gtk::ColumnView` sorter and call
+//! [`set_order`](store::OrderedStore::set_order) with the order matching the clicked header.
+// GObject subclassing requires implementation structs to be public
+#[allow(unreachable_pub)]
+mod imp;
+
+use reexport::gio;
+use reexport::glib;
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use gio::prelude::ListModelExt;
+use glib::ObjectExt;
+use glib::subclass::prelude::ObjectSubclassIsExt;
+
+use record::Id;
+use record::Record;
+use store::DataStore;
+use store::Position;
+use store::StoreId;
+use store::StoreViewMsg;
+use store::math::Range;
+
+glib::wrapper! {
+    /// [`gio::ListModel`] showing records of the data store
+    ///
+    /// Model works with any [`DataStore`], including [`store_view::View`] if you want to show only a window of the
+    /// store. Items are [`StoreListItem`]s created lazily when GTK asks for them, using [`DataStore::get_range`].
+    ///
+    /// Changes in the store are translated into `items-changed` signals
+    ///
+    /// - [`StoreViewMsg::NewAt`] and [`StoreViewMsg::Remove`] add or remove single item
+    /// - [`StoreViewMsg::Move`] and [`StoreViewMsg::Reorder`] replace items in the affected range
+    /// - [`StoreViewMsg::Update`] and [`StoreViewMsg::IdChanged`] replace the item if GTK holds it at the moment
+    /// - [`StoreViewMsg::Reload`] replaces all items
+    pub struct StoreListModel(ObjectSubclass<imp::StoreListModel>)
+        @implements gio::ListModel;
+}
+
+impl StoreListModel {
+    /// Creates new list model showing the records of the `store`
+    ///
+    /// Model listens to the store until it's disposed.
+    pub fn new<Store>(store: Store) -> Self
+    where
+        Store: 'static + DataStore,
+    {
+        let model: Self = glib::Object::new(&[]).expect("Failed to create StoreListModel");
+
+        let id = StoreId::new();
+        let source = Rc::new(StoreSource{
+            id,
+            len: Cell::new(store.len() as u32),
+            store,
+            live: RefCell::new(BTreeMap::new()),
+            prune_at: Cell::new(PRUNE_AT),
+        });
+        model.imp().source.replace(Some(source.clone()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        {
+            let context = glib::MainContext::default();
+            let handler_model = model.downgrade();
+            let handler_source = source.clone();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Store::Record>| {
+                match handler_model.upgrade() {
+                    Some(model) => {
+                        handler_source.translate(&model, msg);
+                        glib::Continue(true)
+                    },
+                    // model is gone, stop listening
+                    None => glib::Continue(false),
+                }
+            });
+        }
+
+        source.store.listen(id, sender);
+
+        model
+    }
+}
+
+glib::wrapper! {
+    /// Item of the [`StoreListModel`] holding a copy of the record
+    ///
+    /// Item is created when GTK asks for it, so it shows the record as it was at that time. When record changes the
+    /// model replaces the item with a new one.
+    pub struct StoreListItem(ObjectSubclass<imp::StoreListItem>);
+}
+
+impl StoreListItem {
+    fn new<R: 'static>(record: R) -> Self {
+        let item: Self = glib::Object::new(&[]).expect("Failed to create StoreListItem");
+        item.imp().record.replace(Some(Box::new(record)));
+        item
+    }
+
+    /// Returns copy of the record kept by the item
+    ///
+    /// Returns `None` if `R` is not the record type of the store behind the model
+    pub fn record<R: 'static + Clone>(&self) -> Option<R> {
+        self.imp().record.borrow()
+            .as_ref()
+            .and_then(|record| record.downcast_ref::<R>())
+            .cloned()
+    }
+}
+
+/// Number of known items above which dead items are forgotten
+const PRUNE_AT: usize = 64;
+
+/// Type erased part of the list model which knows the type of the store
+pub(crate) trait ListModelSource: Debug {
+    /// Number of items in the list model
+    fn n_items(&self) -> u32;
+
+    /// Returns item at the given position
+    fn item(&self, position: u32) -> Option<StoreListItem>;
+
+    /// Stops listening to the store
+    fn disconnect(&self);
+}
+
+struct StoreSource<Store: 'static + DataStore> {
+    id: StoreId<Store>,
+    store: Store,
+    /// Number of items as reported by the `items-changed` signals so far
+    ///
+    /// Store might be already ahead of the model since messages are delivered via channel
+    len: Cell<u32>,
+    /// Items handed out to GTK, by position
+    live: RefCell<BTreeMap<u32, (Id<Store::Record>, glib::WeakRef<StoreListItem>)>>,
+    prune_at: Cell<usize>,
+}
+
+impl<Store: 'static + DataStore> StoreSource<Store> {
+    /// Emits `items-changed` for every change in the message
+    fn translate(&self, model: &StoreListModel, msg: StoreViewMsg<Store::Record>) {
+        match msg {
+            StoreViewMsg::NewAt(Position(position)) => {
+                let position = position as u32;
+                self.shift(position, 1);
+                self.len.set(self.len.get() + 1);
+                model.items_changed(position, 0, 1);
+            },
+            StoreViewMsg::NewRange{from: Position(from), to: Position(to)} => {
+                let position = from as u32;
+                let added = (to - from) as u32;
+                self.shift(position, added as i64);
+                self.len.set(self.len.get() + added);
+                model.items_changed(position, 0, added);
+            },
+            StoreViewMsg::Remove(Position(position)) => {
+                let position = position as u32;
+                if position >= self.len.get() {
+                    return
+                }
+                self.live.borrow_mut().remove(&position);
+                self.shift(position + 1, -1);
+                self.len.set(self.len.get() - 1);
+                model.items_changed(position, 1, 0);
+            },
+            StoreViewMsg::RemoveRange{from: Position(from), to: Position(to)} => {
+                let position = from as u32;
+                let end = (to as u32).min(self.len.get());
+                if position >= end {
+                    return
+                }
+                let removed = end - position;
+                self.live.borrow_mut().retain(|other, _| *other < position || *other >= end);
+                self.shift(end, -(removed as i64));
+                self.len.set(self.len.get() - removed);
+                model.items_changed(position, removed, 0);
+            },
+            StoreViewMsg::Move{from: Position(from), to: Position(to)} => {
+                let start = from.min(to) as u32;
+                let end = from.max(to) as u32 + 1;
+                self.replace(model, start, end);
+            },
+            StoreViewMsg::Reorder{from: Position(from), to: Position(to)} => {
+                self.replace(model, from as u32, to as u32);
+            },
+            StoreViewMsg::Update(id) | StoreViewMsg::IdChanged{old: id, ..} => {
+                let position = self.live.borrow()
+                    .iter()
+                    .find(|(_, (other, _))| *other == id)
+                    .map(|(position, _)| *position);
+
+                // if GTK doesn't hold the item, fresh copy will be fetched when it's needed
+                if let Some(position) = position {
+                    self.replace(model, position, position + 1);
+                }
+            },
+            StoreViewMsg::Reload => {
+                self.live.borrow_mut().clear();
+                let removed = self.len.get();
+                let added = self.store.len() as u32;
+                self.len.set(added);
+                model.items_changed(0, removed, added);
+            },
+            StoreViewMsg::Batch(messages) => {
+                for msg in messages {
+                    self.translate(model, msg);
+                }
+            },
+            // data didn't change
+            StoreViewMsg::Error(_) => {},
+        }
+    }
+
+    /// Forgets the items in `[start, end)` and tells GTK to fetch them again
+    fn replace(&self, model: &StoreListModel, start: u32, end: u32) {
+        let end = end.min(self.len.get());
+        if start >= end {
+            return
+        }
+
+        self.live.borrow_mut().retain(|position, _| *position < start || *position >= end);
+        model.items_changed(start, end - start, end - start);
+    }
+
+    /// Moves positions of known items starting at `from` by `delta`
+    fn shift(&self, from: u32, delta: i64) {
+        let mut live = self.live.borrow_mut();
+        let moved = live.split_off(&from);
+        for (position, item) in moved {
+            live.insert((position as i64 + delta) as u32, item);
+        }
+    }
+
+    /// Forgets items which were already dropped by GTK
+    fn prune(&self) {
+        let mut live = self.live.borrow_mut();
+        if live.len() >= self.prune_at.get() {
+            live.retain(|_, (_, item)| item.upgrade().is_some());
+            self.prune_at.set(PRUNE_AT.max(2 * live.len()));
+        }
+    }
+}
+
+impl<Store: 'static + DataStore> ListModelSource for StoreSource<Store> {
+    fn n_items(&self) -> u32 {
+        self.len.get()
+    }
+
+    fn item(&self, position: u32) -> Option<StoreListItem> {
+        if position >= self.len.get() {
+            return None
+        }
+
+        if let Some((_, item)) = self.live.borrow().get(&position) {
+            if let Some(item) = item.upgrade() {
+                return Some(item)
+            }
+        }
+
+        let idx = position as usize;
+        let record = self.store.get_range(&Range::new(idx, idx + 1)).pop()?;
+        let id = record.get_id();
+        let item = StoreListItem::new(record);
+
+        self.prune();
+        self.live.borrow_mut().insert(position, (id, item.downgrade()));
+
+        Some(item)
+    }
+
+    fn disconnect(&self) {
+        self.store.unlisten(self.id);
+    }
+}
+
+/// Formats [`StoreSource`] for debug format `{:?}`
+impl<Store: 'static + DataStore> Debug for StoreSource<Store> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("StoreSource")
+            .field("id", &self.id)
+            .field("len", &self.len)
+            .field("live", &self.live.borrow().len())
+            .finish()
+    }
+}
@@ -1,6 +1,7 @@
 //! Pagination component for store view
 
 use reexport::gtk;
+use reexport::log;
 use reexport::relm4;
 use reexport::relm4_macros;
 use reexport::tracker;
@@ -39,7 +40,7 @@ pub enum PaginationMsg {
     Next,
     /// Go to previous page
     Prev,
-    /// Go to page
+    /// Go to page typed by the user
     ToPage,
     /// Reload store
     Reload,
@@ -47,6 +48,68 @@ pub enum PaginationMsg {
     StoreUpdated,
 }
 
+/// Parts of the pagination component which are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationLayout {
+    /// Button going to the first page
+    pub first: bool,
+    /// Button going to the previous page
+    pub prev: bool,
+    /// Entry with the current page and total amount of pages
+    pub page: bool,
+    /// Label with the range of shown records, like `21–40 of 312`
+    pub range: bool,
+    /// Button reloading the store
+    pub reload: bool,
+    /// Button going to the next page
+    pub next: bool,
+    /// Button going to the last page
+    pub last: bool,
+}
+
+/// By default all parts are shown
+impl Default for PaginationLayout {
+    fn default() -> Self {
+        Self{
+            first: true,
+            prev: true,
+            page: true,
+            range: true,
+            reload: true,
+            next: true,
+            last: true,
+        }
+    }
+}
+
+/// Names of the icons used by the buttons of the pagination component
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationIcons {
+    /// Icon of the button going to the first page
+    pub first: &'static str,
+    /// Icon of the button going to the previous page
+    pub prev: &'static str,
+    /// Icon of the button reloading the store
+    pub reload: &'static str,
+    /// Icon of the button going to the next page
+    pub next: &'static str,
+    /// Icon of the button going to the last page
+    pub last: &'static str,
+}
+
+/// Symbolic icons from the icon theme
+impl Default for PaginationIcons {
+    fn default() -> Self {
+        Self{
+            first: "go-first-symbolic",
+            prev: "go-previous-symbolic",
+            reload: "view-refresh-symbolic",
+            next: "go-next-symbolic",
+            last: "go-last-symbolic",
+        }
+    }
+}
+
 /// Configuration of the pagination component
 pub trait PaginationConfiguration
 {
@@ -58,6 +121,20 @@ pub trait PaginationConfiguration
     /// Returns a view which will be used by the pagination component
     fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel) 
         -> View<Self::StoreViewPrototype>;
+
+    /// Returns parts of the pagination component which should be shown
+    /// 
+    /// Default implementation shows everything
+    fn layout() -> PaginationLayout {
+        PaginationLayout::default()
+    }
+
+    /// Returns icons used by the buttons
+    /// 
+    /// Default implementation uses symbolic icons from the icon theme
+    fn icons() -> PaginationIcons {
+        PaginationIcons::default()
+    }
 }
 
 /// View model of the pagination component
@@ -71,7 +148,57 @@ where
     view: View<Config::StoreViewPrototype>,
     #[do_not_track]
     page: gtk::EntryBuffer,
+    #[do_not_track]
+    layout: PaginationLayout,
+    #[do_not_track]
+    icons: PaginationIcons,
     total_pages: String,
+    range: String,
+    has_prev: bool,
+    has_next: bool,
+}
+
+impl<Config> PaginationViewModel<Config>
+where
+    Config: PaginationConfiguration,
+{
+    /// Moves the view to the page typed by the user
+    /// 
+    /// Invalid input is ignored, entry is reset to the current page afterwards
+    fn to_page(&self) {
+        match self.page.text().trim().parse::<usize>() {
+            Ok(page) => {
+                if let Err(err) = self.view.to_page(page) {
+                    log::info!("{}", err);
+                }
+            },
+            Err(err) => log::info!("Page must be a number: {}", err),
+        }
+    }
+
+    /// Updates the values shown to the user so they match the store view
+    fn update_values(&mut self) {
+        let current_page = self.view.current_page();
+        let total_pages = self.view.total_pages();
+
+        self.set_total_pages(total_pages.to_string());
+        self.set_range(range_label(&self.view));
+        self.set_has_prev(current_page > 1);
+        self.set_has_next(current_page < total_pages);
+        self.page.set_text(&current_page.to_string());
+    }
+}
+
+/// Returns description of the records shown by the view, like `21–40 of 312`
+fn range_label<SV: StoreView>(view: &SV) -> String {
+    let len = view.len();
+    let shown = view.current_len();
+    if shown == 0 {
+        return format!("0 of {}", len)
+    }
+
+    let start = *view.get_window().start();
+    format!("{}–{} of {}", start + 1, start + shown, len)
 }
 
 impl<Config> ViewModel for PaginationViewModel<Config>
@@ -90,15 +217,19 @@ where
     fn init_model(parent_model: &<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> Self {
         let view = Config::get_view(parent_model); 
 
-        let total_pages = format!("{}", view.total_pages());
-        let current_page: &str = &format!("{}", view.current_page());
-
-        Self{
+        let mut model = Self{
             view,
-            page: gtk::EntryBuffer::new(Some(current_page)),
-            total_pages,
+            page: gtk::EntryBuffer::new(None),
+            layout: Config::layout(),
+            icons: Config::icons(),
+            total_pages: String::new(),
+            range: String::new(),
+            has_prev: false,
+            has_next: false,
             tracker: 0,
-        }
+        };
+        model.update_values();
+        model
     }
 
     fn update(
@@ -108,6 +239,8 @@ where
         _sender: relm4::Sender<Self::Msg>, 
         _parent_sender: relm4::Sender<<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel as ViewModel>::Msg>
     ) {
+        self.reset();
+
         match msg {
             PaginationMsg::First => 
                 self.view.first_page(),
@@ -117,15 +250,14 @@ where
                 self.view.next_page(),
             PaginationMsg::Last => 
                 self.view.last_page(),
-            PaginationMsg::ToPage => (),
+            PaginationMsg::ToPage =>
+                self.to_page(),
             PaginationMsg::Reload =>
                 self.view.send(StoreViewMsg::Reload),
             PaginationMsg::StoreUpdated => (),
         }
 
-        let new_total_pages = self.view.total_pages().to_string(); 
-        self.set_total_pages(new_total_pages);
-        self.page.set_text(&self.view.current_page().to_string());
+        self.update_values();
     }
 }
 
@@ -139,43 +271,77 @@ where
         root = &gtk::Box {
             set_margin_all: 12,
             set_orientation: gtk::Orientation::Horizontal,
-            append: first = &gtk::Button::from_icon_name("go-first-symbolic") {
+            append: first = &gtk::Button::from_icon_name(model.icons.first) {
+                set_visible: model.layout.first,
+                set_sensitive: track!(
+                    model.changed(PaginationViewModel::<Config>::has_prev()),
+                    model.has_prev
+                ),
                 connect_clicked(sender) => move |_| {
                     send!(sender, PaginationMsg::First);
                 },
                 add_css_class: "flat",
             },
-            append: prev = &gtk::Button::from_icon_name("go-previous-symbolic") {
+            append: prev = &gtk::Button::from_icon_name(model.icons.prev) {
+                set_visible: model.layout.prev,
+                set_sensitive: track!(
+                    model.changed(PaginationViewModel::<Config>::has_prev()),
+                    model.has_prev
+                ),
                 connect_clicked(sender) => move |_| {
                     send!(sender, PaginationMsg::Prev)
                 },
                 add_css_class: "flat",
             },
             append: page = &gtk::Entry::with_buffer(&model.page) {
+                set_visible: model.layout.page,
                 connect_activate(sender) => move |_| {
                     send!(sender, PaginationMsg::ToPage)
                 },
             },
-            append = &gtk::Label::with_mnemonic("/") {},
+            append: separator = &gtk::Label::with_mnemonic("/") {
+                set_visible: model.layout.page,
+            },
             append: max_page = &gtk::Label {
+                set_visible: model.layout.page,
                 set_text: track!(
                     model.changed(PaginationViewModel::<Config>::total_pages()),
                     &model.total_pages
                 ),
             },
-            append: reload = &gtk::Button::from_icon_name("view-refresh-symbolic") {
+            append: range = &gtk::Label {
+                set_visible: model.layout.range,
+                set_margin_start: 6,
+                set_margin_end: 6,
+                set_text: track!(
+                    model.changed(PaginationViewModel::<Config>::range()),
+                    &model.range
+                ),
+            },
+            append: reload = &gtk::Button::from_icon_name(model.icons.reload) {
+                set_visible: model.layout.reload,
                 connect_clicked(sender) => move |_| {
                     send!(sender, PaginationMsg::Reload)
                 },
                 add_css_class: "flat",
             },
-            append: next = &gtk::Button::from_icon_name("go-next-symbolic") {
+            append: next = &gtk::Button::from_icon_name(model.icons.next) {
+                set_visible: model.layout.next,
+                set_sensitive: track!(
+                    model.changed(PaginationViewModel::<Config>::has_next()),
+                    model.has_next
+                ),
                 connect_clicked(sender) => move |_| {
                     send!(sender, PaginationMsg::Next)
                 },
                 add_css_class: "flat",
             },
-            append: last = &gtk::Button::from_icon_name("go-last-symbolic") {
+            append: last = &gtk::Button::from_icon_name(model.icons.last) {
+                set_visible: model.layout.last,
+                set_sensitive: track!(
+                    model.changed(PaginationViewModel::<Config>::has_next()),
+                    model.has_next
+                ),
                 connect_clicked(sender) => move |_| {
                     send!(sender, PaginationMsg::Last)
                 },
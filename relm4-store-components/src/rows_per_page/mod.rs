@@ -0,0 +1,186 @@
+//! Component allowing user to choose how many records are shown by the store view
+//!
+//! Store view keeps the records which stay in the window when it's resized, see [StoreView::set_window_size].
+
+use reexport::gtk;
+use reexport::relm4;
+use reexport::relm4_macros;
+use reexport::tracker;
+
+use gtk::prelude::BoxExt;
+use gtk::prelude::OrientableExt;
+use gtk::prelude::WidgetExt;
+
+use relm4::ComponentUpdate;
+use relm4::Model as ViewModel;
+use relm4::send;
+use relm4::Widgets;
+use relm4::WidgetPlus;
+
+use relm4_macros::widget;
+
+use store::StoreView;
+use store::StoreViewPrototype;
+use store_view::View;
+
+/// Messages sent to rows per page component
+#[derive(Debug)]
+pub enum RowsPerPageMsg {
+    /// User selected the size at given index
+    Select(u32),
+    /// Event to be sent when store was updated
+    StoreUpdated,
+}
+
+/// Configuration of the rows per page component
+pub trait RowsPerPageConfiguration
+{
+    /// Type of parent view model
+    ///
+    /// Type of model used by component which holds rows per page component
+    type StoreViewPrototype: StoreViewPrototype;
+
+    /// Returns a view which will be resized by the rows per page component
+    fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel)
+        -> View<Self::StoreViewPrototype>;
+
+    /// Returns sizes user can choose from
+    ///
+    /// If the current size of the store view is not on the list, it's added to it.
+    ///
+    /// Default implementation returns `10`, `20`, `50` and `100`
+    fn sizes() -> Vec<usize> {
+        vec![10, 20, 50, 100]
+    }
+
+    /// Returns text of the label shown in front of the sizes
+    fn label() -> &'static str {
+        "Rows per page"
+    }
+}
+
+/// View model of the rows per page component
+#[tracker::track]
+#[derive(Debug)]
+pub struct RowsPerPageViewModel<Config>
+where
+    Config: RowsPerPageConfiguration + 'static,
+{
+    #[do_not_track]
+    view: View<Config::StoreViewPrototype>,
+    #[do_not_track]
+    sizes: Vec<usize>,
+    #[do_not_track]
+    labels: gtk::StringList,
+    selected: u32,
+}
+
+impl<Config> RowsPerPageViewModel<Config>
+where
+    Config: RowsPerPageConfiguration,
+{
+    /// Returns index of the size of the store view
+    ///
+    /// If the size is not on the list, it's added to the sizes and the labels of the drop down
+    fn index_of_window_size(&mut self) -> u32 {
+        let size = self.view.window_size();
+        match self.sizes.binary_search(&size) {
+            Ok(idx) => idx as u32,
+            Err(idx) => {
+                self.sizes.insert(idx, size);
+                self.labels.splice(idx as u32, 0, &[&size.to_string()]);
+                idx as u32
+            }
+        }
+    }
+}
+
+impl<Config> ViewModel for RowsPerPageViewModel<Config>
+where
+    Config: RowsPerPageConfiguration,
+{
+    type Msg = RowsPerPageMsg;
+    type Widgets = RowsPerPageWidgets;
+    type Components = ();
+}
+
+impl<Config> ComponentUpdate<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for RowsPerPageViewModel<Config>
+where
+    Config: RowsPerPageConfiguration,
+{
+    fn init_model(parent_model: &<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> Self {
+        let view = Config::get_view(parent_model);
+
+        let mut sizes = Config::sizes();
+        sizes.sort_unstable();
+        sizes.dedup();
+
+        let labels = sizes.iter()
+            .map(|size| size.to_string())
+            .collect::<Vec<String>>();
+        let labels = gtk::StringList::new(&labels.iter().map(String::as_str).collect::<Vec<&str>>());
+
+        let mut model = Self{
+            view,
+            sizes,
+            labels,
+            selected: 0,
+            tracker: 0,
+        };
+        model.selected = model.index_of_window_size();
+        model
+    }
+
+    fn update(
+        &mut self,
+        msg: Self::Msg,
+        _components: &Self::Components,
+        _sender: relm4::Sender<Self::Msg>,
+        _parent_sender: relm4::Sender<<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel as ViewModel>::Msg>
+    ) {
+        self.reset();
+
+        match msg {
+            RowsPerPageMsg::Select(idx) => {
+                if let Some(size) = self.sizes.get(idx as usize) {
+                    if *size != self.view.window_size() {
+                        self.view.set_window_size(*size);
+                    }
+                    self.set_selected(idx);
+                }
+            },
+            RowsPerPageMsg::StoreUpdated => {
+                let selected = self.index_of_window_size();
+                self.set_selected(selected);
+            },
+        }
+    }
+}
+
+/// Widgets for rows per page component
+#[widget(visibility=pub, relm4=relm4)]
+impl<Config> Widgets<RowsPerPageViewModel<Config>, <Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for RowsPerPageWidgets
+where
+    Config: RowsPerPageConfiguration,
+{
+    view! {
+        root = &gtk::Box {
+            set_margin_all: 12,
+            set_spacing: 6,
+            set_orientation: gtk::Orientation::Horizontal,
+            append = &gtk::Label {
+                set_text: Config::label(),
+            },
+            append: sizes = &gtk::DropDown {
+                set_model: Some(&model.labels),
+                set_selected: track!(
+                    model.changed(RowsPerPageViewModel::<Config>::selected()),
+                    model.selected
+                ),
+                connect_selected_notify(sender) => move |sizes| {
+                    send!(sender, RowsPerPageMsg::Select(sizes.selected()))
+                },
+            },
+        }
+    }
+}
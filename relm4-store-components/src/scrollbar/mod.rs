@@ -0,0 +1,262 @@
+//! Scrollbar component for store view
+//!
+//! Scrollbar shows where the window of the store view is in the store. Moving it moves the window. Adjustment
+//! of the scrollbar is sized from the length of the store and the window size, so it stays in sync when the store
+//! grows, shrinks or the window behavior moves the window on its own.
+//!
+//! Scrollbar handles the mouse wheel above itself. To scroll with wheel and keyboard above the records call
+//! [connect_scroll_events] with the container widget of the store view
+//!
+//! ```text
+//! fn connect_parent(&mut self, parent_widgets: &TasksListViewWidgets) {
+//!     scrollbar::connect_scroll_events(&parent_widgets.container, self.scrollbar.sender());
+//! }
+//! ```
+
+use reexport::gtk;
+use reexport::relm4;
+use reexport::relm4_macros;
+
+use gtk::gdk;
+use gtk::prelude::AdjustmentExt;
+use gtk::prelude::IsA;
+use gtk::prelude::OrientableExt;
+use gtk::prelude::WidgetExt;
+
+use relm4::ComponentUpdate;
+use relm4::Model as ViewModel;
+use relm4::send;
+use relm4::Sender;
+use relm4::Widgets;
+
+use relm4_macros::widget;
+
+use store::DataStore;
+use store::StoreView;
+use store::StoreViewPrototype;
+use store::math::Range;
+use store_view::View;
+
+#[cfg(test)]
+mod tests;
+
+/// Messages sent to scrollbar component
+#[derive(Debug)]
+pub enum ScrollbarMsg {
+    /// Value of the adjustment was changed
+    Scrolled,
+    /// Scroll by given number of records, negative values scroll towards the beginning of the store
+    ScrollBy(f64),
+    /// Scroll one window towards the beginning of the store
+    PageUp,
+    /// Scroll one window towards the end of the store
+    PageDown,
+    /// Scroll to the beginning of the store
+    First,
+    /// Scroll to the end of the store
+    Last,
+    /// Event to be sent when store was updated
+    StoreUpdated,
+}
+
+/// Configuration of the scrollbar component
+pub trait ScrollbarConfiguration
+{
+    /// Type of parent view model
+    ///
+    /// Type of model used by component which holds scrollbar component
+    type StoreViewPrototype: StoreViewPrototype;
+
+    /// Returns a view which will be used by the scrollbar component
+    fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel)
+        -> View<Self::StoreViewPrototype>;
+}
+
+/// View model of the scrollbar component
+#[derive(Debug)]
+pub struct ScrollbarViewModel<Config>
+where
+    Config: ScrollbarConfiguration + 'static,
+{
+    view: View<Config::StoreViewPrototype>,
+    adjustment: gtk::Adjustment,
+}
+
+impl<Config> ScrollbarViewModel<Config>
+where
+    Config: ScrollbarConfiguration,
+{
+    /// Moves the window of the store view to the value of the adjustment
+    fn scrolled(&self) {
+        let range = window_at(self.adjustment.value(), self.view.window_size());
+
+        if range != self.view.get_window() {
+            self.view.set_window(range);
+        }
+    }
+
+    /// Updates the adjustment to match the store and the window of the store view
+    fn sync(&self) {
+        let state = AdjustmentState::of(
+            self.view.len(),
+            self.view.window_size(),
+            *self.view.get_window().start(),
+            self.adjustment.value(),
+        );
+
+        self.adjustment.configure(state.value, 0.0, state.upper, 1.0, state.page_size, state.page_size);
+    }
+}
+
+/// Values of the adjustment showing the window of the store view
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AdjustmentState {
+    value: f64,
+    upper: f64,
+    page_size: f64,
+}
+
+impl AdjustmentState {
+    /// Returns the adjustment for the store of `len` records and the window of `size` records starting at `start`
+    ///
+    /// `value` is the current value of the adjustment. If it points at the `start` it's kept with the fraction, so
+    /// smooth scrolling can move the window by less than a record per event.
+    fn of(len: usize, size: usize, start: usize, value: f64) -> Self {
+        let value = if value.floor() as usize == start {
+            value
+        }
+        else {
+            start as f64
+        };
+
+        Self{
+            value,
+            upper: len as f64,
+            page_size: size as f64,
+        }
+    }
+}
+
+/// Returns the window of `size` records shown when the adjustment has the `value`
+fn window_at(value: f64, size: usize) -> Range {
+    let start = value.floor() as usize;
+    Range::new(start, start + size)
+}
+
+impl<Config> ViewModel for ScrollbarViewModel<Config>
+where
+    Config: ScrollbarConfiguration,
+{
+    type Msg = ScrollbarMsg;
+    type Widgets = ScrollbarWidgets;
+    type Components = ();
+}
+
+impl<Config> ComponentUpdate<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for ScrollbarViewModel<Config>
+where
+    Config: ScrollbarConfiguration,
+{
+    fn init_model(parent_model: &<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> Self {
+        let view = Config::get_view(parent_model);
+        let start = *view.get_window().start();
+        let state = AdjustmentState::of(view.len(), view.window_size(), start, start as f64);
+        let adjustment = gtk::Adjustment::new(
+            state.value,
+            0.0,
+            state.upper,
+            1.0,
+            state.page_size,
+            state.page_size,
+        );
+
+        Self{
+            view,
+            adjustment,
+        }
+    }
+
+    fn update(
+        &mut self,
+        msg: Self::Msg,
+        _components: &Self::Components,
+        _sender: Sender<Self::Msg>,
+        _parent_sender: Sender<<<Config::StoreViewPrototype as StoreViewPrototype>::ViewModel as ViewModel>::Msg>
+    ) {
+        match msg {
+            ScrollbarMsg::Scrolled => (),
+            ScrollbarMsg::ScrollBy(delta) =>
+                self.adjustment.set_value(self.adjustment.value() + delta),
+            ScrollbarMsg::PageUp =>
+                self.adjustment.set_value(self.adjustment.value() - self.adjustment.page_size()),
+            ScrollbarMsg::PageDown =>
+                self.adjustment.set_value(self.adjustment.value() + self.adjustment.page_size()),
+            ScrollbarMsg::First =>
+                self.adjustment.set_value(self.adjustment.lower()),
+            ScrollbarMsg::Last =>
+                self.adjustment.set_value(self.adjustment.upper()),
+            ScrollbarMsg::StoreUpdated => {
+                self.sync();
+                return
+            },
+        }
+
+        self.scrolled();
+    }
+}
+
+/// Widgets for scrollbar component
+#[widget(visibility=pub, relm4=relm4)]
+impl<Config> Widgets<ScrollbarViewModel<Config>, <Config::StoreViewPrototype as StoreViewPrototype>::ViewModel> for ScrollbarWidgets
+where
+    Config: ScrollbarConfiguration,
+{
+    view! {
+        root = &gtk::Scrollbar {
+            set_orientation: gtk::Orientation::Vertical,
+            set_adjustment: Some(&model.adjustment),
+        }
+    }
+
+    fn post_init() {
+        {
+            let sender = sender.clone();
+            model.adjustment.connect_value_changed(move |_| {
+                send!(sender, ScrollbarMsg::Scrolled);
+            });
+        }
+    }
+}
+
+/// Scrolls the store view using the mouse wheel and keyboard when `widget` has focus or is under the pointer
+///
+/// - wheel scrolls by records, smooth scrolling is supported
+/// - `Up` and `Down` scroll by one record
+/// - `Page Up` and `Page Down` scroll by the window
+/// - `Home` and `End` scroll to the beginning and end of the store
+pub fn connect_scroll_events(widget: &impl IsA<gtk::Widget>, sender: Sender<ScrollbarMsg>) {
+    let scroll = gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::VERTICAL);
+    {
+        let sender = sender.clone();
+        scroll.connect_scroll(move |_, _, dy| {
+            send!(sender, ScrollbarMsg::ScrollBy(dy));
+            gtk::Inhibit(true)
+        });
+    }
+    widget.add_controller(&scroll);
+
+    let keys = gtk::EventControllerKey::new();
+    keys.connect_key_pressed(move |_, key, _, _| {
+        let msg = match key {
+            gdk::Key::Up => ScrollbarMsg::ScrollBy(-1.0),
+            gdk::Key::Down => ScrollbarMsg::ScrollBy(1.0),
+            gdk::Key::Page_Up => ScrollbarMsg::PageUp,
+            gdk::Key::Page_Down => ScrollbarMsg::PageDown,
+            gdk::Key::Home => ScrollbarMsg::First,
+            gdk::Key::End => ScrollbarMsg::Last,
+            _ => return gtk::Inhibit(false),
+        };
+        send!(sender, msg);
+        gtk::Inhibit(true)
+    });
+    widget.add_controller(&keys);
+}
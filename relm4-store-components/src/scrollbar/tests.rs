@@ -0,0 +1,64 @@
+use store::math::Range;
+
+use super::AdjustmentState;
+use super::window_at;
+
+#[test]
+fn adjustment_covers_the_store() {
+    let state = AdjustmentState::of(100, 10, 20, 20.0);
+
+    assert_eq!(state, AdjustmentState{
+        value: 20.0,
+        upper: 100.0,
+        page_size: 10.0,
+    });
+}
+
+#[test]
+fn adjustment_follows_store_length() {
+    let before = AdjustmentState::of(100, 10, 20, 20.0);
+    let after = AdjustmentState::of(150, 10, 20, before.value);
+
+    assert_eq!(after.upper, 150.0, "Upper bound must grow with the store");
+    assert_eq!(after.value, 20.0, "Value must not change when store grows");
+    assert_eq!(after.page_size, 10.0);
+}
+
+#[test]
+fn fraction_of_the_value_is_kept() {
+    let state = AdjustmentState::of(100, 10, 20, 20.75);
+
+    assert_eq!(state.value, 20.75, "Smooth scrolling must be able to move by less than a record");
+}
+
+#[test]
+fn value_follows_window_moved_by_the_behavior() {
+    let state = AdjustmentState::of(100, 10, 23, 20.75);
+
+    assert_eq!(state.value, 23.0, "Value must point to the start of the window");
+}
+
+#[test]
+fn empty_store() {
+    let state = AdjustmentState::of(0, 10, 0, 0.0);
+
+    assert_eq!(state, AdjustmentState{
+        value: 0.0,
+        upper: 0.0,
+        page_size: 10.0,
+    });
+}
+
+#[test]
+fn window_starts_at_the_value() {
+    assert_eq!(window_at(0.0, 10), Range::new(0, 10));
+    assert_eq!(window_at(20.75, 10), Range::new(20, 30));
+}
+
+#[test]
+fn synced_value_maps_back_to_the_window() {
+    for start in [0, 7, 20, 90] {
+        let state = AdjustmentState::of(100, 10, start, start as f64 + 0.5);
+        assert_eq!(window_at(state.value, state.page_size as usize), Range::new(start, start + 10));
+    }
+}
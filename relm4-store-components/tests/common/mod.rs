@@ -0,0 +1,49 @@
+use reexport::glib;
+use reexport::gtk;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use backend_inmemory::InMemoryBackendConfiguration;
+use store::DataStore;
+use store::Store;
+use store::math::Range;
+
+pub struct TestConfig {}
+
+impl InMemoryBackendConfiguration for TestConfig {
+    type Record = TestRecord;
+
+    fn initial_data() -> Vec<Self::Record> {
+        vec![
+            TestRecord::constant("a1").permanent(),
+            TestRecord::constant("b1").permanent(),
+            TestRecord::constant("a2").permanent(),
+            TestRecord::constant("b2").permanent(),
+            TestRecord::constant("a3").permanent(),
+        ]
+    }
+}
+
+pub type TestStore = Store<InMemoryBackend<TestConfig>>;
+
+/// Returns record with given label from the store
+pub fn find(store: &TestStore, label: &str) -> TestRecord {
+    store.get_range(&Range::new(0, store.len()))
+        .into_iter()
+        .find(|r| r.label == label)
+        .unwrap()
+}
+
+/// Initializes gtk and runs the test while holding the default main context
+pub fn run(test: impl FnOnce(&glib::MainContext)) {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    test(&context);
+}
+
+/// Runs the main loop until there are no more pending events
+pub fn flush(context: &glib::MainContext) {
+    while context.iteration(false) {}
+}
@@ -0,0 +1,130 @@
+use reexport::gio;
+use reexport::glib;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gio::prelude::ListModelExt;
+use glib::Cast;
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store_components::list_model::StoreListItem;
+use relm4_store_components::list_model::StoreListModel;
+use store::DataStore;
+use store::StoreMsg;
+use store::StoreViewMsg;
+
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::run;
+
+/// Arguments of the `items-changed` signals as `(position, removed, added)`
+type Changes = Rc<RefCell<Vec<(u32, u32, u32)>>>;
+
+fn changes(model: &StoreListModel) -> Changes {
+    let changes: Changes = Rc::new(RefCell::new(vec![]));
+    let handler_changes = changes.clone();
+    model.connect_items_changed(move |_, position, removed, added| {
+        handler_changes.borrow_mut().push((position, removed, added));
+    });
+    changes
+}
+
+fn label(model: &StoreListModel, position: u32) -> Option<String> {
+    model.item(position)
+        .and_then(|item| item.downcast::<StoreListItem>().ok())
+        .and_then(|item| item.record::<TestRecord>())
+        .map(|record| record.label)
+}
+
+#[test]
+#[serial(gtk)]
+fn items_are_records_of_the_store() {
+    run(|_| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let model = StoreListModel::new(store.clone());
+
+        assert_eq!(model.n_items(), 5);
+        assert_eq!(label(&model, 0).as_deref(), Some("a1"));
+        assert_eq!(label(&model, 4).as_deref(), Some("a3"));
+        assert_eq!(label(&model, 5), None, "There is no item past the end of the store");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn insert_emits_items_changed() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let model = StoreListModel::new(store.clone());
+        let changes = changes(&model);
+
+        store.send(StoreMsg::Commit(TestRecord::since("c1", 0)));
+        flush(context);
+
+        assert_eq!(*changes.borrow(), vec![(5, 0, 1)]);
+        assert_eq!(model.n_items(), 6);
+        assert_eq!(label(&model, 5).as_deref(), Some("c1"));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_emits_items_changed() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let model = StoreListModel::new(store.clone());
+        let changes = changes(&model);
+
+        store.send(StoreMsg::Delete(find(&store, "b1").get_id()));
+        flush(context);
+
+        assert_eq!(*changes.borrow(), vec![(1, 1, 0)]);
+        assert_eq!(model.n_items(), 4);
+        assert_eq!(label(&model, 1).as_deref(), Some("a2"));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn reload_replaces_all_items() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let model = StoreListModel::new(store.clone());
+        let changes = changes(&model);
+
+        store.fire_handlers(&[StoreViewMsg::Reload]);
+        flush(context);
+
+        assert_eq!(*changes.borrow(), vec![(0, 5, 5)]);
+        assert_eq!(model.n_items(), 5);
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_replaces_item_held_by_gtk() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let model = StoreListModel::new(store.clone());
+        let held = model.item(2).expect("Item must exist");
+        let changes = changes(&model);
+
+        let mut a1 = find(&store, "a1");
+        a1.label = String::from("a1 - edited");
+        store.send(StoreMsg::Commit(a1));
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("a2 - edited");
+        store.send(StoreMsg::Commit(a2));
+        flush(context);
+
+        assert_eq!(*changes.borrow(), vec![(2, 1, 1)], "Only items held by GTK are replaced");
+        assert_eq!(label(&model, 2).as_deref(), Some("a2 - edited"));
+        drop(held);
+    });
+}
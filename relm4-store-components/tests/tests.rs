@@ -0,0 +1,3 @@
+mod common;
+
+mod list_model;
@@ -3,6 +3,9 @@ use reexport::relm4;
 use reexport::relm4_macros;
 use store_view::View;
 
+use std::cell::Cell;
+use std::rc::Rc;
+
 use gtk::Box;
 use gtk::CheckButton;
 use gtk::Label;
@@ -45,6 +48,8 @@ pub enum TaskMsg {
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct TaskWidgets {
+    /// Id of the shown task, widgets might be reused for the other task
+    id: Rc<Cell<Id<Task>>>,
     checkbox: CheckButton,
     label: Label,
     root: Box,
@@ -87,11 +92,17 @@ impl<Config: TasksListConfiguration> StoreViewPrototype
         View::new(store, size, redraw_sender)
     }
 
+    fn init_window(_store: &Self::Store) -> Self::Window {
+        PositionTrackingWindow::new()
+    }
+
     fn init_view(
         record: &Task,
         _position: Position,
         sender: Sender<TaskMsg>,
     ) -> Self::RecordWidgets {
+        let id = Rc::new(Cell::new(record.get_id()));
+
         let root = Box::builder()
             .orientation(Orientation::Horizontal)
             .build();
@@ -106,11 +117,11 @@ impl<Config: TasksListConfiguration> StoreViewPrototype
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             checkbox.connect_toggled(move |btn| {
                 send!(sender, TaskMsg::Toggle{
-                    id,
+                    id: id.get(),
                     complete: btn.is_active()
                 });
             });
@@ -128,6 +139,7 @@ impl<Config: TasksListConfiguration> StoreViewPrototype
         root.append(&label);
 
         TaskWidgets {
+            id,
             checkbox,
             label,
             root,
@@ -138,8 +150,11 @@ impl<Config: TasksListConfiguration> StoreViewPrototype
     fn view(
         record: Task,
         _position: Position,
+        _selected: bool,
         widgets: &Self::RecordWidgets,
     ) {
+        widgets.id.set(record.get_id());
+        widgets.label.set_label(&record.description);
         widgets.checkbox.set_active(record.completed);
 
         let attrs = widgets.label.attributes().unwrap_or_default();
@@ -3,11 +3,13 @@ use reexport::relm4;
 use reexport::relm4_macros;
 use store_view::View;
 
+use std::cell::Cell;
+use std::rc::Rc;
+
 use gtk::Box;
 use gtk::CheckButton;
 use gtk::Label;
 use gtk::Orientation;
-use gtk::prelude::AdjustmentExt;
 use gtk::prelude::BoxExt;
 use gtk::prelude::CheckButtonExt;
 use gtk::prelude::EntryExt;
@@ -15,7 +17,9 @@ use gtk::prelude::EntryBufferExtManual;
 use gtk::prelude::OrientableExt;
 use gtk::prelude::WidgetExt;
 
+use relm4::Components;
 use relm4::Model as ViewModel;
+use relm4::RelmComponent;
 use relm4::send;
 use relm4::Sender;  
 use relm4::Widgets;
@@ -23,14 +27,17 @@ use relm4::WidgetPlus;
 
 use relm4_macros::widget;
 
+use components::scrollbar;
+use components::scrollbar::ScrollbarConfiguration;
+use components::scrollbar::ScrollbarMsg;
+use components::scrollbar::ScrollbarViewModel;
 use record::Id;
 use record::Record;
 use store::DataStore;
 use store::StoreViewPrototype;
 use store::FactoryContainerWidgets;
 use store::Position;
-use store::StoreView;
-use store::math::Range;
+use store::StoreViewInnerComponent;
 use store::window::PositionTrackingWindow;
 
 use crate::model::Task;
@@ -45,12 +52,13 @@ pub enum TaskMsg {
         id: Id<Task>,
     },
     New,
-    Scrolled,
 }
 
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct TaskWidgets {
+    /// Id of the shown task, widgets might be reused for the other task
+    id: Rc<Cell<Id<Task>>>,
     checkbox: CheckButton,
     label: Label,
     root: Box,
@@ -69,7 +77,6 @@ where Config: TasksListConfiguration + 'static,
     tasks: Tasks,
     new_task_description: gtk::EntryBuffer,
     store_view: View<Self>,
-    scroll_adjustment: gtk::Adjustment,
 }
 
 impl<Config> ViewModel for TasksListViewModel<Config> 
@@ -77,7 +84,7 @@ where Config: TasksListConfiguration + 'static,
 {
     type Msg = TaskMsg;
     type Widgets = TasksListViewWidgets;
-    type Components = ();
+    type Components = TasksListComponents<Config>;
 }
 
 impl<Config: TasksListConfiguration> StoreViewPrototype for TasksListViewModel<Config> 
@@ -96,11 +103,17 @@ where Config: TasksListConfiguration + 'static,
         View::new(store, size, redraw_sender)
     }
 
+    fn init_window(_store: &Self::Store) -> Self::Window {
+        PositionTrackingWindow::new()
+    }
+
     fn init_view(
         record: &Task,
         _position: Position,
         sender: Sender<TaskMsg>,
     ) -> Self::RecordWidgets {
+        let id = Rc::new(Cell::new(record.get_id()));
+
         let root = Box::builder()
             .orientation(Orientation::Horizontal)
             .build();
@@ -115,11 +128,11 @@ where Config: TasksListConfiguration + 'static,
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             checkbox.connect_toggled(move |btn| {
                 send!(sender, TaskMsg::Toggle{
-                    id,
+                    id: id.get(),
                     complete: btn.is_active()
                 });
             });
@@ -137,6 +150,7 @@ where Config: TasksListConfiguration + 'static,
         root.append(&label);
 
         TaskWidgets {
+            id,
             checkbox,
             label,
             root,
@@ -147,8 +161,11 @@ where Config: TasksListConfiguration + 'static,
     fn view(
         record: Task,
         _position: Position,
+        _selected: bool,
         widgets: &Self::RecordWidgets,
     ) {
+        widgets.id.set(record.get_id());
+        widgets.label.set_label(&record.description);
         widgets.checkbox.set_active(record.completed);
 
         let attrs = widgets.label.attributes().unwrap_or_default();
@@ -182,32 +199,56 @@ where Config: TasksListConfiguration + 'static,
                     tasks.send(StoreMsg::Commit(updated));
                 }
             },
-            TaskMsg::Scrolled => {
-                let value = view_model.scroll_adjustment.value();
-                let page_size = view_model.scroll_adjustment.page_size(); 
-
-                view_model.store_view.set_window(
-                    Range::new(
-                        value.floor() as usize,
-                        (value+page_size).floor() as usize,
-                    )
-                );
-            },
         }
     }
 
     fn init_view_model(parent_view_model: &Self::ParentViewModel, store_view: &View<Self>) -> Self {
-        let view_length = store_view.len();
-
         TasksListViewModel{
             tasks: Config::get_tasks(parent_view_model),
             new_task_description: gtk::EntryBuffer::new(None),
             store_view: store_view.clone(),
-            scroll_adjustment: gtk::Adjustment::new(0.0, 0.0, view_length as f64, 1.0, 1.0, Config::page_size(parent_view_model) as f64),
         }
     }
 }
 
+pub struct TasksListComponents<Config>
+where Config: TasksListConfiguration + 'static {
+    scrollbar: RelmComponent<ScrollbarViewModel<Self>, TasksListViewModel<Config>>
+}
+
+impl<Config> Components<TasksListViewModel<Config>> for TasksListComponents<Config> 
+where Config: TasksListConfiguration,
+{
+    fn init_components(
+        parent_model: &TasksListViewModel<Config>, 
+        parent_sender: Sender<<TasksListViewModel<Config> as ViewModel>::Msg>
+    ) -> Self {
+        Self {
+            scrollbar: RelmComponent::new(parent_model, parent_sender.clone()),
+        }
+    }
+
+    fn connect_parent(&mut self, parent_widgets: &TasksListViewWidgets) {
+        scrollbar::connect_scroll_events(&parent_widgets.container, self.scrollbar.sender());
+    }
+}
+
+impl<Config> ScrollbarConfiguration for TasksListComponents<Config>
+where Config: TasksListConfiguration + 'static {
+    type StoreViewPrototype = TasksListViewModel<Config>;
+
+    fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> View<Self::StoreViewPrototype> {
+        parent_view_model.store_view.clone()
+    }
+}
+
+impl<Config> StoreViewInnerComponent<TasksListViewModel<Config>> for TasksListComponents<Config>
+where Config: TasksListConfiguration + 'static {
+    fn on_store_update(&mut self) {
+        self.scrollbar.send(ScrollbarMsg::StoreUpdated).unwrap();
+    }
+}
+
 #[widget(visibility=pub, relm4=reexport::relm4)]
 impl<Config: TasksListConfiguration> Widgets<TasksListViewModel<Config>, Config::ParentViewModel> for TasksListViewWidgets {
     view!{
@@ -223,26 +264,16 @@ impl<Config: TasksListConfiguration> Widgets<TasksListViewModel<Config>, Config:
                 set_hexpand: true,
                 set_vexpand: true,
 
-                append: scrollbar = &gtk::Scrollbar {
-                    set_orientation: gtk::Orientation::Vertical,
-                    set_adjustment: Some(&model.scroll_adjustment),
-                },
+                append: components.scrollbar.root_widget(),
                 append: container = &gtk::Box {
                     set_orientation: gtk::Orientation::Vertical,
+                    set_hexpand: true,
+                    set_focusable: true,
                     factory!(model.store_view)
                 }
             },
         }
     }
-
-    fn post_init() {
-        {
-            let sender = sender.clone();
-            model.scroll_adjustment.connect_value_changed(move |_| {
-                send!(sender, TaskMsg::Scrolled);
-            });
-        }
-    }
 }
 
 impl<Config: 'static + TasksListConfiguration> FactoryContainerWidgets<TasksListViewModel<Config>> for TasksListViewWidgets {
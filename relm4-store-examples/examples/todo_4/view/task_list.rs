@@ -3,6 +3,9 @@ use reexport::relm4;
 use reexport::relm4_macros;
 use store_view::View;
 
+use std::cell::Cell;
+use std::rc::Rc;
+
 use gtk::Box;
 use gtk::Button;
 use gtk::CheckButton;
@@ -54,6 +57,8 @@ pub enum TaskMsg {
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct TaskWidgets {
+    /// Id of the shown task, widgets might be reused for the other task
+    id: Rc<Cell<Id<Task>>>,
     checkbox: CheckButton,
     label: Label,
     delete_button: Button,
@@ -98,11 +103,17 @@ where Config: TasksListConfiguration + 'static,
         View::new(store, size, redraw_sender)
     }
 
+    fn init_window(_store: &Self::Store) -> Self::Window {
+        PositionTrackingWindow::new()
+    }
+
     fn init_view(
         record: &Task,
         _position: Position,
         sender: Sender<TaskMsg>,
     ) -> Self::RecordWidgets {
+        let id = Rc::new(Cell::new(record.get_id()));
+
         let root = Box::builder()
             .orientation(Orientation::Horizontal)
             .build();
@@ -117,11 +128,11 @@ where Config: TasksListConfiguration + 'static,
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             checkbox.connect_toggled(move |btn| {
                 send!(sender, TaskMsg::Toggle{
-                    id,
+                    id: id.get(),
                     complete: btn.is_active()
                 });
             });
@@ -149,10 +160,10 @@ where Config: TasksListConfiguration + 'static,
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             delete_button.connect_clicked(move |_| {
-                send!(sender, TaskMsg::Delete(id));
+                send!(sender, TaskMsg::Delete(id.get()));
             });
         }
 
@@ -161,6 +172,7 @@ where Config: TasksListConfiguration + 'static,
         root.append(&delete_button);
 
         TaskWidgets {
+            id,
             checkbox,
             label,
             delete_button,
@@ -172,8 +184,11 @@ where Config: TasksListConfiguration + 'static,
     fn view(
         record: Task,
         _position: Position,
+        _selected: bool,
         widgets: &Self::RecordWidgets,
     ) {
+        widgets.id.set(record.get_id());
+        widgets.label.set_label(&record.description);
         widgets.checkbox.set_active(record.completed);
 
         let attrs = widgets.label.attributes().unwrap_or_default();
@@ -60,6 +60,9 @@ struct TaskList1Configuration {}
 impl TasksListConfiguration for TaskList1Configuration {
     type ParentViewModel = MainWindowViewModel;
     type Window = PositionTrackingWindow;
+    fn init_window() -> Self::Window {
+        PositionTrackingWindow::new()
+    }
     fn get_tasks(parent_model: &Self::ParentViewModel) -> Tasks {
         parent_model.tasks.clone()
     }
@@ -69,6 +72,9 @@ struct TaskList2Configuration {}
 impl TasksListConfiguration for TaskList2Configuration {
     type ParentViewModel = MainWindowViewModel;
     type Window = ValueTrackingWindow;
+    fn init_window() -> Self::Window {
+        ValueTrackingWindow::new()
+    }
     fn get_tasks(parent_model: &Self::ParentViewModel) -> Tasks {
         parent_model.tasks.clone()
     }
@@ -78,6 +84,9 @@ struct TaskList3Configuration {}
 impl TasksListConfiguration for TaskList3Configuration {
     type ParentViewModel = MainWindowViewModel;
     type Window = KeepOnTop;
+    fn init_window() -> Self::Window {
+        KeepOnTop::new()
+    }
     fn get_tasks(parent_model: &Self::ParentViewModel) -> Tasks {
         parent_model.tasks.clone()
     }
@@ -87,6 +96,9 @@ struct TaskList4Configuration {}
 impl TasksListConfiguration for TaskList4Configuration {
     type ParentViewModel = MainWindowViewModel;
     type Window = KeepOnBottom;
+    fn init_window() -> Self::Window {
+        KeepOnBottom::new()
+    }
     fn get_tasks(parent_model: &Self::ParentViewModel) -> Tasks {
         parent_model.tasks.clone()
     }
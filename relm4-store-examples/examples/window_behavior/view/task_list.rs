@@ -3,7 +3,9 @@ use reexport::relm4;
 use reexport::relm4_macros;
 use store_view::View;
 
+use std::cell::Cell;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 use gtk::Box;
 use gtk::Button;
@@ -31,6 +33,9 @@ use relm4_macros::widget;
 use components::pagination::PaginationConfiguration;
 use components::pagination::PaginationViewModel;
 use components::pagination::PaginationMsg;
+use components::rows_per_page::RowsPerPageConfiguration;
+use components::rows_per_page::RowsPerPageViewModel;
+use components::rows_per_page::RowsPerPageMsg;
 use record::Id;
 use record::Record;
 use store::StoreViewInnerComponent;
@@ -57,6 +62,8 @@ pub enum TaskMsg {
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct TaskWidgets {
+    /// Id of the shown task, widgets might be reused for the other task
+    id: Rc<Cell<Id<Task>>>,
     checkbox: CheckButton,
     label: Label,
     delete_button: Button,
@@ -66,6 +73,7 @@ pub struct TaskWidgets {
 pub trait TasksListConfiguration {
     type ParentViewModel: ViewModel;
     type Window: WindowBehavior;
+    fn init_window() -> Self::Window;
     fn get_tasks(parent_view_model: &Self::ParentViewModel) -> Tasks;
 }
 
@@ -105,11 +113,17 @@ where
         View::new(store, size, redraw_sender)
     }
 
+    fn init_window(_store: &Self::Store) -> Self::Window {
+        Config::init_window()
+    }
+
     fn init_view(
         record: &Task,
         _position: Position,
         sender: Sender<TaskMsg>,
     ) -> Self::RecordWidgets {
+        let id = Rc::new(Cell::new(record.get_id()));
+
         let root = Box::builder()
             .orientation(Orientation::Horizontal)
             .build();
@@ -124,11 +138,11 @@ where
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             checkbox.connect_toggled(move |btn| {
                 send!(sender, TaskMsg::Toggle{
-                    id,
+                    id: id.get(),
                     complete: btn.is_active()
                 });
             });
@@ -156,10 +170,10 @@ where
 
         {
             let sender = sender.clone();
-            let id = record.get_id();
+            let id = id.clone();
 
             delete_button.connect_clicked(move |_| {
-                send!(sender, TaskMsg::Delete(id));
+                send!(sender, TaskMsg::Delete(id.get()));
             });
         }
 
@@ -168,6 +182,7 @@ where
         root.append(&delete_button);
 
         TaskWidgets {
+            id,
             checkbox,
             label,
             delete_button,
@@ -179,8 +194,11 @@ where
     fn view(
         record: Task,
         _position: Position,
+        _selected: bool,
         widgets: &Self::RecordWidgets,
     ) {
+        widgets.id.set(record.get_id());
+        widgets.label.set_label(&record.description);
         widgets.checkbox.set_active(record.completed);
 
         let attrs = widgets.label.attributes().unwrap_or_default();
@@ -235,7 +253,8 @@ pub struct TasksListComponents<Config>
 where
     Config: TasksListConfiguration + 'static,
 {
-    pagination: RelmComponent<PaginationViewModel<Self>, TasksListViewModel<Config>>
+    pagination: RelmComponent<PaginationViewModel<Self>, TasksListViewModel<Config>>,
+    rows_per_page: RelmComponent<RowsPerPageViewModel<Self>, TasksListViewModel<Config>>,
 }
 
 impl<Config> Components<TasksListViewModel<Config>> for TasksListComponents<Config> 
@@ -248,6 +267,7 @@ where
     ) -> Self {
         Self {
             pagination: RelmComponent::new(parent_model, parent_sender.clone()),
+            rows_per_page: RelmComponent::new(parent_model, parent_sender.clone()),
         }
     }
 
@@ -265,12 +285,24 @@ where
     }
 }
 
+impl<Config> RowsPerPageConfiguration for TasksListComponents<Config>
+where
+    Config: TasksListConfiguration + 'static,
+{
+    type StoreViewPrototype = TasksListViewModel<Config>;
+
+    fn get_view(parent_view_model: &<Self::StoreViewPrototype as StoreViewPrototype>::ViewModel) -> View<Self::StoreViewPrototype> {
+        parent_view_model.store_view.clone()
+    }
+}
+
 impl<Config> StoreViewInnerComponent<TasksListViewModel<Config>> for TasksListComponents<Config>
 where
     Config: TasksListConfiguration + 'static,
 {
     fn on_store_update(&mut self) {
         self.pagination.send(PaginationMsg::StoreUpdated).unwrap();
+        self.rows_per_page.send(RowsPerPageMsg::StoreUpdated).unwrap();
     }
 }
 
@@ -293,7 +325,11 @@ impl<Config: TasksListConfiguration> Widgets<TasksListViewModel<Config>, Config:
                     factory!(model.store_view)
                 }
             },
-            append: components.pagination.root_widget(),
+            append = &gtk::Box {
+                set_orientation: gtk::Orientation::Horizontal,
+                append: components.pagination.root_widget(),
+                append: components.rows_per_page.root_widget(),
+            },
         }
     }
 }
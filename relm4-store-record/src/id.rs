@@ -150,6 +150,21 @@ where
             value,
         }
     }
+
+    /// Creates id for other kind of record with the same value
+    ///
+    /// Useful when one kind of records wraps another one and you need to keep the ids in sync.
+    /// Returned id is new if and only if this id is new.
+    pub fn transfer<Other>(&self) -> Id<Other>
+    where
+        Other: ?Sized + Record,
+        Other::Allocator: TemporaryIdAllocator<Type=<T::Allocator as TemporaryIdAllocator>::Type>,
+    {
+        match self {
+            Id::New{value} => Id::New{value: *value},
+            Id::Permanent{value} => Id::Permanent{value: *value},
+        }
+    }
 }
 
 impl<T> Default for Id<T> 
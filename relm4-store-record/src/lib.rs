@@ -56,10 +56,22 @@ pub trait Record: Clone {
     fn set_permanent_id(&mut self, value: <Self::Allocator as TemporaryIdAllocator>::Type) -> Result<(), IdentityError>;
 }
 
+/// Definition of the record kept in the tree
+/// 
+/// Tree is described by the records pointing at their parents. Records without parent are roots of the tree.
+pub trait TreeRecord: Record {
+    /// Returns id of the parent of this record
+    /// 
+    /// If returns [None] then this record is a root
+    fn get_parent(&self) -> Option<Id<Self>>;
+}
+
 /// Provides a way to create temporary id's
 pub trait TemporaryIdAllocator: Clone + Debug {
     /// Type of values on which `Id` is based of
-    type Type: Copy + PartialEq + Hash + Eq + std::fmt::Debug;
+    /// 
+    /// Values must be totally ordered, so stores can break ties between records which compare as equal
+    type Type: Copy + PartialEq + Hash + Eq + Ord + std::fmt::Debug;
     /// Returns value of new **temporary** id
     /// 
     /// Every call must return new different value otherwise it's possible to have a conflict which could end up
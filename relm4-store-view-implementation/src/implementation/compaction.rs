@@ -0,0 +1,57 @@
+//! Compaction of the changes waiting to be applied to the view
+//!
+//! View collects the changes between the renders and applies them in one go. Applying a change might require
+//! reading the records from the store, so the less changes the better.
+
+use std::collections::HashSet;
+
+use record::Id;
+use record::Record;
+use store::StoreViewMsg;
+
+#[cfg(test)]
+mod tests;
+
+/// Returns shorter list of changes which has the same effect on the view as the `changes`
+///
+/// - [StoreViewMsg::Reload] reads all records again, so everything before the last reload is dropped except of
+///   [StoreViewMsg::IdChanged] which re-keys the records kept by the view
+/// - Adjacent inserts and removes are merged into ranges, removal of just inserted records cancels the insert,
+///   see [StoreViewMsg::merge_ranges]
+/// - Only the last [StoreViewMsg::Update] of the record is kept, since record is read from the store anyway
+pub(crate) fn compact<R: Record>(changes: Vec<StoreViewMsg<R>>) -> Vec<StoreViewMsg<R>> {
+    let changes = match changes.iter().rposition(|change| matches!(change, StoreViewMsg::Reload)) {
+        Some(last_reload) => {
+            let mut changes = changes;
+            let after = changes.split_off(last_reload);
+            changes.retain(|change| matches!(change, StoreViewMsg::IdChanged{..}));
+            changes.extend(after);
+            changes
+        },
+        None => changes,
+    };
+
+    dedup_updates(StoreViewMsg::merge_ranges(changes))
+}
+
+/// Drops the update if the same record is updated later
+///
+/// [StoreViewMsg::IdChanged] is a barrier, updates are not moved over it
+fn dedup_updates<R: Record>(changes: Vec<StoreViewMsg<R>>) -> Vec<StoreViewMsg<R>> {
+    let mut updated_later: HashSet<Id<R>> = HashSet::new();
+    let mut kept = Vec::with_capacity(changes.len());
+
+    for change in changes.into_iter().rev() {
+        match change {
+            StoreViewMsg::Update(id) if !updated_later.insert(id) => {},
+            StoreViewMsg::IdChanged{..} => {
+                updated_later.clear();
+                kept.push(change);
+            },
+            change => kept.push(change),
+        }
+    }
+
+    kept.reverse();
+    kept
+}
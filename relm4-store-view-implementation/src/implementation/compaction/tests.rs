@@ -0,0 +1,272 @@
+use std::cmp::min;
+use std::collections::HashSet;
+
+use proptest::prelude::*;
+
+use backend_dummy::test_cases::TestRecord;
+use record::Id;
+use store::Position;
+use store::StoreViewMsg;
+use store::math::Range;
+use store::window::KeepOnTop;
+use store::window::PositionTrackingWindow;
+use store::window::StoreState;
+use store::window::WindowBehavior;
+use store::window::WindowTransition;
+
+use super::compact;
+
+type Msg = StoreViewMsg<TestRecord>;
+
+/// What the view would show at given position
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Slot {
+    /// Record which was at the position before the changes
+    Old(usize),
+    /// Record inserted by the changes
+    New,
+    /// Record read by the reload
+    Reloaded(usize),
+}
+
+/// Window behavior of the `kind`
+///
+/// - [KeepOnBottom](store::window::KeepOnBottom) is left out since it expects the window at the end of the store
+/// - [ValueTrackingWindow](store::window::ValueTrackingWindow) is left out since it slides on insert before the
+///   window but not on removal, so it ends up in a different place when the insert and removal cancel out
+fn window(kind: usize) -> Box<dyn WindowBehavior> {
+    match kind {
+        0 => Box::new(PositionTrackingWindow::new()),
+        _ => Box::new(KeepOnTop::new()),
+    }
+}
+
+/// Result of applying the changes one by one
+#[derive(Debug, PartialEq)]
+struct Replay {
+    slots: Vec<Slot>,
+    /// Records which must be read again from the store
+    refreshed: HashSet<Id<TestRecord>>,
+    /// Range of the window
+    page: Range,
+}
+
+/// Naive replay of the changes
+///
+/// `reload_lens` holds the store length at each reload. Window of the `kind` starts at `page`.
+fn replay(initial_len: usize, reload_lens: &[usize], kind: usize, page: Range, changes: &[Msg]) -> Replay {
+    let mut slots: Vec<Slot> = (0..initial_len).map(Slot::Old).collect();
+    let mut refreshed = HashSet::new();
+    let mut reloads = reload_lens.iter();
+    let mut window = window(kind);
+    let mut page = page;
+
+    for change in changes {
+        let view = slots.len().saturating_sub(*page.start()).min(page.len());
+        let state = StoreState{
+            page: &page,
+            view,
+        };
+        let transition = match change {
+            StoreViewMsg::NewAt(at) => window.insert(&state, &at.to_point()),
+            StoreViewMsg::NewRange{from, to} => window.insert_range(&state, &Range::new(from.0, to.0)),
+            StoreViewMsg::Remove(at) => window.remove(&state, &at.to_point()),
+            StoreViewMsg::RemoveRange{from, to} => window.remove_range(&state, &Range::new(from.0, to.0)),
+            StoreViewMsg::Move{from, to} => window.move_record(&state, &from.to_point(), &to.to_point()),
+            StoreViewMsg::Reorder{from, to} => window.reorder(&state, &Range::new(from.0, to.0)),
+            StoreViewMsg::Reload => {
+                page = window.reload(&state);
+                WindowTransition::Identity
+            },
+            _ => WindowTransition::Identity,
+        };
+        // same moves of the window as in the view
+        match transition {
+            WindowTransition::SlideLeft(by) => page = page.slide(page.start().saturating_sub(by)),
+            WindowTransition::SlideRight(by) => page = page.slide(page.start() + by),
+            WindowTransition::InsertLeft{by, ..} if *page.start() != 0 || view + by > page.len() => {
+                page = page.to_right(by);
+            },
+            WindowTransition::RemoveLeft{pos, by} => page = page.to_left(min(by, pos - page.start())),
+            _ => {},
+        }
+
+        match change {
+            StoreViewMsg::NewAt(Position(at)) => slots.insert(*at, Slot::New),
+            StoreViewMsg::NewRange{from, to} => {
+                slots.splice(from.0..from.0, (from.0..to.0).map(|_| Slot::New));
+            },
+            StoreViewMsg::Remove(Position(at)) => {
+                slots.remove(*at);
+            },
+            StoreViewMsg::RemoveRange{from, to} => {
+                slots.drain(from.0..to.0);
+            },
+            StoreViewMsg::Move{from, to} => {
+                let slot = slots.remove(from.0);
+                slots.insert(to.0, slot);
+            },
+            StoreViewMsg::Reorder{from, to} => slots[from.0..to.0].reverse(),
+            StoreViewMsg::Update(id) => {
+                refreshed.insert(*id);
+            },
+            StoreViewMsg::IdChanged{old, new} => {
+                if refreshed.remove(old) {
+                    refreshed.insert(*new);
+                }
+            },
+            StoreViewMsg::Reload => {
+                let len = *reloads.next().expect("Reload must have the store length");
+                slots = (0..len).map(Slot::Reloaded).collect();
+                refreshed.clear();
+            },
+            StoreViewMsg::Batch(_) | StoreViewMsg::Error(_) => {},
+        }
+    }
+
+    Replay{
+        slots,
+        refreshed,
+        page,
+    }
+}
+
+/// Builds valid sequence of changes for the store of `initial_len` records
+///
+/// Returns changes and number of records in the store at each reload
+fn changes(initial_len: usize, seeds: &[(u8, usize, usize, usize)], ids: &[Id<TestRecord>]) -> (Vec<Msg>, Vec<usize>) {
+    let mut len = initial_len;
+    let mut changes = Vec::new();
+    let mut reload_lens = Vec::new();
+
+    for &(kind, a, b, id) in seeds {
+        match kind {
+            0 | 1 => {
+                changes.push(StoreViewMsg::NewAt(Position(a % (len + 1))));
+                len += 1;
+            },
+            2 => {
+                let from = a % (len + 1);
+                let by = b % 4;
+                changes.push(StoreViewMsg::NewRange{from: Position(from), to: Position(from + by)});
+                len += by;
+            },
+            3 | 4 if len > 0 => {
+                changes.push(StoreViewMsg::Remove(Position(a % len)));
+                len -= 1;
+            },
+            5 if len > 0 => {
+                let from = a % len;
+                let to = from + b % (len - from + 1);
+                changes.push(StoreViewMsg::RemoveRange{from: Position(from), to: Position(to)});
+                len -= to - from;
+            },
+            6 if len > 0 => changes.push(StoreViewMsg::Move{from: Position(a % len), to: Position(b % len)}),
+            7 => {
+                let from = a % (len + 1);
+                let to = from + b % (len - from + 1);
+                changes.push(StoreViewMsg::Reorder{from: Position(from), to: Position(to)});
+            },
+            8 | 9 => changes.push(StoreViewMsg::Update(ids[id])),
+            10 => changes.push(StoreViewMsg::IdChanged{old: ids[id], new: ids[(id + 1) % ids.len()]}),
+            11 => {
+                changes.push(StoreViewMsg::Reload);
+                len = b % 15;
+                reload_lens.push(len);
+            },
+            _ => {},
+        }
+    }
+
+    (changes, reload_lens)
+}
+
+proptest! {
+    #[test]
+    fn compacted_changes_match_naive_replay(
+        initial_len in 0usize..15,
+        seeds in prop::collection::vec((0u8..12, any::<usize>(), any::<usize>(), 0usize..4), 0..40),
+        kind in 0usize..2,
+        start in 0usize..10,
+        size in 1usize..8,
+    ) {
+        let ids: Vec<Id<TestRecord>> = (0..4).map(|_| Id::new()).collect();
+        let (changes, reload_lens) = changes(initial_len, &seeds, &ids);
+        let page = Range::new(start, start + size);
+
+        let compacted = compact(changes.clone());
+        let mut expected = replay(initial_len, &reload_lens, kind, page, &changes);
+
+        // reload replaces the window with the one picked by the window behavior for the new data, moves caused by
+        // the changes before the reload don't matter
+        if let Some(last_reload) = changes.iter().rposition(|change| matches!(change, StoreViewMsg::Reload)) {
+            expected.page = replay(initial_len, &reload_lens[reload_lens.len() - 1..], kind, page, &changes[last_reload..]).page;
+        }
+
+        prop_assert!(compacted.len() <= changes.len());
+        prop_assert_eq!(
+            replay(initial_len, &reload_lens[reload_lens.len().saturating_sub(1)..], kind, page, &compacted),
+            expected
+        );
+    }
+}
+
+#[test]
+fn insert_and_remove_of_the_same_record_cancel_out() {
+    let compacted = compact::<TestRecord>(vec![
+        StoreViewMsg::NewAt(Position(3)),
+        StoreViewMsg::Remove(Position(3)),
+    ]);
+
+    assert!(compacted.is_empty());
+}
+
+#[test]
+fn adjacent_changes_are_merged_into_ranges() {
+    let compacted = compact::<TestRecord>(vec![
+        StoreViewMsg::NewAt(Position(3)),
+        StoreViewMsg::NewAt(Position(4)),
+        StoreViewMsg::NewAt(Position(5)),
+        StoreViewMsg::Move{from: Position(0), to: Position(1)},
+        StoreViewMsg::Remove(Position(2)),
+        StoreViewMsg::Remove(Position(2)),
+    ]);
+
+    assert_eq!(compacted.len(), 3);
+    assert!(matches!(compacted[0], StoreViewMsg::NewRange{from: Position(3), to: Position(6)}));
+    assert!(matches!(compacted[1], StoreViewMsg::Move{from: Position(0), to: Position(1)}));
+    assert!(matches!(compacted[2], StoreViewMsg::RemoveRange{from: Position(2), to: Position(4)}));
+}
+
+#[test]
+fn updates_are_deduplicated() {
+    let id = Id::new();
+    let mut changes: Vec<Msg> = (0..100).map(|_| StoreViewMsg::Update(id)).collect();
+    changes.push(StoreViewMsg::NewAt(Position(0)));
+
+    let compacted = compact(changes);
+
+    assert_eq!(compacted.len(), 2);
+    assert!(matches!(compacted[0], StoreViewMsg::Update(updated) if updated == id));
+    assert!(matches!(compacted[1], StoreViewMsg::NewAt(Position(0))));
+}
+
+#[test]
+fn reload_drops_earlier_changes_except_of_id_changes() {
+    let old = Id::new();
+    let new = Id::new();
+    let compacted = compact::<TestRecord>(vec![
+        StoreViewMsg::NewAt(Position(3)),
+        StoreViewMsg::Update(Id::new()),
+        StoreViewMsg::Reload,
+        StoreViewMsg::IdChanged{old, new},
+        StoreViewMsg::Remove(Position(0)),
+        StoreViewMsg::Reload,
+        StoreViewMsg::Remove(Position(1)),
+    ]);
+
+    assert_eq!(compacted.len(), 3);
+    assert!(matches!(compacted[0], StoreViewMsg::IdChanged{old: o, new: n} if o == old && n == new));
+    assert!(matches!(compacted[1], StoreViewMsg::Reload));
+    assert!(matches!(compacted[2], StoreViewMsg::Remove(Position(1))));
+}
@@ -89,7 +89,9 @@ where
     }
 
     /// [store::StoreView::inbox_queue_size()]
+    /// 
+    /// Pending resize of the window counts as a message
     pub fn inbox_queue_size(&self) -> usize {
-        self.changes.borrow().len()
+        self.changes.borrow().len() + usize::from(self.resize.get().is_some())
     }
 }
@@ -1,3 +1,4 @@
+mod compaction;
 mod data_store;
 
 use reexport::log;
@@ -5,9 +6,12 @@ use reexport::relm4;
 use store::StoreViewMsg;
 
 
+use std::cell::Cell;
 use std::cell::RefCell;
-// use std::cmp::min;
+use std::cmp::max;
+use std::cmp::min;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use relm4::Model as ViewModel;
@@ -18,10 +22,12 @@ use relm4::factory::FactoryView;
 use record::Id;
 
 use collections::WindowChangeset;
+use collections::WindowPatch;
 use collections::DataContainer;
 use store::DataStore;
 use store::StoreViewPrototype;
 use store::Position;
+use store::SelectionModel;
 use store::math::Range;
 use store::window::StoreState;
 use store::window::WindowBehavior;
@@ -59,7 +65,14 @@ where
     #[allow(clippy::type_complexity)]
     changes: Rc<RefCell<Vec<StoreViewMsg<<Configuration::Store as DataStore>::Record>>>>,
     range: Rc<RefCell<Range>>,
-    size: usize,
+    size: Cell<usize>,
+    /// Size of the window requested by [StoreViewImplementation::set_window_size] waiting for the next batch of changes
+    resize: Cell<Option<usize>>,
+    window: RefCell<Configuration::Window>,
+    /// Widgets of the removed records waiting to be bound to the new ones
+    pool: RefCell<Vec<Configuration::RecordWidgets>>,
+    pool_size: Cell<usize>,
+    selection: RefCell<Option<SelectionModel<Configuration::Store>>>,
 }
 
 impl<Configuration> std::fmt::Debug for StoreViewImplementation<Configuration> 
@@ -70,6 +83,8 @@ where
         f.debug_struct("StoreViewImplementation")
             .field("size", &self.size)
             .field("range", &self.range)
+            .field("window", &self.window)
+            .field("pool_size", &self.pool_size)
             .finish_non_exhaustive()
     }
 }
@@ -82,7 +97,25 @@ where
     /// 
     /// - **store** store which will provide a source data
     /// - **size** size of the page
-    pub fn new(store: Configuration::Store, size: usize) -> Self {
+    /// - **window** behavior of the window in presence of changes
+    pub fn new(store: Configuration::Store, size: usize, window: Configuration::Window) -> Self {
+        Self::with_pool_size(store, size, window, 0)
+    }
+
+    /// Creates new instance of this struct which reuses the widgets of removed records
+    /// 
+    /// Widgets of the records leaving the view are reset by [StoreViewPrototype::reset_view] and kept in the pool.
+    /// Records entering the view are bound to them by [StoreViewPrototype::view]. Pool keeps up to `size` widgets,
+    /// so paging through the store doesn't create and destroy widgets for every page.
+    /// 
+    /// - **store** store which will provide a source data
+    /// - **size** size of the page
+    /// - **window** behavior of the window in presence of changes
+    pub fn with_widget_pool(store: Configuration::Store, size: usize, window: Configuration::Window) -> Self {
+        Self::with_pool_size(store, size, window, size)
+    }
+
+    fn with_pool_size(store: Configuration::Store, size: usize, window: Configuration::Window, pool_size: usize) -> Self {
         let range = Rc::new(RefCell::new(Range::new(0, size)));
 
         let changes = Rc::new(RefCell::new(Vec::new()));
@@ -95,37 +128,100 @@ where
             widgets: Rc::new(RefCell::new(HashMap::new())),
             changes,
             range,
-            size,
+            size: Cell::new(size),
+            resize: Cell::new(None),
+            window: RefCell::new(window),
+            pool: RefCell::new(Vec::new()),
+            pool_size: Cell::new(pool_size),
+            selection: RefCell::new(None),
+        }
+    }
+
+    /// Replaces the window behavior returning the old one
+    /// 
+    /// New behavior is used starting from the next batch of changes
+    pub fn set_window_behavior(&self, window: Configuration::Window) -> Configuration::Window {
+        self.window.replace(window)
+    }
+
+    /// Changes the number of records in the window
+    /// 
+    /// New size is applied with the next batch of changes, after the messages received so far. Window behavior
+    /// decides where the resized window starts, see [WindowBehavior::resize]. Only records which enter or leave the
+    /// window are loaded or dropped, widgets of the records which stay in the window are kept. If the view uses the
+    /// widget pool, the pool keeps up to `size` widgets from now on.
+    pub fn set_window_size(&self, size: usize) {
+        self.resize.set(Some(size));
+    }
+
+    /// Replaces the selection model returning the old one
+    /// 
+    /// Records which are selected in any of them are redrawn, so [StoreViewPrototype::view] is told about the new
+    /// selection
+    pub fn set_selection(&self, selection: Option<SelectionModel<Configuration::Store>>) -> Option<SelectionModel<Configuration::Store>> {
+        let old = self.selection.replace(selection);
+
+        let mut changed = HashSet::new();
+        for selection in old.iter().chain(self.selection.borrow().iter()) {
+            changed.extend(selection.selected());
         }
+        self.changes.borrow_mut().extend(changed.into_iter().map(StoreViewMsg::Update));
+
+        old
+    }
+
+    /// Returns `true` if the record is selected in the attached selection model
+    pub(crate) fn is_selected(&self, id: &Id<<Configuration::Store as DataStore>::Record>) -> bool {
+        matches!(self.selection.borrow().as_ref(), Some(selection) if selection.is_selected(id))
     }
 
     /// Adds message to the inbox
     /// 
     /// Messages are handled at the render time in batch
     pub fn inbox(&self, message: StoreViewMsg<<Configuration::Store as DataStore>::Record>) {
-        self.changes.borrow_mut().push(message);
+        match message {
+            StoreViewMsg::Batch(mut messages) => self.changes.borrow_mut().append(&mut messages),
+            // data didn't change, so there is nothing to redraw
+            StoreViewMsg::Error(err) => log::warn!("Store reported an error: {}", err),
+            message => self.changes.borrow_mut().push(message),
+        }
     }
 
     fn convert_to_transition(&self, state: &StoreState<'_>, message: &StoreViewMsg<<Configuration::Store as DataStore>::Record>) -> WindowTransition {
+        let mut window = self.window.borrow_mut();
         match message {
             StoreViewMsg::NewAt(p) => {
-                Configuration::Window::insert(state, &p.to_point())
+                window.insert(state, &p.to_point())
+            },
+            StoreViewMsg::NewRange{from, to} => {
+                window.insert_range(state, &Range::new(from.0, to.0))
             },
             StoreViewMsg::Move{from, to} => {
-                Configuration::Window::slide(state, &Range::new(from.0, to.0))
+                window.move_record(state, &from.to_point(), &to.to_point())
             },
             StoreViewMsg::Reorder{from, to} => {
-                Configuration::Window::slide(state, &Range::new(from.0, to.0))
+                window.reorder(state, &Range::new(from.0, to.0))
             },
             StoreViewMsg::Remove(at) => {
-                Configuration::Window::remove(state, &at.to_point())
+                window.remove(state, &at.to_point())
+            },
+            StoreViewMsg::RemoveRange{from, to} => {
+                window.remove_range(state, &Range::new(from.0, to.0))
             },
             StoreViewMsg::Update(_) => {
                 WindowTransition::Identity
             },
+            StoreViewMsg::IdChanged{old, new} => {
+                window.id_changed(old, new);
+                WindowTransition::Identity
+            },
             StoreViewMsg::Reload => {
                 WindowTransition::Identity
             },
+            // batches are unpacked and errors are dropped by the inbox
+            StoreViewMsg::Batch(_) | StoreViewMsg::Error(_) => {
+                WindowTransition::Identity
+            },
         }
     }
 
@@ -188,7 +284,7 @@ where
         
         let position = pos - start;
 
-        if start == 0 && view.len() + by <= self.size {
+        if start == 0 && view.len() + by <= self.size.get() {
             view.insert_right(changeset, position, data);
         }
         else {
@@ -338,10 +434,65 @@ where
         view.remove_left(changeset, container_position, by, left_data, right_data);
     }
 
+    /// Reads records in `[from, to)` again from the store and puts them in place of the old ones
+    /// 
+    /// Only positions loaded into the view are read, the rest of the range is ignored
+    fn reorder(&self, changeset: &mut WindowChangeset<<Configuration::Store as DataStore>::Record>, from: usize, to: usize) {
+        let start = *self.range.borrow().start();
+        let mut view = self.view.borrow_mut();
+        let from = max(from, start);
+        let to = min(to, start + view.len());
+        if from >= to {
+            return;
+        }
+
+        let records = self.store.get_range(&Range::new(from, to));
+        view.reorder(changeset, from - start, records);
+    }
+
+    /// Resizes the window to `size` records keeping the records which stay in the window
+    fn resize(&self, changeset: &mut WindowChangeset<<Configuration::Store as DataStore>::Record>, size: usize) {
+        let old_range = *self.range.borrow();
+        let loaded = self.view.borrow().len();
+        let new_range = {
+            let state = StoreState{
+                page: &old_range,
+                view: loaded,
+            };
+            self.window.borrow_mut().resize(&state, size)
+        };
+
+        log::trace!("Resize from {:?} to {:?}", old_range, new_range);
+
+        self.size.set(size);
+        if self.pool_size.get() > 0 {
+            self.pool_size.set(size);
+        }
+        self.range.replace(new_range);
+
+        // positions in the store of the records which are loaded and stay in the window
+        let loaded_start = *old_range.start();
+        let keep_start = max(loaded_start, *new_range.start());
+        let keep_end = min(loaded_start + loaded, *new_range.end());
+
+        let mut view = self.view.borrow_mut();
+        if keep_start >= keep_end {
+            let records = self.store.get_range(&new_range);
+            view.resize(changeset, size, loaded, vec![], records);
+        }
+        else {
+            let left = self.store.get_range(&Range::new(*new_range.start(), keep_start));
+            let right = self.store.get_range(&Range::new(keep_end, *new_range.end()));
+            view.resize(changeset, size, keep_start - loaded_start, left, right);
+        }
+    }
+
     fn compile_changes(&self) -> WindowChangeset<<Configuration::Store as DataStore>::Record> {
         let mut changeset = WindowChangeset::default();
 
-        for change in self.changes.borrow_mut().iter() {
+        let changes = compaction::compact(self.changes.replace(vec![]));
+
+        for change in changes.iter() {
             let transition = {
                 let state = StoreState{
                     page: {
@@ -364,8 +515,22 @@ where
                                 view.update(record);
                             }
                         },
+                        StoreViewMsg::IdChanged{old, new} => {
+                            let mut view = self.view.borrow_mut();
+                            if let Some(record) = self.store.get(new) {
+                                view.change_id(&mut changeset, old, record);
+                            }
+                        },
                         StoreViewMsg::Reload => {
                             log::trace!("Reload");
+                            let range = {
+                                let state = StoreState{
+                                    page: &self.range.borrow(),
+                                    view: self.view.borrow().len(),
+                                };
+                                self.window.borrow_mut().reload(&state)
+                            };
+                            self.range.replace(range);
                             changeset.reload = true;
                             self.reload(&mut changeset);
                         },
@@ -388,6 +553,15 @@ where
                     log::trace!("RemoveRight");
                     self.remove_right(&mut changeset, pos, by);
                 }
+                WindowTransition::Move{from, to} => {
+                    log::trace!("Move");
+                    // records between `from` and `to` (both inclusive) are shifted
+                    self.reorder(&mut changeset, min(from, to), max(from, to) + 1);
+                }
+                WindowTransition::Reorder{from, to} => {
+                    log::trace!("Reorder");
+                    self.reorder(&mut changeset, from, to);
+                }
                 WindowTransition::SlideLeft(by) => {
                     log::trace!("SlideLeft");
 
@@ -413,58 +587,31 @@ where
                     self.reload(&mut changeset);
                 }
                 WindowTransition::SlideRight(by) => {
-                    //exceeds is true if we try to slide outside of available data
-                    let exceeds = {
+                    log::trace!("SlideRight");
+
+                    // Window can't go past the last page of the store. If it's already there, or there is less
+                    // data in the store then one page, it stays in place.
+                    //
+                    // TODO: Check for data overlap, currently we just force a full reload which might be more
+                    //   expensive then needed
+                    let new_range = {
                         let range = self.range.borrow();
-                        self.len() >= range.end() + by
+                        let start = *range.start();
+                        let last_page = self.len().saturating_sub(self.size.get());
+                        range.slide(min(start + by, max(start, last_page)))
                     };
 
-                    if by > self.size || exceeds {
-                        // Two cases solved here
-                        // 1. Sliding more then page size, so we must reload whole range
-                        // 2. Trying to go outside of data range of the store
-                        //   2.1 There is less data in the store then one page so keep being on the first page
-                        //   2.2 In all other cases stay on the last page
-                        //
-                        // TODO: Check in case 2 if we can reuse the data which already are in the store
-                        //   currently we don't check for data overlap, just force a full reload which might
-                        //   be more expensive then needed
-                        let new_range = {
-                            let range = self.range.borrow();
-                            let new_end = range.end() + by;
-                            if new_end > self.len() {
-                                // Case 2
-                                if self.size > self.len() {
-                                    // Case 2.2
-                                    range.slide(self.len()-self.size)
-                                }
-                                else {
-                                    // Case 2.1
-                                    range.slide(0)
-                                }
-                            }
-                            else {
-                                // Case 1
-                                range.slide(range.start()+by)
-                            }
-                        };
-
-                        self.range.replace(new_range);
-                        self.reload(&mut changeset);
-                    }
-                    else {
-                        let new_range = {
-                            let range = self.range.borrow();
-                            range.slide(range.start() + by)
-                        };
-                        self.range.replace(new_range);
-                        self.reload(&mut changeset);
-                    }
+                    self.range.replace(new_range);
+                    self.reload(&mut changeset);
                 }
             }
         }
 
-        self.changes.replace(vec![]);
+        if let Some(size) = self.resize.take() {
+            self.resize(&mut changeset, size);
+        }
+
+        self.view.borrow_mut().patch(&mut changeset);
 
         // if we need to add record since it was not present we can remove it from updates
         //
@@ -476,13 +623,22 @@ where
         changeset
     }
 
+    /// Puts widgets of the removed record into the pool if the pool is used and isn't full yet
+    fn recycle(&self, record_widgets: Configuration::RecordWidgets) {
+        let mut pool = self.pool.borrow_mut();
+        if pool.len() < self.pool_size.get() {
+            Configuration::reset_view(&record_widgets);
+            pool.push(record_widgets);
+        }
+    }
+
     /// Implementation of the [relm4::factory::FactoryPrototype::generate]
     pub fn view(&self, view: &Configuration::View, sender: Sender<<Configuration::ViewModel as ViewModel>::Msg>) {
         log::info!("[StoreViewImplementation::generate]");
 
         let empty = {
             let changes = self.changes.borrow();
-            changes.is_empty()
+            changes.is_empty() && self.resize.get().is_none()
         };
 
         if empty { 
@@ -490,115 +646,106 @@ where
             return
         }
 
-        let old_order = {
-            let view_order = self.view.borrow();
-            let iter = view_order.ordered_record_ids();
-            let mut v = Vec::with_capacity(view_order.len());
-            for id in iter { //manual copy cos of the lifetime
-                v.push(*id);
-            }
-
-            v
-        };
-        let old_order_len = old_order.len();
-
         let WindowChangeset{
-            ids_to_remove,
             ids_to_add,
             ids_to_update,
-            reload: _,
+            ids_changed,
+            patch,
+            ..
         } = self.compile_changes();
 
-        if ids_to_remove.is_empty() && ids_to_add.is_empty() && ids_to_update.is_empty() {
+        if patch.is_empty() && ids_to_add.is_empty() && ids_to_update.is_empty() {
             //if all changes leads to identity then return
             return
         }
 
         let mut widgets = self.widgets.borrow_mut();
+
+        // records which got new id keep their widgets
+        for (old, new) in ids_changed {
+            if let Some(widget) = widgets.remove(&old) {
+                widgets.insert(new, widget);
+            }
+        }
         let view_order = self.view.borrow();
+        let start = *self.range.borrow().start();
 
         log::trace!("[StoreViewImplementation::generate] view should have same length as data.\t\tview.len(): {}", view_order.len());
         log::trace!("[StoreViewImplementation::generate] widgets should have same length as view.\twidgets.len(): {}", widgets.len());
-        log::trace!("[StoreViewImplementation::generate] Ordered record ids should have same length as view. \tview_order.ordered_record_ids(): {}", view_order.ordered_record_ids().len());
         log::trace!("[StoreViewImplementation::generate] Changes should be empty. Is it? {}", self.changes.borrow().is_empty());
         log::trace!("[StoreViewImplementation::generate]");
-        log::trace!("[StoreViewImplementation::generate] ids_to_add.len(): {}", ids_to_add.len());
+        log::trace!("[StoreViewImplementation::generate] patch.len(): {}", patch.len());
         log::trace!("[StoreViewImplementation::generate] ids_to_update.len(): {}", ids_to_update.len());
 
-        let mut position: Position = Position(*self.range.borrow().start());
-        let range = self.range.borrow();
-        for id in view_order.ordered_record_ids() {
-            if ids_to_add.contains(id) {
-                log::trace!("[StoreViewImplementation::generate] Id to add: {:?}", id);
-                if let Some(record) = self.get(id) {
-                    log::trace!("[StoreViewImplementation::generate] Got record {:?}", record);
-                    let new_widgets = Configuration::init_view(&record, position, sender.clone());
-                    let widgets_root = Configuration::root_widget(&new_widgets);
-
-                    let root = if widgets.is_empty() || position.0 == *range.start() {
-                        log::trace!("[StoreViewImplementation::generate] Adding first element");
-                        view.push_front(widgets_root)
+        // widgets which must be synchronized with the records after the patch is applied
+        let mut to_refresh: HashSet<Id<<Configuration::Store as DataStore>::Record>> = ids_to_update;
+        to_refresh.extend(ids_to_add);
+
+        for change in patch {
+            match change {
+                WindowPatch::Remove{id, ..} => {
+                    log::trace!("[StoreViewImplementation::generate] Id to remove: {:?}", id);
+                    if let Some(widget) = widgets.remove(&id) {
+                        view.remove(&widget.root);
+                        self.recycle(widget.widgets);
                     }
-                    else {
-                        log::trace!("[StoreViewImplementation::generate] Adding non first element");
-                        let prev_idx = (position - 1 - *range.start()).0;
-                        log::info!("Index of previous elements: {}", prev_idx);
-                        let prev_id = view_order.get_record_id_at((position - 1 - *range.start()).0);
-                        let prev = widgets.get(prev_id).unwrap();
-                        view.insert_after(widgets_root, &prev.root)
-                    };
-    
-                    widgets.insert(
-                        *id,
-                        widgets::Widgets{
-                            widgets: new_widgets,
-                            root,
-                        }
-                    );
-                }
-            }
-            
-            if ids_to_update.contains(id) {
-                
-                if let Some(record) = self.get(id) {
-                    if let Some( widget ) = widgets.get_mut(id) {
-                        <Configuration as StoreViewPrototype>::view(record, position, &widget.widgets);
-                        if old_order_len > position.0 && old_order[position.0] != *id {
-                            // things got reordered so we need to remove widget from old place and attach it to the new one
-                            if let Some(widget) = widgets.remove(id) {
-                                view.remove(&widget.root);
-                                let root = if position.0 == *range.start() {
-                                    view.push_front(Configuration::root_widget(&widget.widgets))
+                },
+                WindowPatch::Insert{at, id} => {
+                    log::trace!("[StoreViewImplementation::generate] Id to add: {:?}", id);
+                    if let Some(record) = self.get(&id) {
+                        log::trace!("[StoreViewImplementation::generate] Got record {:?}", record);
+                        let position = Position(start + at);
+                        let selected = self.is_selected(&id);
+                        let recycled = self.pool.borrow_mut().pop();
+                        let new_widgets = match recycled {
+                            Some(recycled) => {
+                                <Configuration as StoreViewPrototype>::view(record, position, selected, &recycled);
+                                recycled
+                            },
+                            None => {
+                                let new_widgets = Configuration::init_view(&record, position, sender.clone());
+                                if selected {
+                                    <Configuration as StoreViewPrototype>::view(record, position, selected, &new_widgets);
                                 }
-                                else {
-                                    let prev_idx = (position - 1 - *range.start()).0;
-                                    log::info!("Index of previous elements: {}", prev_idx);
-                                    let prev_id = view_order.get_record_id_at((position - 1 - *range.start()).0);
-                                    let prev = widgets.get(prev_id).unwrap();
-                                    view.insert_after(Configuration::root_widget(&widget.widgets), &prev.root)
-                                };
+                                new_widgets
+                            },
+                        };
 
-                                widgets.insert(
-                                    *id,
-                                    widgets::Widgets{
-                                        widgets: widget.widgets,
-                                        root,
-                                    }
-                                );
+                        let root = Self::attach(view, &widgets, &view_order, at, Configuration::root_widget(&new_widgets));
+                        widgets.insert(
+                            id,
+                            widgets::Widgets{
+                                widgets: new_widgets,
+                                root,
                             }
-                            
-                        }
+                        );
+                        to_refresh.remove(&id);
                     }
-                }
-
+                },
+                WindowPatch::Move{to, id, ..} => {
+                    log::trace!("[StoreViewImplementation::generate] Id to move: {:?}", id);
+                    // widget is kept, so it doesn't loose it's state
+                    if let Some(widget) = widgets.remove(&id) {
+                        view.remove(&widget.root);
+                        let root = Self::attach(view, &widgets, &view_order, to, Configuration::root_widget(&widget.widgets));
+                        widgets.insert(
+                            id,
+                            widgets::Widgets{
+                                widgets: widget.widgets,
+                                root,
+                            }
+                        );
+                        to_refresh.insert(id);
+                    }
+                },
             }
-
-            position = position + 1;
         }
 
-        for id in ids_to_remove {
-            if let Some(widget) = widgets.remove(&id) {
-                view.remove(&widget.root);
+        for (idx, id) in view_order.ordered_record_ids().enumerate() {
+            if to_refresh.contains(id) {
+                if let (Some(record), Some(widget)) = (self.get(id), widgets.get(id)) {
+                    <Configuration as StoreViewPrototype>::view(record, Position(start + idx), self.is_selected(id), &widget.widgets);
+                }
             }
         }
 
@@ -607,4 +754,23 @@ where
         log::trace!("[StoreViewImplementation::generate] widgets should have same length as view.\twidgets.len(): {}", widgets.len());
         log::trace!("[StoreViewImplementation::generate] Should be empty. Is it? {}", self.changes.borrow().is_empty());
     }
+
+    /// Attaches `root` of the record at `at` right after the widget of the previous record in the view
+    #[allow(clippy::type_complexity)]
+    fn attach(
+        view: &Configuration::View,
+        widgets: &HashMap<Id<<Configuration::Store as DataStore>::Record>, widgets::Widgets<Configuration::RecordWidgets, <Configuration::View as FactoryView<Configuration::Root>>::Root>>,
+        view_order: &DataContainer<<Configuration::Store as DataStore>::Record>,
+        at: usize,
+        root: &Configuration::Root,
+    ) -> <Configuration::View as FactoryView<Configuration::Root>>::Root {
+        // previous record might be missing if the store didn't return it
+        let prev = (0..at).rev()
+            .find_map(|idx| widgets.get(view_order.get_record_id_at(idx)));
+
+        match prev {
+            Some(prev) => view.insert_after(root, &prev.root),
+            None => view.push_front(root),
+        }
+    }
 }
@@ -36,7 +36,9 @@ use store::StoreId;
 use store::StoreSize;
 use store::StoreViewPrototype;
 use store::redraw_messages::RedrawMessages;
+use store::PageOutOfRange;
 use store::Pagination;
+use store::SelectionModel;
 
 /// StoreView implementation
 pub struct View<Configuration>
@@ -56,12 +58,25 @@ where
     Configuration: ?Sized + StoreViewPrototype + 'static,
 {
     /// Creates new instance of the View
+    /// 
+    /// Window behavior is created by [StoreViewPrototype::init_window]
     pub fn new(store: Configuration::Store, size: StoreSize, redraw_sender: Sender<RedrawMessages>) -> Self {
+        let implementation = StoreViewImplementation::new(store.clone(), size.items(), Configuration::init_window(&store));
+        Self::from_implementation(store, implementation, redraw_sender)
+    }
+
+    /// Creates new instance of the View which reuses the widgets of records leaving the view
+    /// 
+    /// Pool keeps up to `size` widgets. See [StoreViewImplementation::with_widget_pool] for details.
+    pub fn with_widget_pool(store: Configuration::Store, size: StoreSize, redraw_sender: Sender<RedrawMessages>) -> Self {
+        let implementation = StoreViewImplementation::with_widget_pool(store.clone(), size.items(), Configuration::init_window(&store));
+        Self::from_implementation(store, implementation, redraw_sender)
+    }
+
+    fn from_implementation(store: Configuration::Store, implementation: StoreViewImplementation<Configuration>, redraw_sender: Sender<RedrawMessages>) -> Self {
         let id = StoreId::new();
 
-        let implementation = Rc::new(RefCell::new(
-            StoreViewImplementation::new(store.clone(), size.items())
-        ));
+        let implementation = Rc::new(RefCell::new(implementation));
         let handler_implementation = implementation.clone();
         let handler_redraw_sender = redraw_sender.clone();
         let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
@@ -69,6 +84,13 @@ where
         {
             let context = glib::MainContext::default();
             receiver.attach(Some(&context), move |msg| {
+                if let StoreViewMsg::Error(err) = msg {
+                    // data didn't change, so there is nothing to redraw
+                    log::warn!("Store reported an error: {}", err);
+                    handler_redraw_sender.send(RedrawMessages::Error(err)).expect("Unexpected failure while sending message via redraw_sender");
+                    return glib::Continue(true)
+                }
+
                 if let Ok(implementation) = handler_implementation.try_borrow_mut() {
                     implementation.inbox(msg);
                     log::trace!("StoreView is sending redraw message");
@@ -92,6 +114,34 @@ where
             redraw_sender,
         }
     }
+
+    /// Replaces the window behavior returning the old one
+    /// 
+    /// New behavior is used starting from the next batch of changes. For example log view can switch from
+    /// [KeepOnBottom](store::window::KeepOnBottom) to [ValueTrackingWindow](store::window::ValueTrackingWindow)
+    /// when the user scrolls up, so new entries don't move the records user is reading.
+    pub fn set_window_behavior(&self, window: Configuration::Window) -> Configuration::Window {
+        self.implementation.borrow().set_window_behavior(window)
+    }
+
+    /// Attaches the selection model to the view returning the previous one
+    ///
+    /// [StoreViewPrototype::view] is told if the record is selected and records are redrawn every time their
+    /// selection changes. Selection is kept by the selection model, so it survives paging.
+    pub fn set_selection(&self, selection: Option<SelectionModel<Configuration::Store>>) -> Option<SelectionModel<Configuration::Store>> {
+        let listened = selection.clone();
+
+        let old = self.implementation.borrow().set_selection(selection);
+        if let Some(old) = &old {
+            old.unlisten(self.id.transfer());
+        }
+        if let Some(selection) = &listened {
+            selection.listen(self.id.transfer(), self.sender.clone());
+        }
+
+        self.redraw_sender.send(RedrawMessages::Redraw).expect("Unexpected failure while sending message via redraw_sender");
+        old
+    }
 }
 
 impl<Configuration> Identifiable<Self, <<Configuration::Store as DataStore>::Allocator as TemporaryIdAllocator>::Type> for View<Configuration>
@@ -165,6 +215,11 @@ where
         self.send(StoreViewMsg::Reload);
     }
 
+    fn set_window_size(&self, size: usize) {
+        self.implementation.borrow().set_window_size(size);
+        self.redraw_sender.send(RedrawMessages::Redraw).expect("Unexpected failure while sending message via redraw_sender");
+    }
+
     fn get_view_data(&self) -> Vec<store::RecordWithLocation<Self::Record>> {
         self.implementation.borrow().get_view_data()
     }
@@ -207,6 +262,21 @@ where
         self.set_window(range);
     }
 
+    fn to_page(&self, page: usize) -> Result<(), PageOutOfRange> {
+        let total_pages = self.total_pages();
+        if page == 0 || page > total_pages.max(1) {
+            return Err(PageOutOfRange{
+                page,
+                total_pages,
+            })
+        }
+
+        let size = self.window_size();
+        let start = (page-1)*size;
+        self.set_window(Range::new(start, start+size));
+        Ok(())
+    }
+
     fn inbox_queue_size(&self) -> usize {
         self.implementation.borrow().inbox_queue_size()
     }
@@ -251,7 +321,8 @@ where
     ) {
         let model = self.get(key).expect("Key doesn't point to the model in the store while updating! WTF?");
         let position = self.get_position(&model.get_id()).expect("Unsynchronized view with store! WTF?");
-        <Configuration as StoreViewPrototype>::view(model, position, widgets)
+        let selected = self.implementation.borrow().is_selected(&model.get_id());
+        <Configuration as StoreViewPrototype>::view(model, position, selected, widgets)
     }
 
     /// Get the outermost widget from the widgets.
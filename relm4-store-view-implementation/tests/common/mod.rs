@@ -3,37 +3,121 @@ use reexport::gtk;
 use reexport::relm4;
 use reexport::relm4::factory::Factory;
 
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use gtk::prelude::WidgetExt;
 use relm4::Sender;
 
+use record::Record;
 use backend_dummy::DummyBackend;
 use backend_dummy::StepByStepStore;
 use backend_dummy::test_cases::TestCase;
 use backend_dummy::test_cases::TestRecord;
 use store::DataStore;
+use store::SelectionMode;
+use store::SelectionModel;
 use store::StoreViewPrototype;
 use store::Position;
 use store::Store;
 use store::StoreSize;
+use store::StoreView;
 use store::math::Range;
 use store::redraw_messages::RedrawMessages;
+use store::window::KeepOnBottom;
+use store::window::KeepOnTop;
+use store::window::PositionTrackingWindow;
+use store::window::RecordTrackingWindow;
+use store::window::ValueTrackingWindow;
 use store::window::WindowBehavior;
 
 use relm4_store_view_implementation::View;
 
+thread_local! {
+    static INIT_VIEW_CALLS: Cell<usize> = const { Cell::new(0) };
+    static RESET_VIEW_CALLS: Cell<usize> = const { Cell::new(0) };
+    static DRAWN_SELECTED: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    static SELECTION: RefCell<Option<SelectionModel<Store<DummyBackend<TestRecord>>>>> = const { RefCell::new(None) };
+}
+
+/// Number of times [StoreViewPrototype::init_view] was called in the current test
+pub fn init_view_calls() -> usize {
+    INIT_VIEW_CALLS.with(|calls| calls.get())
+}
+
+/// Number of times [StoreViewPrototype::reset_view] was called in the current test
+pub fn reset_view_calls() -> usize {
+    RESET_VIEW_CALLS.with(|calls| calls.get())
+}
+
+/// Returns `true` if the last [StoreViewPrototype::view] call for the record with given label was told it's selected
+pub fn drawn_selected(label: &str) -> bool {
+    DRAWN_SELECTED.with(|drawn| drawn.borrow().contains(label))
+}
+
+/// Selection model attached to the store view by [StoreViewTest::selected]
+pub fn selection() -> SelectionModel<Store<DummyBackend<TestRecord>>> {
+    SELECTION.with(|selection| selection.borrow().clone()).expect("Selection model is attached only by `StoreViewTest::selected`")
+}
+
 #[derive(Debug)]
 pub struct TestWidgets {
     root: gtk::Box,
 }
 
+/// Position of the record which is an anchor for the [RecordTrackingWindow]
+pub const ANCHOR: usize = 15;
+
+/// Creates the window behavior for the tests
+pub trait TestWindow: WindowBehavior {
+    fn init(store: &Store<DummyBackend<TestRecord>>) -> Self;
+}
+
+impl TestWindow for PositionTrackingWindow {
+    fn init(_store: &Store<DummyBackend<TestRecord>>) -> Self {
+        PositionTrackingWindow::new()
+    }
+}
+
+impl TestWindow for ValueTrackingWindow {
+    fn init(_store: &Store<DummyBackend<TestRecord>>) -> Self {
+        ValueTrackingWindow::new()
+    }
+}
+
+impl TestWindow for KeepOnTop {
+    fn init(_store: &Store<DummyBackend<TestRecord>>) -> Self {
+        KeepOnTop::new()
+    }
+}
+
+impl TestWindow for KeepOnBottom {
+    fn init(_store: &Store<DummyBackend<TestRecord>>) -> Self {
+        KeepOnBottom::new()
+    }
+}
+
+impl TestWindow for RecordTrackingWindow<Store<DummyBackend<TestRecord>>> {
+    /// Anchors the window on the record at [ANCHOR] position
+    fn init(store: &Store<DummyBackend<TestRecord>>) -> Self {
+        let mut window = RecordTrackingWindow::new(store.clone());
+        let anchor = store.get_range(&Range::new(ANCHOR, ANCHOR+1))
+            .first()
+            .map(|record| (record.get_id(), Position(ANCHOR)));
+        window.set_anchor(anchor);
+        window
+    }
+}
+
 #[derive(Debug)]
-pub struct TestConfig<Window: 'static + WindowBehavior> {
+pub struct TestConfig<Window: 'static + TestWindow> {
     _window: PhantomData<*const Window>,
 }
 
-impl<Window: 'static + WindowBehavior + Debug> StoreViewPrototype for TestConfig<Window> {
+impl<Window: 'static + TestWindow + Debug> StoreViewPrototype for TestConfig<Window> {
     type Store = Store<DummyBackend<TestRecord>>;
     type StoreView = View<Self>;
     type RecordWidgets = TestWidgets;
@@ -47,13 +131,36 @@ impl<Window: 'static + WindowBehavior + Debug> StoreViewPrototype for TestConfig
         View::new(store, size, redraw_sender)
     }
 
-    fn init_view(_record: &<Self::Store as store::DataStore>::Record, _position: Position, _sender: Sender<()>) -> Self::RecordWidgets {
+    fn init_window(store: &Self::Store) -> Self::Window {
+        Window::init(store)
+    }
+
+    fn init_view(record: &<Self::Store as store::DataStore>::Record, _position: Position, _sender: Sender<()>) -> Self::RecordWidgets {
+        INIT_VIEW_CALLS.with(|calls| calls.set(calls.get() + 1));
+        let root = gtk::Box::default();
+        root.set_widget_name(&record.label);
+        DRAWN_SELECTED.with(|drawn| drawn.borrow_mut().remove(&record.label));
         TestWidgets{
-            root: gtk::Box::default()
+            root
         }
     }
 
-    fn view(_model: <Self::Store as store::DataStore>::Record, _position: Position, _widgets: &Self::RecordWidgets) {}
+    fn view(model: <Self::Store as store::DataStore>::Record, _position: Position, selected: bool, widgets: &Self::RecordWidgets) {
+        widgets.root.set_widget_name(&model.label);
+        DRAWN_SELECTED.with(|drawn| {
+            let mut drawn = drawn.borrow_mut();
+            if selected {
+                drawn.insert(model.label);
+            }
+            else {
+                drawn.remove(&model.label);
+            }
+        });
+    }
+
+    fn reset_view(_widgets: &Self::RecordWidgets) {
+        RESET_VIEW_CALLS.with(|calls| calls.set(calls.get() + 1));
+    }
 
     fn update(_view_model: &mut Self::ViewModel, _msg: (), _sender: Sender<()>) {}
     
@@ -66,24 +173,54 @@ impl<Window: 'static + WindowBehavior + Debug> StoreViewPrototype for TestConfig
     }
 }
 
+/// Asserts that the view shows records of the store which are in it's window
+pub fn assert_window_content<Window: 'static + TestWindow + Debug>(store_view: &View<TestConfig<Window>>, store_data: &[TestRecord]) {
+    let window = store_view.get_window();
+    let data = store_view.get_view_data();
+    assert_eq!(data.len(), window.len(), "View must be full");
+    for (idx, item) in data.iter().enumerate() {
+        let position = window.start() + idx;
+        assert_eq!(item.position, Position(position), "Position of {}th record don't match", idx);
+        assert_eq!(item.record, store_data[position], "{}th record don't match", idx);
+    }
+}
+
+/// Asserts that widgets in the `container` are in the same order as the records in the view
+pub fn assert_widgets_order<Window: 'static + TestWindow + Debug>(container: &gtk::Box, store_view: &View<TestConfig<Window>>) {
+    let mut labels = Vec::new();
+    let mut child = container.first_child();
+    while let Some(widget) = child {
+        labels.push(widget.widget_name().to_string());
+        child = widget.next_sibling();
+    }
+
+    let expected: Vec<String> = store_view.get_view_data()
+        .iter()
+        .map(|item| item.record.label.clone())
+        .collect();
+    assert_eq!(labels, expected, "Widgets must be in the same order as records");
+}
+
 pub type Assertion<Window> = &'static dyn Fn(&Vec<TestRecord>, &View<TestConfig<Window>>, &Vec<TestRecord>) -> ();
 pub type Prepare<Window> = &'static dyn Fn(&View<TestConfig<Window>>) -> bool;
 
 pub struct StoreViewTest<Window>
 where
-    Window: 'static + WindowBehavior + Debug
+    Window: 'static + TestWindow + Debug
 {
     asserts: Vec<Assertion<Window>>,
     initial_assertion: Assertion<Window>,
     prepare: Option<Prepare<Window>>,
     test_case: TestCase,
     window_size: StoreSize,
+    widget_pool: bool,
+    selected: &'static [usize],
     _window: PhantomData<*const Window>,
 }
 
 impl<Window> StoreViewTest<Window>
 where
-    Window: 'static + WindowBehavior + Debug,
+    Window: 'static + TestWindow + Debug,
 {
     pub fn from(config: TestCase) -> StoreViewTest<Window> {
         StoreViewTest{
@@ -92,6 +229,8 @@ where
             prepare: None,
             test_case: config,
             window_size: StoreSize::Unlimited,
+            widget_pool: false,
+            selected: &[],
             _window: PhantomData,
         }
     }
@@ -117,6 +256,18 @@ where
         self
     }
 
+    /// Store view reuses the widgets of removed records
+    pub fn widget_pool(&mut self) -> &mut Self {
+        self.widget_pool = true;
+        self
+    }
+
+    /// Store view gets the selection model with records at given positions selected
+    pub fn selected(&mut self, positions: &'static [usize]) -> &mut Self {
+        self.selected = positions;
+        self
+    }
+
     pub fn prepare(&mut self, f: Prepare<Window>) -> &mut Self {
         self.prepare = Some(f);
         self
@@ -135,20 +286,38 @@ where
 
         let mut data_store: Store<DummyBackend<TestRecord>> = Store::new(DummyBackend::new(self.test_case.configuration.clone()));
 
-        let store_view: View<TestConfig<Window>> = View::new(
-            data_store.clone(), 
-            self.window_size,
-            redraw_sender,
-        );
+        INIT_VIEW_CALLS.with(|calls| calls.set(0));
+        RESET_VIEW_CALLS.with(|calls| calls.set(0));
+        DRAWN_SELECTED.with(|drawn| drawn.borrow_mut().clear());
+        SELECTION.with(|selection| selection.replace(None));
+
+        let store_view: View<TestConfig<Window>> = if self.widget_pool {
+            View::with_widget_pool(data_store.clone(), self.window_size, redraw_sender)
+        }
+        else {
+            View::new(data_store.clone(), self.window_size, redraw_sender)
+        };
+
+        if !self.selected.is_empty() {
+            let selection = SelectionModel::new(data_store.clone(), SelectionMode::Multiple);
+            for position in self.selected {
+                let record = data_store.get_range(&Range::new(*position, *position+1)).pop().expect("Selected position must be in the store");
+                selection.select(&record.get_id());
+            }
+            store_view.set_selection(Some(selection.clone()));
+            SELECTION.with(|s| s.replace(Some(selection)));
+        }
 
         // StoreView is using `Reload` event to populate itself
         context.iteration(false);
         store_view.generate(&container, view_sender.clone());
+        assert_widgets_order(&container, &store_view);
 
         if let Some(p) = self.prepare {
             let block = p(&store_view);
             context.iteration(block);
             store_view.generate(&container, view_sender.clone());
+            assert_widgets_order(&container, &store_view);
         }
         
 
@@ -164,6 +333,7 @@ where
             }
             context.iteration(false);
             store_view.generate(&container, view_sender.clone());
+            assert_widgets_order(&container, &store_view);
             let data_store_len = data_store.len();
             assertion(&self.test_case.data, &store_view, &data_store.get_range(&Range::new(0, data_store_len)));
         }
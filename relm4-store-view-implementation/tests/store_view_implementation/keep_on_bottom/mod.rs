@@ -3,4 +3,7 @@ use crate::common::StoreViewTest;
 
 type ST = StoreViewTest<KeepOnBottom>;
 
-mod remove;
\ No newline at end of file
+mod moves;
+mod ranges;
+mod remove;
+mod resize;
@@ -0,0 +1,57 @@
+use super::ST;
+
+mod last_page {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreView;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_first_to_last() {
+        ST::from(TestCases::move_nth(0, 2, 3))
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.current_len(), 3, "Store must keep all the records");
+                let data = store_view.get_view_data();
+                assert_eq!(data[0].position, Position(0));
+                assert_eq!(data[1].position, Position(1));
+                assert_eq!(data[2].position, Position(2));
+                assert_eq!(data[0].record, test_data[1]);
+                assert_eq!(data[1].record, test_data[2]);
+                assert_eq!(data[2].record, test_data[0]);
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_last_to_first() {
+        ST::from(TestCases::move_nth(2, 0, 3))
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.current_len(), 3, "Store must keep all the records");
+                let data = store_view.get_view_data();
+                assert_eq!(data[0].record, test_data[2]);
+                assert_eq!(data[1].record, test_data[0]);
+                assert_eq!(data[2].record, test_data[1]);
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn reorder() {
+        ST::from(TestCases::reverse_range(0, 3, 3))
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.current_len(), 3, "Store must keep all the records");
+                let data = store_view.get_view_data();
+                assert_eq!(data[0].position, Position(0));
+                assert_eq!(data[2].position, Position(2));
+                assert_eq!(data[0].record, test_data[2]);
+                assert_eq!(data[1].record, test_data[1]);
+                assert_eq!(data[2].record, test_data[0]);
+            })
+            .run();
+    }
+}
@@ -0,0 +1,57 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn add_range_at_the_end() {
+    ST::from(TestCases::add_range(30, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.last_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(23, 33), "Window must stay at the bottom");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_in_page() {
+    ST::from(TestCases::remove_range(25, 28, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.last_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(17, 27), "Window must stay at the bottom");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_over_page_start() {
+    ST::from(TestCases::remove_range(15, 22, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.last_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(13, 23), "Window must stay at the bottom");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
@@ -0,0 +1,48 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn shrinking_window_keeps_last_record() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.last_page();
+            view.set_window_size(3);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(22, 25));
+            let data = store_view.get_view_data();
+            assert_eq!(data.len(), 3);
+            assert_eq!(data[2].record, store_data[24], "Last record must stay in the view");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn growing_window_loads_records_on_the_left() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.last_page();
+            view.set_window_size(8);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(17, 25));
+            let data = store_view.get_view_data();
+            assert_eq!(data.len(), 8);
+            assert_eq!(data[0].record, store_data[17]);
+            assert_eq!(data[7].record, store_data[24]);
+        })
+        .skip_step()
+        .run();
+}
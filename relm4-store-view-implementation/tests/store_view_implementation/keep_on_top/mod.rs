@@ -3,4 +3,6 @@ use crate::common::StoreViewTest;
 
 type ST = StoreViewTest<KeepOnTop>;
 
-mod remove;
\ No newline at end of file
+mod moves;
+mod ranges;
+mod remove;
@@ -0,0 +1,82 @@
+use super::ST;
+
+mod first_page {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_within_page() {
+        ST::from(TestCases::move_nth(2, 7, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[7].record, test_data[2], "Moved record must be at it's new position");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_to_top() {
+        ST::from(TestCases::move_nth(20, 0, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[0].record, test_data[20], "Moved record must be on top");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn reorder_after_page() {
+        ST::from(TestCases::reverse_range(10, 25, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+                let data = store_view.get_view_data();
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, test_data[idx], "View must not change");
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn reorder_whole_store() {
+        ST::from(TestCases::reverse_range(0, 25, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[0].record, test_data[24]);
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+}
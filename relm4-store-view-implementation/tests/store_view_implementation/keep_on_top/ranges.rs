@@ -0,0 +1,45 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn add_range() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(10))
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range() {
+    ST::from(TestCases::remove_range(3, 7, 30))
+        .window_size(StoreSize::Items(10))
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_over_page_end() {
+    ST::from(TestCases::remove_range(8, 15, 30))
+        .window_size(StoreSize::Items(10))
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay on top");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
@@ -3,4 +3,5 @@
 mod keep_on_bottom;
 mod keep_on_top;
 mod position_tracking_window;
+mod record_tracking_window;
 mod value_tracking_window;
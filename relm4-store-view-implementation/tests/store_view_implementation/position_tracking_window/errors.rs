@@ -0,0 +1,48 @@
+use reexport::glib;
+use reexport::gtk;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serial_test::serial;
+use backend_dummy::DummyBackend;
+use backend_dummy::test_cases::TestCases;
+use backend_dummy::test_cases::TestRecord;
+use store::BackendError;
+use store::Store;
+use store::StoreSize;
+use store::StoreViewMsg;
+use store::redraw_messages::RedrawMessages;
+use store::window::PositionTrackingWindow;
+
+use relm4_store_view_implementation::View;
+
+use crate::common::TestConfig;
+
+#[test]
+#[serial(gtk)]
+fn store_error_is_passed_to_the_component() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let (redraw_sender, redraw_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    let redraws = Rc::new(RefCell::new(vec![]));
+    let handler_redraws = redraws.clone();
+    redraw_receiver.attach(Some(&context), move |msg| {
+        handler_redraws.borrow_mut().push(msg);
+        glib::Continue(true)
+    });
+
+    let store: Store<DummyBackend<TestRecord>> = Store::new(DummyBackend::new(TestCases::multistep_add_unsafe(5, vec![vec![]]).configuration));
+    let _store_view: View<TestConfig<PositionTrackingWindow>> = View::new(store.clone(), StoreSize::Items(10), redraw_sender);
+    while context.iteration(false) {}
+    redraws.borrow_mut().clear();
+
+    store.fire_handlers(&[StoreViewMsg::Error(BackendError::Rejected(String::from("test")))]);
+    while context.iteration(false) {}
+
+    let redraws = redraws.borrow();
+    assert_eq!(redraws.len(), 1, "Error doesn't change the data so there is nothing to redraw");
+    assert!(matches!(&redraws[0], RedrawMessages::Error(BackendError::Rejected(reason)) if reason == "test"));
+}
@@ -1,5 +1,12 @@
 
+mod errors;
+mod moves;
+mod pages;
+mod ranges;
 mod remove;
+mod resize;
+mod selection;
+mod widget_pool;
 
 use store::window::PositionTrackingWindow;
 use crate::common::StoreViewTest;
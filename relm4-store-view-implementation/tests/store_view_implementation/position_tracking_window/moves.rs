@@ -0,0 +1,168 @@
+use super::ST;
+
+mod first_page {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+
+    use crate::common::init_view_calls;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_within_page() {
+        ST::from(TestCases::move_nth(2, 7, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[7].record, test_data[2], "Moved record must be at it's new position");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_within_page_keeps_widgets() {
+        ST::from(TestCases::move_nth(2, 7, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|_, _, _|{
+                assert_eq!(init_view_calls(), 10, "Moved record must keep it's widget");
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_into_page() {
+        ST::from(TestCases::move_nth(15, 3, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[3].record, test_data[15], "Moved record must become visible");
+                assert_eq!(data[9].record, test_data[8], "Last record must be pushed out of the view");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_out_of_page() {
+        ST::from(TestCases::move_nth(3, 20, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[9].record, test_data[10], "Next record must become visible");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_after_page() {
+        ST::from(TestCases::move_nth(15, 20, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, test_data[idx], "View must not change");
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn reorder_overlapping_page() {
+        ST::from(TestCases::reverse_range(5, 15, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[5].record, test_data[14]);
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+}
+
+mod second_page {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_over_page() {
+        ST::from(TestCases::move_nth(0, 24, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[0].record, test_data[11], "Records must be shifted by one");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(10 + idx));
+                    assert_eq!(record.record, store_data[10 + idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_before_page() {
+        ST::from(TestCases::move_nth(2, 7, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+                let data = store_view.get_view_data();
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(10 + idx));
+                    assert_eq!(record.record, test_data[10 + idx], "View must not change");
+                }
+            })
+            .run();
+    }
+}
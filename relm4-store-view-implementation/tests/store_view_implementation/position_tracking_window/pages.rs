@@ -0,0 +1,73 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::PageOutOfRange;
+use store::Pagination;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn to_page_moves_window() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.to_page(2).expect("Second page exists");
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(10, 20));
+            assert_eq!(store_view.current_page(), 2);
+            assert_window_content(store_view, store_data);
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn to_page_goes_to_last_partial_page() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.to_page(3).expect("Last page exists");
+            true
+        })
+        .initial(&|_, store_view, _| {
+            assert_eq!(store_view.get_window(), Range::new(20, 30));
+            assert_eq!(store_view.get_view_data().len(), 5, "Last page must show remaining records");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn to_page_out_of_range_is_refused() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .initial(&|_, store_view, _| {
+            assert_eq!(store_view.to_page(0), Err(PageOutOfRange{page: 0, total_pages: 3}));
+            assert_eq!(store_view.to_page(4), Err(PageOutOfRange{page: 4, total_pages: 3}));
+            assert_eq!(store_view.get_window(), Range::new(0, 10), "Window must stay in place");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn first_page_of_empty_store_exists() {
+    ST::from(TestCases::empty(1))
+        .window_size(StoreSize::Items(10))
+        .initial(&|_, store_view, _| {
+            assert_eq!(store_view.to_page(1), Ok(()));
+            assert_eq!(store_view.to_page(2), Err(PageOutOfRange{page: 2, total_pages: 0}));
+        })
+        .skip_step()
+        .run();
+}
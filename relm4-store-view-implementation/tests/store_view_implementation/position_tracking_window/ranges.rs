@@ -0,0 +1,73 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn add_range_before_page() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_in_page() {
+    ST::from(TestCases::add_range(15, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_over_page_start() {
+    ST::from(TestCases::remove_range(5, 12, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_in_page() {
+    ST::from(TestCases::remove_range(12, 15, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
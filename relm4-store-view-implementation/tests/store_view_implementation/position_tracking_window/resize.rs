@@ -0,0 +1,117 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+use crate::common::init_view_calls;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn growing_window_loads_missing_records() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.set_window_size(15);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(0, 15));
+            assert_eq!(store_view.window_size(), 15);
+            assert_window_content(store_view, store_data);
+            assert_eq!(init_view_calls(), 15, "Widgets must be created only for the records entering the view");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn shrinking_window_drops_records() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.set_window_size(4);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(0, 4));
+            assert_window_content(store_view, store_data);
+            assert_eq!(init_view_calls(), 10, "Widgets of the records staying in the view must be kept");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn resized_window_keeps_first_record() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.next_page();
+            view.set_window_size(5);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(10, 15));
+            assert_window_content(store_view, store_data);
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn resized_window_snaps_to_the_page() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.next_page();
+            view.set_window_size(4);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(8, 12), "Window must move to the page containing first record");
+            assert_window_content(store_view, store_data);
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn grown_window_snaps_to_the_page() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.next_page();
+            view.set_window_size(15);
+            true
+        })
+        .initial(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(0, 15));
+            assert_window_content(store_view, store_data);
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn resized_window_follows_changes() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view| {
+            view.set_window_size(6);
+            true
+        })
+        .step(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(0, 6));
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
@@ -0,0 +1,74 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use record::Record;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+use crate::common::drawn_selected;
+use crate::common::selection;
+
+use super::ST;
+
+fn label(idx: usize) -> String {
+    format!("Initial record {}", idx)
+}
+
+#[test]
+#[serial(gtk)]
+fn selected_records_are_drawn_selected() {
+    ST::from(TestCases::multistep_add_unsafe(20, vec![vec![]]))
+        .window_size(StoreSize::Items(10))
+        .selected(&[2, 5])
+        .initial(&|_, store_view, store_data| {
+            assert_window_content(store_view, store_data);
+            assert!(drawn_selected(&label(2)), "Selected record must be drawn as selected");
+            assert!(drawn_selected(&label(5)), "Selected record must be drawn as selected");
+            assert!(!drawn_selected(&label(3)), "Not selected record must not be drawn as selected");
+        })
+        .skip_step()
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn selection_change_redraws_record() {
+    ST::from(TestCases::multistep_add_unsafe(20, vec![vec![], vec![]]))
+        .window_size(StoreSize::Items(10))
+        .selected(&[2])
+        .step(&|_, _, store_data| {
+            selection().toggle(&store_data[2].get_id());
+            selection().toggle(&store_data[3].get_id());
+        })
+        .step(&|_, _, _| {
+            assert!(!drawn_selected(&label(2)), "Unselected record must be redrawn");
+            assert!(drawn_selected(&label(3)), "Selected record must be redrawn");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn selection_survives_paging() {
+    ST::from(TestCases::multistep_add_unsafe(20, vec![vec![], vec![]]))
+        .window_size(StoreSize::Items(10))
+        .selected(&[2, 12])
+        .prepare(&|view| {
+            view.next_page();
+            true
+        })
+        .initial(&|_, store_view, _| {
+            assert_eq!(store_view.get_window(), Range::new(10, 20));
+            assert!(drawn_selected(&label(12)), "Selected record on the second page must be drawn as selected");
+        })
+        .step(&|_, store_view, _| {
+            store_view.first_page();
+        })
+        .step(&|_, store_view, store_data| {
+            assert_eq!(store_view.get_window(), Range::new(0, 10));
+            assert!(drawn_selected(&label(2)), "Selection must be kept while the record is out of the view");
+            assert!(selection().is_selected(&store_data[12].get_id()));
+        })
+        .run();
+}
@@ -0,0 +1,71 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+
+use crate::common::assert_window_content;
+use crate::common::init_view_calls;
+use crate::common::reset_view_calls;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn widgets_are_created_for_new_records_without_pool() {
+    ST::from(TestCases::remove_nth(2, 20))
+        .window_size(StoreSize::Items(10))
+        .initial(&|_, _, _| {
+            assert_eq!(init_view_calls(), 10, "Widgets must be created for the first page");
+        })
+        .step(&|_, store_view, store_data| {
+            assert_window_content(store_view, store_data);
+            assert_eq!(init_view_calls(), 11, "Widgets must be created for the record entering the view");
+            assert_eq!(reset_view_calls(), 0, "Widgets must not be reset without the pool");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn widgets_of_removed_record_are_reused() {
+    ST::from(TestCases::remove_nth(2, 20))
+        .window_size(StoreSize::Items(10))
+        .widget_pool()
+        .initial(&|_, _, _| {
+            assert_eq!(init_view_calls(), 10, "Widgets must be created for the first page");
+        })
+        .step(&|_, store_view, store_data| {
+            assert_window_content(store_view, store_data);
+            assert_eq!(init_view_calls(), 10, "Widgets of the removed record must be reused");
+            assert_eq!(reset_view_calls(), 1, "Widgets must be reset before reuse");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn widgets_of_removed_range_are_reused() {
+    ST::from(TestCases::remove_range(2, 6, 20))
+        .window_size(StoreSize::Items(10))
+        .widget_pool()
+        .step(&|_, store_view, store_data| {
+            assert_window_content(store_view, store_data);
+            assert_eq!(init_view_calls(), 10, "Widgets of the removed records must be reused");
+            assert_eq!(reset_view_calls(), 4, "Widgets must be reset before reuse");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn pool_is_not_used_when_nothing_enters_the_view() {
+    ST::from(TestCases::remove_nth(2, 5))
+        .window_size(StoreSize::Items(10))
+        .widget_pool()
+        .step(&|_, store_view, _| {
+            assert_eq!(store_view.get_view_data().len(), 4, "View must show remaining records");
+            assert_eq!(init_view_calls(), 5, "No widgets must be created");
+            assert_eq!(reset_view_calls(), 1, "Widgets of the removed record must be kept in the pool");
+        })
+        .run();
+}
@@ -0,0 +1,87 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::Position;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn insert_before_page() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![3]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(11, 21), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[0].position, Position(11));
+            assert_eq!(data[0].record, test_data[10]);
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn insert_in_page_before_anchor() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![12]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(11, 21), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[0].position, Position(11));
+            assert_eq!(data[0].record, test_data[11]);
+            assert_eq!(data[1].record, test_data[25], "New record must be visible");
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn insert_in_page_after_anchor() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![17]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            assert_eq!(data[7].record, test_data[25], "New record must be visible");
+            assert_eq!(data[9].position, Position(19));
+            assert_eq!(data[9].record, test_data[18]);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn insert_after_page() {
+    ST::from(TestCases::multistep_add_unsafe(25, vec![vec![22]]))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(10 + idx));
+                assert_eq!(record.record, test_data[10 + idx], "View must not change");
+            }
+        })
+        .run();
+}
@@ -0,0 +1,15 @@
+//! Anchor is the record at the [ANCHOR](crate::common::ANCHOR) position. Tests move the window to the second page
+//! of the 10 records, so anchor is in the middle of the window.
+
+use store::window::RecordTrackingWindow;
+use backend_dummy::DummyBackend;
+use backend_dummy::test_cases::TestRecord;
+use store::Store;
+use crate::common::StoreViewTest;
+
+type ST = StoreViewTest<RecordTrackingWindow<Store<DummyBackend<TestRecord>>>>;
+
+mod add;
+mod moves;
+mod ranges;
+mod remove;
@@ -0,0 +1,140 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::Position;
+use store::StoreSize;
+use store::StoreView;
+use store::StoreViewMsg;
+use store::math::Range;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn move_anchor() {
+    ST::from(TestCases::move_nth(15, 20, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(15, 25), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(15 + idx));
+                assert_eq!(record.record, store_data[15 + idx]);
+            }
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn move_over_anchor() {
+    ST::from(TestCases::move_nth(2, 22, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(9, 19), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[0].record, test_data[10]);
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(9 + idx));
+                assert_eq!(record.record, store_data[9 + idx]);
+            }
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn move_before_anchor() {
+    ST::from(TestCases::move_nth(11, 13, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            assert_eq!(data[3].record, test_data[11], "Moved record must be at it's new position");
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(10 + idx));
+                assert_eq!(record.record, store_data[10 + idx]);
+            }
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn reorder_anchor() {
+    ST::from(TestCases::reverse_range(10, 20, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Anchor is not known until the reload");
+            let data = store_view.get_view_data();
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(10 + idx));
+                assert_eq!(record.record, store_data[10 + idx]);
+            }
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn reorder_anchor_and_reload() {
+    let mut test_case = TestCases::reverse_range(10, 20, 25);
+    for step in test_case.configuration.steps.iter_mut() {
+        step.events.push(StoreViewMsg::Reload);
+    }
+
+    ST::from(test_case)
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(9, 19), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(9 + idx));
+                assert_eq!(record.record, store_data[9 + idx]);
+            }
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn reorder_before_page() {
+    ST::from(TestCases::reverse_range(0, 5, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            for (idx, record) in data.iter().enumerate() {
+                assert_eq!(record.position, Position(10 + idx));
+                assert_eq!(record.record, test_data[10 + idx], "View must not change");
+            }
+        })
+        .run();
+}
@@ -0,0 +1,174 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::ANCHOR;
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn add_range_before_page() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(14, 24), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_larger_than_page_before_page() {
+    ST::from(TestCases::add_range(3, 30, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(40, 50), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_on_the_first_page() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(20))
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(4, 24), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[ANCHOR].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_larger_than_page_on_the_last_page() {
+    ST::from(TestCases::add_range(3, 20, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.set_window(Range::new(15, 25));
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(35, 45), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[0].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_doesnt_move_window_past_the_end() {
+    ST::from(TestCases::add_range(3, 5, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.set_window(Range::new(20, 30));
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(20, 30), "Window must stay on the last page");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_in_page_before_anchor() {
+    ST::from(TestCases::add_range(12, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(13, 23), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_after_anchor() {
+    ST::from(TestCases::add_range(17, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_before_page() {
+    ST::from(TestCases::remove_range(5, 12, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(3, 13), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_in_page_before_anchor() {
+    ST::from(TestCases::remove_range(12, 15, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(7, 17), "Window must follow the anchor");
+            assert_eq!(store_view.get_view_data()[5].record, test_data[ANCHOR], "Anchor must keep it's offset");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_with_anchor() {
+    ST::from(TestCases::remove_range(14, 17, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
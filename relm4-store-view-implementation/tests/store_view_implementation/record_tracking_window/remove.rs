@@ -0,0 +1,83 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::Position;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn remove_before_page() {
+    ST::from(TestCases::remove_nth(3, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(9, 19), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[0].position, Position(9));
+            assert_eq!(data[0].record, test_data[10]);
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_in_page_before_anchor() {
+    ST::from(TestCases::remove_nth(12, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(9, 19), "Window must follow the anchor");
+            let data = store_view.get_view_data();
+            assert_eq!(data[0].position, Position(9));
+            assert_eq!(data[0].record, test_data[9]);
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_anchor() {
+    ST::from(TestCases::remove_nth(15, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            assert_eq!(data[5].record, test_data[16], "Neighbour must take place of the anchor");
+            assert_eq!(data[9].record, test_data[20]);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_after_anchor() {
+    ST::from(TestCases::remove_nth(18, 25))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, _|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Window must keep it's position");
+            let data = store_view.get_view_data();
+            assert_eq!(data[5].record, test_data[15], "Anchor must keep it's offset");
+            assert_eq!(data[9].position, Position(19));
+            assert_eq!(data[9].record, test_data[20]);
+        })
+        .run();
+}
@@ -4,4 +4,6 @@ use crate::common::StoreViewTest;
 type ST = StoreViewTest<ValueTrackingWindow>;
 
 mod add;
+mod moves;
+mod ranges;
 mod remove;
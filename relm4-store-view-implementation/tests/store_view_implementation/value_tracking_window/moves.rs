@@ -0,0 +1,143 @@
+use super::ST;
+
+mod first_page {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_within_page() {
+        ST::from(TestCases::move_nth(7, 2, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10));
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[2].record, test_data[7], "Moved record must be at it's new position");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn reorder_whole_store() {
+        ST::from(TestCases::reverse_range(0, 25, 25))
+            .window_size(StoreSize::Items(10))
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(0, 10));
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[0].record, test_data[24]);
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(idx));
+                    assert_eq!(record.record, store_data[idx]);
+                }
+            })
+            .run();
+    }
+}
+
+mod somwhere_in_the_middle {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn move_from_before_to_after_page() {
+        ST::from(TestCases::move_nth(0, 24, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(9, 19), "Window must follow the records");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(9 + idx));
+                    assert_eq!(record.record, test_data[10 + idx], "Records in the view must not change");
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_from_after_to_before_page() {
+        ST::from(TestCases::move_nth(24, 0, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(11, 21), "Window must follow the records");
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(11 + idx));
+                    assert_eq!(record.record, test_data[10 + idx], "Records in the view must not change");
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_into_page() {
+        ST::from(TestCases::move_nth(2, 15, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, store_data|{
+                assert_eq!(store_view.get_window(), Range::new(10, 20));
+                let data = store_view.get_view_data();
+                assert_eq!(data.len(), 10, "Store view must be full");
+                assert_eq!(data[5].record, test_data[2], "Moved record must become visible");
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(10 + idx));
+                    assert_eq!(record.record, store_data[10 + idx]);
+                }
+            })
+            .run();
+    }
+
+    #[test]
+    #[serial(gtk)]
+    fn move_after_page() {
+        ST::from(TestCases::move_nth(20, 24, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.get_window(), Range::new(10, 20));
+                let data = store_view.get_view_data();
+                for (idx, record) in data.iter().enumerate() {
+                    assert_eq!(record.position, Position(10 + idx));
+                    assert_eq!(record.record, test_data[10 + idx], "View must not change");
+                }
+            })
+            .run();
+    }
+}
@@ -0,0 +1,106 @@
+use serial_test::serial;
+use backend_dummy::test_cases::TestCases;
+use store::StoreSize;
+use store::StoreView;
+use store::math::Range;
+
+use crate::common::assert_window_content;
+
+use super::ST;
+
+#[test]
+#[serial(gtk)]
+fn add_range_before_page() {
+    ST::from(TestCases::add_range(3, 4, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|test_data, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(14, 24), "Window must slide by the number of new records");
+            assert_eq!(store_view.get_view_data()[0].record, test_data[10], "Window must keep the records");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_before_threshold() {
+    ST::from(TestCases::add_range(12, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(13, 23), "Records before the threshold must move left");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn add_range_after_threshold() {
+    ST::from(TestCases::add_range(17, 3, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Records after the threshold must move right");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_before_threshold() {
+    ST::from(TestCases::remove_range(12, 15, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(7, 17), "Records before the threshold must move right");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_after_threshold() {
+    ST::from(TestCases::remove_range(16, 25, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20), "Records after the threshold must move left");
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_range_over_page_start() {
+    ST::from(TestCases::remove_range(5, 12, 30))
+        .window_size(StoreSize::Items(10))
+        .prepare(&|view|{
+            view.next_page();
+            true
+        })
+        .step(&|_, store_view, store_data|{
+            assert_eq!(store_view.get_window(), Range::new(10, 20));
+            assert_window_content(store_view, store_data);
+        })
+        .run();
+}
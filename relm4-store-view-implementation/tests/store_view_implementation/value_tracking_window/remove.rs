@@ -165,4 +165,39 @@ mod somwhere_in_the_middle {
             })
             .run();
     }
-}
\ No newline at end of file
+}
+
+mod configured_threshold {
+    use serial_test::serial;
+    use backend_dummy::test_cases::TestCases;
+    use store::Position;
+    use store::StoreSize;
+    use store::StoreView;
+    use store::math::Range;
+    use store::window::ValueTrackingWindow;
+
+    use super::ST;
+
+    #[test]
+    #[serial(gtk)]
+    fn remove_after_threshold() {
+        ST::from(TestCases::remove_nth(14, 25))
+            .window_size(StoreSize::Items(10))
+            .prepare(&|view|{
+                view.set_window_behavior(ValueTrackingWindow::with_threshold(0.3));
+                view.next_page();
+                true
+            })
+            .step(&|test_data, store_view, _|{
+                assert_eq!(store_view.current_len(), 10, "Store view must be full");
+                let range = store_view.get_window();
+                assert_eq!(range, Range::new(10, 20), "Records after the threshold are moved to the left");
+                let data = store_view.get_view_data();
+                assert_eq!(data[0].position, Position(10));
+                assert_eq!(data[0].record, test_data[10]);
+                assert_eq!(data[9].position, Position(19));
+                assert_eq!(data[9].record, test_data[20]);
+            })
+            .run();
+    }
+}
@@ -0,0 +1,37 @@
+//! Errors reported by the backends
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+/// Error returned by [`Backend::inbox`](crate::Backend::inbox) when the message can't be applied
+/// 
+/// Backend which returns an error must leave the data untouched. For [`StoreMsg::Transaction`](crate::StoreMsg::Transaction)
+/// this means all of the changes made by the transaction so far are rolled back.
+/// 
+/// Errors are not generic over the record type, so they can be sent around freely. Values describing what went
+/// wrong are already formatted for the user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BackendError {
+    /// Record with given id is not in the store. Value is the debug representation of the id
+    NotFound(String),
+    /// Backend was unable to give the record a permanent id
+    Identity(String),
+    /// Backend refused the change, value describes the reason
+    Rejected(String),
+}
+
+/// Formats [`BackendError`] for empty format `{}`
+/// 
+/// This allows you to print errors without doing `matching` or `if let` statements
+impl Display for BackendError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            BackendError::NotFound(id) => f.write_fmt(format_args!("Record {} is not in the store", id)),
+            BackendError::Identity(reason) => f.write_fmt(format_args!("Unable to set permanent id: {}", reason)),
+            BackendError::Rejected(reason) => f.write_fmt(format_args!("Change rejected: {}", reason)),
+        }
+    }
+}
+
+impl Error for BackendError {}
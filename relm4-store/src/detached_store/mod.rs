@@ -0,0 +1,257 @@
+//! Store which keeps local copy of the records until the changes are committed
+mod state;
+
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Identifiable;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+use crate::DataStore;
+use crate::StoreId;
+use crate::StoreMsg;
+use crate::StoreViewMsg;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::math::Range;
+
+use state::DetachedState;
+
+/// Error returned by [`DetachedStore::commit`] if the parent store changed records which were edited locally
+#[derive(Debug)]
+pub struct CommitConflict<R: Record> {
+    /// Ids of the records changed both in the detached store and in the parent store
+    pub ids: Vec<Id<R>>,
+}
+
+/// Formats [`CommitConflict`] for empty format `{}`
+impl<R: Record> Display for CommitConflict<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_fmt(format_args!("{} record(s) changed in the parent store since the snapshot was taken", self.ids.len()))
+    }
+}
+
+/// Data store keeping a snapshot of the records from the parent store which can be edited without touching the parent
+///
+/// This is an implementation of the 1-* editing scenario described in [`record::Id`]. Detached store takes a copy of
+/// the records from the parent store. Messages sent to the detached store ([`StoreMsg::Commit`] and
+/// [`StoreMsg::Delete`]) are applied to this copy only, so views on top of it show the changes while the rest of
+/// the application still sees the original data. New records (with [`Id::New`]) are added at the end.
+///
+/// When user is done [`DetachedStore::commit`] sends the whole change set to the parent store. [`DetachedStore::discard`]
+/// (or [`StoreMsg::Reload`] sent to the detached store) throws the changes away and takes the fresh copy of the
+/// snapshot records.
+///
+/// Detached store keeps listening to the parent store. If record edited locally has been updated or removed by the
+/// parent store in the meantime it's reported as a conflict and commit is refused until you either
+/// [`discard`](DetachedStore::discard) the changes or [`resolve`](DetachedStore::resolve) the conflict.
+pub struct DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    id: StoreId<Self>,
+    parent: Parent,
+    state: Rc<RefCell<DetachedState<Parent::Record>>>,
+    connections: Connections<Self, Parent::Record>,
+    sender: Sender<StoreMsg<Parent::Record>>,
+}
+
+impl<Parent> DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    /// Creates new instance of the detached store with a snapshot of the records of the parent store in the `range`
+    pub fn new(parent: Parent, range: &Range) -> Self {
+        let id = StoreId::new();
+        let state = Rc::new(RefCell::new(DetachedState::new(parent.get_range(range))));
+        let connections: Connections<Self, Parent::Record> = Rc::new(RefCell::new(HashMap::new()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let (parent_sender, parent_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+
+        {
+            let context = glib::MainContext::default();
+
+            let handler_parent = parent.clone();
+            let handler_state = state.clone();
+            let handler_connections = connections.clone();
+            receiver.attach(Some(&context), move |msg: StoreMsg<Parent::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    let replies = match msg {
+                        StoreMsg::Reload => state.discard(&handler_parent),
+                        msg => state.apply(msg),
+                    };
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow detached store state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+
+            let handler_parent = parent.clone();
+            let handler_state = state.clone();
+            let handler_connections = connections.clone();
+            parent_receiver.attach(Some(&context), move |msg: StoreViewMsg<Parent::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    let replies = state.parent_changed(&handler_parent, msg);
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow detached store state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        parent.listen(id.transfer(), parent_sender);
+
+        Self {
+            id,
+            parent,
+            state,
+            connections,
+            sender,
+        }
+    }
+
+    /// Returns `true` if there are local changes which were not committed yet
+    pub fn has_changes(&self) -> bool {
+        self.state.borrow().has_changes()
+    }
+
+    /// Returns ids of the records edited locally which were changed by the parent store since the snapshot
+    pub fn conflicts(&self) -> Vec<Id<Parent::Record>> {
+        self.state.borrow().conflicts()
+    }
+
+    /// Marks the conflict as resolved so local version of the record will be committed
+    pub fn resolve(&self, id: &Id<Parent::Record>) {
+        self.state.borrow_mut().resolve(id);
+    }
+
+    /// Sends all local changes to the parent store
+    ///
+    /// Changes are sent as a single [`StoreMsg::Transaction`]. Deletes go first, then commits of the changed and new
+    /// records in the order of the detached store. If any of the changed records has been modified by the parent store in
+    /// the meantime nothing is sent and conflicting ids are returned.
+    ///
+    /// After successful commit detached store has no local changes and committed records become the new snapshot.
+    /// New records are kept under their temporary ids until the parent store reports their permanent ids with
+    /// [`StoreViewMsg::IdChanged`], which is passed to the listeners of the detached store.
+    pub fn commit(&self) -> Result<(), CommitConflict<Parent::Record>> {
+        let messages = self.state.borrow_mut().commit()?;
+        if !messages.is_empty() {
+            self.parent.send(StoreMsg::Transaction(messages).into());
+        }
+        Ok(())
+    }
+
+    /// Throws away all local changes and takes the current version of the snapshot records from the parent store
+    pub fn discard(&self) {
+        let replies = self.state.borrow_mut().discard(&self.parent);
+        fire_handlers(&self.connections, &replies);
+    }
+}
+
+impl<Parent> Identifiable<Self, <Parent::Allocator as TemporaryIdAllocator>::Type> for DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    type Id = StoreId<Self>;
+
+    fn get_id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl<Parent> DataStore for DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    type Record = Parent::Record;
+    type Allocator = Parent::Allocator;
+    type Messages = StoreMsg<Parent::Record>;
+
+    fn len(&self) -> usize {
+        self.state.borrow().len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        self.state.borrow().get(id)
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        self.state.borrow().get_range(range)
+    }
+
+    fn listen(&self, id: StoreId<Self>, sender: Sender<StoreViewMsg<Self::Record>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    fn unlisten(&self, id: StoreId<Self>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn sender(&self) -> Sender<Self::Messages> {
+        self.sender.clone()
+    }
+
+    fn send(&self, msg: Self::Messages) {
+        // this shouldn't fail since receiver should still be there
+        self.sender.send(msg).unwrap();
+    }
+}
+
+/// Implements shallow clone. Clone shares the local changes and listeners with the original
+impl<Parent> Clone for DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            parent: self.parent.clone(),
+            state: self.state.clone(),
+            connections: self.connections.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+/// Formats [`DetachedStore`] for debug format `{:?}`
+impl<Parent> Debug for DetachedStore<Parent>
+where
+    Parent: 'static + DataStore + Clone,
+    Parent::Messages: From<StoreMsg<Parent::Record>>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("DetachedStore")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
@@ -0,0 +1,262 @@
+use std::cmp::min;
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use reexport::log;
+
+use record::Id;
+use record::Identity;
+use record::Record;
+
+use crate::DataStore;
+use crate::Position;
+use crate::StoreMsg;
+use crate::StoreViewMsg;
+use crate::math::Range;
+
+use super::CommitConflict;
+
+/// Local copy of the records with the bookkeeping of the changes
+#[derive(Debug)]
+pub(crate) struct DetachedState<R: Record> {
+    /// Working copy of the records
+    records: Vec<R>,
+    /// Ids of the records taken from the parent store in the parent order
+    snapshot: Vec<Id<R>>,
+    /// Ids of the records committed to the detached store, including the new ones
+    changed: HashSet<Id<R>>,
+    /// Ids of the snapshot records deleted locally in the order of deletion
+    deleted: Vec<Id<R>>,
+    /// Ids of the snapshot records which were updated or removed by the parent store
+    parent_changed: HashSet<Id<R>>,
+    /// Ids sent to the parent store by the last commit, parent will echo them back as updates
+    pending: HashSet<Id<R>>,
+}
+
+impl<R> DetachedState<R>
+where
+    R: Record + Debug + Clone + 'static,
+{
+    pub(crate) fn new(records: Vec<R>) -> Self {
+        Self {
+            snapshot: records.iter().map(|record| record.get_id()).collect(),
+            records,
+            changed: HashSet::new(),
+            deleted: Vec::new(),
+            parent_changed: HashSet::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub(crate) fn get(&self, id: &Id<R>) -> Option<R> {
+        self.records.iter()
+            .find(|record| record.get_id() == *id)
+            .cloned()
+    }
+
+    pub(crate) fn get_range(&self, range: &Range) -> Vec<R> {
+        let start = min(*range.start(), self.len());
+        let end = min(*range.end(), self.len());
+
+        self.records[start..end].to_vec()
+    }
+
+    pub(crate) fn has_changes(&self) -> bool {
+        !self.changed.is_empty() || !self.deleted.is_empty()
+    }
+
+    pub(crate) fn conflicts(&self) -> Vec<Id<R>> {
+        self.records.iter()
+            .map(|record| record.get_id())
+            .filter(|id| self.changed.contains(id))
+            .chain(self.deleted.iter().copied())
+            .filter(|id| self.parent_changed.contains(id))
+            .collect()
+    }
+
+    pub(crate) fn resolve(&mut self, id: &Id<R>) {
+        self.parent_changed.remove(id);
+    }
+
+    /// Applies local change to the working copy
+    pub(crate) fn apply(&mut self, msg: StoreMsg<R>) -> Vec<StoreViewMsg<R>> {
+        match msg {
+            StoreMsg::Commit(record) => {
+                let id = record.get_id();
+                self.changed.insert(id);
+
+                match self.position(&id) {
+                    Some(position) => {
+                        self.records[position] = record;
+                        vec![StoreViewMsg::Update(id)]
+                    },
+                    None => {
+                        // record deleted before is back
+                        self.deleted.retain(|other| *other != id);
+                        self.records.push(record);
+                        vec![StoreViewMsg::NewAt(Position(self.records.len() - 1))]
+                    }
+                }
+            },
+            StoreMsg::Restore{record, position} => {
+                let id = record.get_id();
+                if self.position(&id).is_some() {
+                    return self.apply(StoreMsg::Commit(record))
+                }
+
+                self.changed.insert(id);
+                self.deleted.retain(|other| *other != id);
+                let position = min(position.0, self.records.len());
+                self.records.insert(position, record);
+                vec![StoreViewMsg::NewAt(Position(position))]
+            },
+            StoreMsg::Delete(id) => {
+                match self.position(&id) {
+                    Some(position) => {
+                        self.records.remove(position);
+                        self.changed.remove(&id);
+                        if self.snapshot.contains(&id) {
+                            self.deleted.push(id);
+                        }
+                        vec![StoreViewMsg::Remove(Position(position))]
+                    },
+                    None => vec![],
+                }
+            },
+            // reload requires parent store, it's handled by the detached store itself
+            StoreMsg::Reload => vec![],
+            StoreMsg::Transaction(messages) => {
+                let mut replies = Vec::new();
+                for msg in messages {
+                    for reply in self.apply(msg) {
+                        match reply {
+                            StoreViewMsg::Batch(mut inner) => replies.append(&mut inner),
+                            reply => replies.push(reply),
+                        }
+                    }
+                }
+
+                if replies.is_empty() {
+                    vec![]
+                }
+                else {
+                    vec![StoreViewMsg::Batch(replies)]
+                }
+            },
+        }
+    }
+
+    /// Records changes done by the parent store to the snapshot records
+    /// 
+    /// Returns messages for the listeners of the detached store
+    pub(crate) fn parent_changed<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, msg: StoreViewMsg<R>) -> Vec<StoreViewMsg<R>> {
+        match msg {
+            StoreViewMsg::Update(id) => {
+                if !self.pending.remove(&id) && self.snapshot.contains(&id) {
+                    self.parent_changed.insert(id);
+                }
+            },
+            StoreViewMsg::Remove(_) | StoreViewMsg::RemoveRange{..} | StoreViewMsg::Reload => {
+                // position doesn't tell which record is gone, so check all of them
+                for id in self.snapshot.iter() {
+                    if parent.get(id).is_none() {
+                        self.parent_changed.insert(*id);
+                    }
+                }
+            },
+            StoreViewMsg::Batch(messages) => {
+                let mut replies = Vec::new();
+                for msg in messages {
+                    replies.append(&mut self.parent_changed(parent, msg));
+                }
+                return replies
+            },
+            StoreViewMsg::IdChanged{old, new} => return self.change_id(parent, old, new),
+            StoreViewMsg::NewAt(_) | StoreViewMsg::NewRange{..} | StoreViewMsg::Move{..} | StoreViewMsg::Reorder{..} | StoreViewMsg::Error(_) => {},
+        }
+        vec![]
+    }
+
+    /// Parent store gave the record a new id, usually permanent one for the record committed from here
+    fn change_id<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, old: Id<R>, new: Id<R>) -> Vec<StoreViewMsg<R>> {
+        for ids in [&mut self.changed, &mut self.parent_changed, &mut self.pending] {
+            if ids.remove(&old) {
+                ids.insert(new);
+            }
+        }
+        for id in self.snapshot.iter_mut().chain(self.deleted.iter_mut()).filter(|id| **id == old) {
+            *id = new;
+        }
+
+        let position = match self.position(&old) {
+            Some(position) => position,
+            None => return vec![],
+        };
+
+        // keep local edits if there are any
+        let mut record = self.records[position].clone();
+        let local = self.changed.contains(&new) && record.set_permanent_id(new.get_value()).is_ok() && record.get_id() == new;
+        if !local {
+            match parent.get(&new) {
+                Some(parent_record) => record = parent_record,
+                None => {
+                    log::warn!("Record {:?} is not in the parent store", new);
+                    return vec![]
+                }
+            }
+        }
+        self.records[position] = record;
+
+        vec![StoreViewMsg::IdChanged{old, new}]
+    }
+
+    /// Returns messages for the parent store and clears the local changes
+    pub(crate) fn commit(&mut self) -> Result<Vec<StoreMsg<R>>, CommitConflict<R>> {
+        let ids = self.conflicts();
+        if !ids.is_empty() {
+            return Err(CommitConflict{ids})
+        }
+
+        let mut messages: Vec<StoreMsg<R>> = self.deleted.drain(..)
+            .map(StoreMsg::Delete)
+            .collect();
+
+        for record in self.records.iter() {
+            let id = record.get_id();
+            if self.changed.contains(&id) {
+                if !id.is_new() {
+                    self.pending.insert(id);
+                }
+                messages.push(StoreMsg::Commit(record.clone()));
+            }
+        }
+
+        self.changed.clear();
+        self.parent_changed.clear();
+        self.snapshot = self.records.iter().map(|record| record.get_id()).collect();
+
+        Ok(messages)
+    }
+
+    /// Throws away local changes and takes fresh copy of the snapshot records
+    pub(crate) fn discard<Parent: DataStore<Record=R>>(&mut self, parent: &Parent) -> Vec<StoreViewMsg<R>> {
+        self.records = self.snapshot.iter()
+            .filter_map(|id| parent.get(id))
+            .collect();
+        self.snapshot = self.records.iter().map(|record| record.get_id()).collect();
+        self.changed.clear();
+        self.deleted.clear();
+        self.parent_changed.clear();
+        self.pending.clear();
+
+        vec![StoreViewMsg::Reload]
+    }
+
+    fn position(&self, id: &Id<R>) -> Option<usize> {
+        self.records.iter().position(|record| record.get_id() == *id)
+    }
+}
@@ -14,6 +14,7 @@ use relm4::factory::FactoryListView;
 use relm4::factory::FactoryPrototype;
 use relm4::factory::FactoryView;
 
+use crate::BackendError;
 use crate::DataStore;
 use crate::StoreSize;
 use crate::StoreView;
@@ -63,6 +64,12 @@ pub trait StoreViewPrototype
     /// Initialize store view
     fn init_store_view(store: Self::Store, size: StoreSize, redraw_sender: Sender<RedrawMessages>) -> Self::StoreView;
 
+    /// Creates instance of the window behavior used by the store view
+    /// 
+    /// Store view owns the returned instance, so it's the place to configure it. `store` is the store
+    /// for which the store view is created.
+    fn init_window(store: &Self::Store) -> Self::Window;
+
     /// Creates instance of the [Self::RecordWidgets] responsible for displaying `record`
     /// at the `position`
     /// 
@@ -76,13 +83,32 @@ pub trait StoreViewPrototype
     /// Function called when record in store view is modified and you need to 
     /// synchronize the state of the view with data in the model
     /// 
+    /// `selected` tells if the record is selected in the [SelectionModel][crate::SelectionModel] attached to
+    /// the store view. It's always `false` if there is no selection model. When the selection of the record changes
+    /// this method is called again. It's also called right after [StoreViewPrototype::init_view] for records
+    /// which are selected, so widgets can highlight them.
+    /// 
     /// This method is equivalent of [FactoryPrototype::view][relm4::factory::FactoryPrototype::view]
     fn view(
         model: <Self::Store as DataStore>::Record,
         position: Position,
+        selected: bool,
         widgets: &Self::RecordWidgets,
     );
 
+    /// Function called when widgets of the removed record are put into the widget pool
+    ///
+    /// Store view created with the widget pool doesn't drop the widgets of the records which left the view.
+    /// Instead they are bound to the next record which enters the view by [StoreViewPrototype::view], without
+    /// calling [StoreViewPrototype::init_view]. Reset here the state which belongs to the record displayed so far.
+    ///
+    /// Signal handlers connected in [StoreViewPrototype::init_view] stay connected, so they must not capture the id of
+    /// the record. Keep the id in the widgets, for example in `Rc<Cell<Id<_>>>`, and set it in
+    /// [StoreViewPrototype::view]. This also keeps the handlers right when the record gets it's permanent id.
+    ///
+    /// Default implementation does nothing
+    fn reset_view(_widgets: &Self::RecordWidgets) {}
+
     /// Function called when component received a message
     /// 
     /// This method is equivalent of [ComponentUpdate::update][relm4::ComponentUpdate::update]
@@ -92,6 +118,14 @@ pub trait StoreViewPrototype
         sender: Sender<<Self::ViewModel as ViewModel>::Msg>,
     );
 
+    /// Function called when the store reported an error
+    /// 
+    /// Store view doesn't change when the store rejects a message, so this is the place to let the user know.
+    /// Send a message to the view model using `sender` and handle it in [StoreViewPrototype::update].
+    /// 
+    /// Default implementation does nothing
+    fn on_error(_error: &BackendError, _sender: Sender<<Self::ViewModel as ViewModel>::Msg>) {}
+
     /// Creates new instance of [StoreViewPrototype]
     /// 
     /// If you wish to use store view in widgets you must save it in your model
@@ -0,0 +1,231 @@
+use std::cmp::min;
+use std::collections::HashMap;
+
+use record::Id;
+use record::Record;
+
+use crate::Position;
+use crate::StoreViewMsg;
+use crate::math::Range;
+
+/// Keeps track which records of the parent store are matching the filter
+///
+/// Index keeps the ids of all records of the parent store in the parent order and sorted list of
+/// positions of matching records. Position in the filtered store is an index in the `matching`.
+///
+/// Positions of the records in the parent store are updated lazily. Insert or remove only marks
+/// positions after it as stale and they are recomputed once somebody asks for them, so bulk changes
+/// don't pay for the whole tail of the parent store on every record.
+#[derive(Debug)]
+pub(crate) struct FilterIndex<R: Record> {
+    /// Ids of the records in the parent store
+    parent: Vec<Id<R>>,
+    /// Positions of the records in the parent store
+    ///
+    /// Values lower than `stale` are always valid, the rest must be recomputed before use
+    positions: HashMap<Id<R>, usize>,
+    /// First position in the parent store which might be out of date in `positions`
+    stale: usize,
+    /// Sorted positions of the records in the parent store which are matching the filter
+    matching: Vec<usize>,
+}
+
+impl<R: Record> FilterIndex<R> {
+    pub(crate) fn new() -> Self {
+        Self {
+            parent: Vec::new(),
+            positions: HashMap::new(),
+            stale: 0,
+            matching: Vec::new(),
+        }
+    }
+
+    /// Number of records matching the filter
+    pub(crate) fn len(&self) -> usize {
+        self.matching.len()
+    }
+
+    /// Number of records in the parent store known to the index
+    pub(crate) fn parent_len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns ids of records at the given range of the filtered store
+    pub(crate) fn ids(&self, range: &Range) -> Vec<Id<R>> {
+        let start = min(*range.start(), self.len());
+        let end = min(*range.end(), self.len());
+
+        self.matching[start..end].iter()
+            .map(|p| self.parent[*p])
+            .collect()
+    }
+
+    /// Returns `true` if record is known to the index
+    pub(crate) fn contains(&self, id: &Id<R>) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    /// Returns position of the record in the parent store
+    pub(crate) fn parent_position(&mut self, id: &Id<R>) -> Option<usize> {
+        let position = *self.positions.get(id)?;
+        if position < self.stale {
+            return Some(position)
+        }
+
+        self.reindex();
+        self.positions.get(id).copied()
+    }
+
+    /// Returns id of the record at the given position in the parent store
+    pub(crate) fn parent_id(&self, position: usize) -> Option<Id<R>> {
+        self.parent.get(position).copied()
+    }
+
+    /// Replaces `old` id with the `new` one
+    ///
+    /// Returns `false` if `old` id is unknown to the index
+    pub(crate) fn change_id(&mut self, old: &Id<R>, new: Id<R>) -> bool {
+        match self.parent_position(old) {
+            Some(position) => {
+                self.positions.remove(old);
+                self.parent[position] = new;
+                self.positions.insert(new, position);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Recomputes stale positions of the records in the parent store
+    fn reindex(&mut self) {
+        for (idx, id) in self.parent[self.stale..].iter().enumerate() {
+            self.positions.insert(*id, self.stale + idx);
+        }
+        self.stale = self.parent.len();
+    }
+
+    /// Number of matching records before given position in the parent store
+    fn rank(&self, parent_position: usize) -> usize {
+        self.matching.partition_point(|p| *p < parent_position)
+    }
+
+    /// Replaces content of the index
+    ///
+    /// `records` is list of ids in the parent order with information if record is matching the filter
+    pub(crate) fn rebuild(&mut self, records: impl Iterator<Item=(Id<R>, bool)>) {
+        self.parent.clear();
+        self.positions.clear();
+        self.matching.clear();
+
+        for (idx, (id, matches)) in records.enumerate() {
+            self.parent.push(id);
+            self.positions.insert(id, idx);
+            if matches {
+                self.matching.push(idx);
+            }
+        }
+        self.stale = self.parent.len();
+    }
+
+    /// Record has been inserted into the parent store at `position`
+    ///
+    /// Returns position in the filtered store if record is matching
+    pub(crate) fn insert(&mut self, position: usize, id: Id<R>, matches: bool) -> Option<Position> {
+        let rank = self.rank(position);
+        for p in self.matching[rank..].iter_mut() {
+            *p += 1;
+        }
+        self.parent.insert(position, id);
+        self.positions.insert(id, position);
+        self.stale = min(self.stale, position);
+
+        if matches {
+            self.matching.insert(rank, position);
+            Some(Position(rank))
+        }
+        else {
+            None
+        }
+    }
+
+    /// Record has been removed from the parent store at `position`
+    ///
+    /// Returns position in the filtered store if record was matching
+    pub(crate) fn remove(&mut self, position: usize) -> Option<Position> {
+        let id = self.parent.remove(position);
+        self.positions.remove(&id);
+        self.stale = min(self.stale, position);
+
+        let result = match self.matching.binary_search(&position) {
+            Ok(rank) => {
+                self.matching.remove(rank);
+                Some(Position(rank))
+            },
+            Err(_) => None,
+        };
+
+        let rank = self.rank(position);
+        for p in self.matching[rank..].iter_mut() {
+            *p -= 1;
+        }
+
+        result
+    }
+
+    /// Record at `position` in the parent store has been updated
+    ///
+    /// Returns message for the listeners of the filtered store if anything changed in there
+    pub(crate) fn update(&mut self, position: usize, id: Id<R>, matches: bool) -> Option<StoreViewMsg<R>> {
+        match (self.matching.binary_search(&position), matches) {
+            (Ok(_), true) => Some(StoreViewMsg::Update(id)),
+            (Ok(rank), false) => {
+                self.matching.remove(rank);
+                Some(StoreViewMsg::Remove(Position(rank)))
+            },
+            (Err(rank), true) => {
+                self.matching.insert(rank, position);
+                Some(StoreViewMsg::NewAt(Position(rank)))
+            },
+            (Err(_), false) => None,
+        }
+    }
+
+    /// Record has been moved in the parent store
+    ///
+    /// Returns message for the listeners of the filtered store if record is visible and it's position
+    /// among matching records has changed
+    pub(crate) fn move_record(&mut self, from: usize, to: usize) -> Option<StoreViewMsg<R>> {
+        let id = self.parent[from];
+        let old = self.remove(from);
+        let new = self.insert(to, id, old.is_some());
+
+        match (old, new) {
+            (Some(from), Some(to)) if from != to => Some(StoreViewMsg::Move{from, to}),
+            _ => None,
+        }
+    }
+
+    /// Records in the parent store starting at `from` have been reordered
+    ///
+    /// Returns range in the filtered store affected by reorder and number of matching records in the range
+    /// after the reorder
+    pub(crate) fn reorder(&mut self, from: usize, records: Vec<(Id<R>, bool)>) -> (Range, usize) {
+        let to = from + records.len();
+        let start = self.rank(from);
+        let end = self.rank(to);
+
+        let mut matching = Vec::new();
+        for (idx, (id, matches)) in records.into_iter().enumerate() {
+            self.parent[from + idx] = id;
+            self.positions.insert(id, from + idx);
+            if matches {
+                matching.push(from + idx);
+            }
+        }
+
+        let count = matching.len();
+        self.matching.splice(start..end, matching);
+
+        (Range::new(start, end), count)
+    }
+}
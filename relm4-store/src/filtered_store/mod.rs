@@ -0,0 +1,348 @@
+//! Store which shows only records matching the filter
+pub(crate) mod index;
+
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Identifiable;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+use crate::DataStore;
+use crate::Position;
+use crate::StoreId;
+use crate::StoreViewMsg;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::handlers::translate_batch;
+use crate::math::Range;
+
+use index::FilterIndex;
+
+/// Data store showing only the records of the parent store which are matching the filter
+///
+/// Filtered store listens to the parent store and translates positions from the parent store into
+/// positions among the matching records. Every time record is added or updated in the parent store
+/// filter is evaluated, so updated records can enter or leave the filtered store.
+///
+/// Filtered store is a [`DataStore`] by itself so you can build a store view on top of it the same
+/// way as for any other store. Messages sent to the filtered store are passed to the parent store.
+///
+/// ```text
+/// let incomplete = FilteredStore::new(tasks.clone(), Incomplete{});
+/// ```
+pub struct FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    id: StoreId<Self>,
+    parent: Parent,
+    state: Rc<RefCell<FilteredStoreState<Parent::Record, Filter>>>,
+    connections: Connections<Self, Parent::Record>,
+}
+
+impl<Parent, Filter> FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    /// Creates new instance of the filtered store
+    pub fn new(parent: Parent, filter: Filter) -> Self {
+        let id = StoreId::new();
+        let mut state = FilteredStoreState{
+            index: FilterIndex::new(),
+            filter,
+        };
+        state.rebuild(&parent);
+
+        let state = Rc::new(RefCell::new(state));
+        let connections: Connections<Self, Parent::Record> = Rc::new(RefCell::new(HashMap::new()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let handler_parent = parent.clone();
+        let handler_state = state.clone();
+        let handler_connections = connections.clone();
+
+        {
+            let context = glib::MainContext::default();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Parent::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    let replies = state.translate(&handler_parent, msg);
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow filtered store state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        parent.listen(id.transfer(), sender);
+
+        Self {
+            id,
+            parent,
+            state,
+            connections,
+        }
+    }
+
+    /// Replaces the filter
+    ///
+    /// All records in the parent store are evaluated again and listeners receive [`StoreViewMsg::Reload`]
+    pub fn set_filter(&self, filter: Filter) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.filter = filter;
+            state.rebuild(&self.parent);
+        }
+
+        fire_handlers(&self.connections, &[StoreViewMsg::Reload]);
+    }
+}
+
+/// Part of the filtered store shared with the handler listening to the parent store
+#[derive(Debug)]
+struct FilteredStoreState<R, Filter>
+where
+    R: Record,
+    Filter: crate::Filter<R>,
+{
+    index: FilterIndex<R>,
+    filter: Filter,
+}
+
+impl<R, Filter> FilteredStoreState<R, Filter>
+where
+    R: Record + Debug + Clone + 'static,
+    Filter: crate::Filter<R>,
+{
+    /// Evaluates filter for every record in the parent store
+    fn rebuild<Parent: DataStore<Record=R>>(&mut self, parent: &Parent) {
+        let records = parent.get_range(&Range::new(0, parent.len()));
+        let filter = &self.filter;
+        self.index.rebuild(records.iter().map(|record| (record.get_id(), filter.matches(record))));
+    }
+
+    /// Translates message from the parent store into messages for listeners of the filtered store
+    fn translate<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, msg: StoreViewMsg<R>) -> Vec<StoreViewMsg<R>> {
+        let replies = match msg {
+            StoreViewMsg::NewAt(Position(position)) => self.new_at(parent, position),
+            StoreViewMsg::Remove(Position(position)) => self.remove(position),
+            StoreViewMsg::Update(id) => self.update(parent, id),
+            StoreViewMsg::Move{from: Position(from), to: Position(to)} => self.move_record(from, to),
+            StoreViewMsg::Reorder{from: Position(from), to: Position(to)} => self.reorder(parent, from, to),
+            StoreViewMsg::Reload => {
+                self.rebuild(parent);
+                Some(vec![StoreViewMsg::Reload])
+            },
+            msg @ (StoreViewMsg::NewRange{..} | StoreViewMsg::RemoveRange{..}) => {
+                Some(translate_batch(msg.expand(), |msg| self.translate(parent, msg)))
+            },
+            StoreViewMsg::Batch(messages) => Some(translate_batch(messages, |msg| self.translate(parent, msg))),
+            StoreViewMsg::Error(err) => Some(vec![StoreViewMsg::Error(err)]),
+            StoreViewMsg::IdChanged{old, new} => {
+                if self.index.change_id(&old, new) {
+                    Some(vec![StoreViewMsg::IdChanged{old, new}])
+                }
+                else {
+                    // record was already added with the new id
+                    Some(vec![])
+                }
+            },
+        };
+
+        replies.unwrap_or_else(|| {
+            log::warn!("Filtered store is out of sync with the parent store. Reloading");
+            self.rebuild(parent);
+            vec![StoreViewMsg::Reload]
+        })
+    }
+
+    fn new_at<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, position: usize) -> Option<Vec<StoreViewMsg<R>>> {
+        if position > self.index.parent_len() {
+            return None
+        }
+
+        let record = parent.get_range(&Range::new(position, position+1)).pop()?;
+        let id = record.get_id();
+        if self.index.contains(&id) {
+            // parent store is already ahead of us, position doesn't point to the new record
+            return None
+        }
+
+        let matches = self.filter.matches(&record);
+        Some(
+            self.index.insert(position, id, matches)
+                .map(StoreViewMsg::NewAt)
+                .into_iter()
+                .collect()
+        )
+    }
+
+    fn remove(&mut self, position: usize) -> Option<Vec<StoreViewMsg<R>>> {
+        if position >= self.index.parent_len() {
+            return None
+        }
+
+        Some(
+            self.index.remove(position)
+                .map(StoreViewMsg::Remove)
+                .into_iter()
+                .collect()
+        )
+    }
+
+    fn update<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, id: Id<R>) -> Option<Vec<StoreViewMsg<R>>> {
+        let position = self.index.parent_position(&id)?;
+
+        match parent.get(&id) {
+            Some(record) => {
+                let matches = self.filter.matches(&record);
+                Some(self.index.update(position, id, matches).into_iter().collect())
+            },
+            // record is already gone from the parent, remove message will follow
+            None => Some(vec![]),
+        }
+    }
+
+    fn move_record(&mut self, from: usize, to: usize) -> Option<Vec<StoreViewMsg<R>>> {
+        if from >= self.index.parent_len() || to >= self.index.parent_len() {
+            return None
+        }
+
+        Some(self.index.move_record(from, to).into_iter().collect())
+    }
+
+    fn reorder<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, from: usize, to: usize) -> Option<Vec<StoreViewMsg<R>>> {
+        if to > self.index.parent_len() {
+            return None
+        }
+
+        let records = parent.get_range(&Range::new(from, to));
+        if records.len() != to - from {
+            return None
+        }
+
+        let filter = &self.filter;
+        let (range, count) = self.index.reorder(
+            from,
+            records.iter().map(|record| (record.get_id(), filter.matches(record))).collect()
+        );
+
+        if range.len() != count {
+            Some(vec![StoreViewMsg::Reload])
+        }
+        else if range.is_empty() {
+            Some(vec![])
+        }
+        else {
+            Some(vec![StoreViewMsg::Reorder{
+                from: Position(*range.start()),
+                to: Position(*range.end()),
+            }])
+        }
+    }
+}
+
+impl<Parent, Filter> Identifiable<Self, <Parent::Allocator as TemporaryIdAllocator>::Type> for FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    type Id = StoreId<Self>;
+
+    fn get_id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl<Parent, Filter> DataStore for FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    type Record = Parent::Record;
+    type Allocator = Parent::Allocator;
+    type Messages = Parent::Messages;
+
+    fn len(&self) -> usize {
+        self.state.borrow().index.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        let state = self.state.borrow();
+        self.parent.get(id)
+            .filter(|record| state.filter.matches(record))
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        let ids = self.state.borrow().index.ids(range);
+        ids.iter()
+            .filter_map(|id| self.parent.get(id))
+            .collect()
+    }
+
+    fn listen(&self, id: StoreId<Self>, sender: Sender<StoreViewMsg<Self::Record>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    fn unlisten(&self, id: StoreId<Self>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn sender(&self) -> Sender<Self::Messages> {
+        self.parent.sender()
+    }
+
+    fn send(&self, msg: Self::Messages) {
+        self.parent.send(msg)
+    }
+}
+
+/// Implements shallow clone. Clone shares the filter and listeners with the original
+impl<Parent, Filter> Clone for FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            parent: self.parent.clone(),
+            state: self.state.clone(),
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+/// Formats [`FilteredStore`] for debug format `{:?}`
+impl<Parent, Filter> Debug for FilteredStore<Parent, Filter>
+where
+    Parent: 'static + DataStore + Clone,
+    Filter: 'static + crate::Filter<Parent::Record>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FilteredStore")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
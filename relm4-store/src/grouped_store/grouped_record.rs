@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+
+use record::Id;
+use record::IdentityError;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+/// Entry of the [`GroupedStore`](crate::GroupedStore)
+///
+/// Grouped store shows a header before the records of each group. Your [`StoreViewPrototype`](crate::StoreViewPrototype)
+/// decides how to render both of them by matching on this enum in `init_view` and `view`.
+#[derive(Debug, Clone)]
+pub enum GroupedRecord<R, Key>
+where
+    R: Record,
+    Key: Clone,
+{
+    /// Header shown before the records of the group
+    Header{
+        /// Id of the header
+        ///
+        /// It's stable as long as there are records in the group
+        id: Id<Self>,
+        /// Key of the group
+        key: Key,
+        /// Number of records in the group
+        len: usize,
+        /// `true` if records of the group are hidden
+        collapsed: bool,
+    },
+    /// Record from the parent store
+    Record(R),
+}
+
+impl<R, Key> Record for GroupedRecord<R, Key>
+where
+    R: Record,
+    Key: Clone,
+{
+    type Allocator = R::Allocator;
+
+    fn get_id(&self) -> Id<Self> {
+        match self {
+            GroupedRecord::Header{id, ..} => *id,
+            GroupedRecord::Record(record) => record.get_id().transfer(),
+        }
+    }
+
+    fn set_permanent_id(&mut self, value: <Self::Allocator as TemporaryIdAllocator>::Type) -> Result<(), IdentityError> {
+        match self {
+            GroupedRecord::Header{..} => Err(IdentityError("Group headers are not persisted")),
+            GroupedRecord::Record(record) => record.set_permanent_id(value),
+        }
+    }
+}
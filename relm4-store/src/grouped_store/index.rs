@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::cmp::max;
+use std::cmp::min;
+
+use record::Id;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+use crate::Position;
+use crate::StoreViewMsg;
+use crate::math::Range;
+
+use super::GroupedRecord;
+
+type GroupedId<R, Key> = Id<GroupedRecord<R, Key>>;
+type GroupedMsg<R, Key> = StoreViewMsg<GroupedRecord<R, Key>>;
+
+/// Entry of the grouped store at given position
+#[derive(Debug)]
+pub(crate) enum Entry<R, Key>
+where
+    R: Record,
+    Key: Clone,
+{
+    Header{
+        id: GroupedId<R, Key>,
+        key: Key,
+        len: usize,
+        collapsed: bool,
+    },
+    Record(Id<R>),
+}
+
+#[derive(Debug)]
+struct Group<R, Key>
+where
+    R: Record,
+    Key: Clone,
+{
+    header: GroupedId<R, Key>,
+    /// Records of the group in the parent order
+    records: Vec<Id<R>>,
+}
+
+/// Keeps track of the groups and records in them
+///
+/// Groups are kept in the order of keys. Records in the group are kept in the parent order.
+/// Group exists as long as there are records in it. Collapsed state is remembered even if
+/// group doesn't exist at the moment.
+#[derive(Debug)]
+pub(crate) struct GroupIndex<R, Key>
+where
+    R: Record,
+    Key: Ord + Clone,
+{
+    /// Ids of the records in the parent store with the key of their group
+    parent: Vec<(Id<R>, Key)>,
+    /// Positions of the records in the parent store
+    positions: HashMap<Id<R>, usize>,
+    groups: BTreeMap<Key, Group<R, Key>>,
+    collapsed: BTreeSet<Key>,
+}
+
+impl<R, Key> GroupIndex<R, Key>
+where
+    R: Record,
+    Key: Ord + Clone,
+{
+    pub(crate) fn new() -> Self {
+        Self {
+            parent: Vec::new(),
+            positions: HashMap::new(),
+            groups: BTreeMap::new(),
+            collapsed: BTreeSet::new(),
+        }
+    }
+
+    /// Number of entries (headers and visible records) in the grouped store
+    pub(crate) fn len(&self) -> usize {
+        self.groups.iter()
+            .map(|(key, group)| self.group_len(key, group))
+            .sum()
+    }
+
+    pub(crate) fn parent_len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub(crate) fn contains(&self, id: &Id<R>) -> bool {
+        self.positions.contains_key(id)
+    }
+
+    pub(crate) fn is_collapsed(&self, key: &Key) -> bool {
+        self.collapsed.contains(key)
+    }
+
+    /// Returns true if the record is visible in the grouped store
+    pub(crate) fn is_visible(&self, id: &Id<R>) -> bool {
+        self.positions.get(id)
+            .map(|position| !self.is_collapsed(&self.parent[*position].1))
+            .unwrap_or(false)
+    }
+
+    /// Returns header with given id
+    pub(crate) fn header(&self, id: &GroupedId<R, Key>) -> Option<Entry<R, Key>> {
+        self.groups.iter()
+            .find(|(_, group)| group.header == *id)
+            .map(|(key, group)| self.header_entry(key, group))
+    }
+
+    /// Returns entries at the given range
+    pub(crate) fn entries(&self, range: &Range) -> Vec<Entry<R, Key>> {
+        let mut result = Vec::new();
+        let mut offset = 0;
+
+        for (key, group) in self.groups.iter() {
+            let len = self.group_len(key, group);
+            let start = max(*range.start(), offset);
+            let end = min(*range.end(), offset + len);
+
+            for position in start..end {
+                if position == offset {
+                    result.push(self.header_entry(key, group));
+                }
+                else {
+                    result.push(Entry::Record(group.records[position - offset - 1]));
+                }
+            }
+
+            offset += len;
+            if offset >= *range.end() {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// Replaces `old` id of the record with the `new` one
+    ///
+    /// Returns `false` if `old` id is unknown to the index
+    pub(crate) fn change_id(&mut self, old: &Id<R>, new: Id<R>) -> bool {
+        let key = match self.positions.remove(old) {
+            Some(position) => {
+                self.positions.insert(new, position);
+                self.parent[position].0 = new;
+                self.parent[position].1.clone()
+            },
+            None => return false,
+        };
+
+        if let Some(group) = self.groups.get_mut(&key) {
+            for id in group.records.iter_mut().filter(|id| *id == old) {
+                *id = new;
+            }
+        }
+        true
+    }
+
+    /// Replaces content of the index
+    ///
+    /// Groups which still exist keep their header ids
+    pub(crate) fn rebuild(&mut self, records: impl Iterator<Item=(Id<R>, Key)>) {
+        let mut groups = BTreeMap::new();
+        self.parent.clear();
+        self.positions.clear();
+
+        for (id, key) in records {
+            let group = groups.entry(key.clone()).or_insert_with(|| Group{
+                header: self.groups.get(&key).map(|g| g.header).unwrap_or_else(new_header_id),
+                records: Vec::new(),
+            });
+            group.records.push(id);
+            self.positions.insert(id, self.parent.len());
+            self.parent.push((id, key));
+        }
+
+        self.groups = groups;
+    }
+
+    /// Record has been inserted into the parent store at `position`
+    pub(crate) fn insert(&mut self, position: usize, id: Id<R>, key: Key) -> Vec<GroupedMsg<R, Key>> {
+        let mut replies = Vec::new();
+        let idx = self.index_in_group(position, &key);
+        self.parent.insert(position, (id, key.clone()));
+        self.reindex(position);
+
+        let created = !self.groups.contains_key(&key);
+        if created {
+            self.groups.insert(key.clone(), Group{
+                header: new_header_id(),
+                records: Vec::new(),
+            });
+            replies.push(StoreViewMsg::NewAt(Position(self.group_start(&key))));
+        }
+
+        let group = self.groups.get_mut(&key).expect("Group was just created");
+        group.records.insert(idx, id);
+        let header = group.header;
+
+        if !self.is_collapsed(&key) {
+            replies.push(StoreViewMsg::NewAt(Position(self.group_start(&key) + 1 + idx)));
+        }
+
+        if !created {
+            replies.push(StoreViewMsg::Update(header));
+        }
+
+        replies
+    }
+
+    /// Record has been removed from the parent store at `position`
+    pub(crate) fn remove(&mut self, position: usize) -> Vec<GroupedMsg<R, Key>> {
+        let mut replies = Vec::new();
+        let (id, key) = self.parent.remove(position);
+        self.positions.remove(&id);
+        self.reindex(position);
+        let idx = self.index_in_group(position, &key);
+        let start = self.group_start(&key);
+
+        if !self.is_collapsed(&key) {
+            replies.push(StoreViewMsg::Remove(Position(start + 1 + idx)));
+        }
+
+        let group = self.groups.get_mut(&key).expect("Record is in the index but not in the group");
+        group.records.remove(idx);
+
+        if group.records.is_empty() {
+            self.groups.remove(&key);
+            replies.push(StoreViewMsg::Remove(Position(start)));
+        }
+        else {
+            replies.push(StoreViewMsg::Update(group.header));
+        }
+
+        replies
+    }
+
+    /// Record has been updated in the parent store and now belongs to the group `key`
+    ///
+    /// Returns `None` if record is unknown to the index
+    pub(crate) fn update(&mut self, id: Id<R>, key: Key) -> Option<Vec<GroupedMsg<R, Key>>> {
+        let position = *self.positions.get(&id)?;
+
+        if self.parent[position].1 == key {
+            if self.is_collapsed(&key) {
+                Some(vec![])
+            }
+            else {
+                Some(vec![StoreViewMsg::Update(id.transfer())])
+            }
+        }
+        else {
+            let mut replies = self.remove(position);
+            replies.append(&mut self.insert(position, id, key));
+            Some(replies)
+        }
+    }
+
+    /// Record has been moved in the parent store
+    pub(crate) fn move_record(&mut self, from: usize, to: usize) -> Vec<GroupedMsg<R, Key>> {
+        let (id, key) = self.parent[from].clone();
+        let old_idx = self.index_in_group(from, &key);
+        let entry = self.parent.remove(from);
+        self.parent.insert(to, entry);
+        self.reindex(min(from, to));
+        let new_idx = self.index_in_group(to, &key);
+
+        if old_idx == new_idx {
+            return vec![]
+        }
+
+        let group = self.groups.get_mut(&key).expect("Record is in the index but not in the group");
+        group.records.remove(old_idx);
+        group.records.insert(new_idx, id);
+
+        if self.is_collapsed(&key) {
+            vec![]
+        }
+        else {
+            let start = self.group_start(&key);
+            vec![StoreViewMsg::Move{
+                from: Position(start + 1 + old_idx),
+                to: Position(start + 1 + new_idx),
+            }]
+        }
+    }
+
+    /// Hides or shows the records of the group
+    pub(crate) fn set_collapsed(&mut self, key: Key, collapsed: bool) -> Vec<GroupedMsg<R, Key>> {
+        if self.is_collapsed(&key) == collapsed {
+            return vec![]
+        }
+
+        if collapsed {
+            self.collapsed.insert(key.clone());
+        }
+        else {
+            self.collapsed.remove(&key);
+        }
+
+        let mut replies = Vec::new();
+        if let Some(group) = self.groups.get(&key) {
+            let start = self.group_start(&key);
+
+            for idx in 0..group.records.len() {
+                if collapsed {
+                    // every removal moves next record of the group to the same position
+                    replies.push(StoreViewMsg::Remove(Position(start + 1)));
+                }
+                else {
+                    replies.push(StoreViewMsg::NewAt(Position(start + 1 + idx)));
+                }
+            }
+
+            replies.push(StoreViewMsg::Update(group.header));
+        }
+
+        replies
+    }
+
+    /// Updates positions of the records in the parent store starting at `from`
+    fn reindex(&mut self, from: usize) {
+        for (idx, (id, _)) in self.parent[from..].iter().enumerate() {
+            self.positions.insert(*id, from + idx);
+        }
+    }
+
+    /// Number of entries occupied by the group
+    fn group_len(&self, key: &Key, group: &Group<R, Key>) -> usize {
+        if self.is_collapsed(key) {
+            1
+        }
+        else {
+            1 + group.records.len()
+        }
+    }
+
+    /// Position of the group header
+    fn group_start(&self, key: &Key) -> usize {
+        self.groups.range(..key)
+            .map(|(key, group)| self.group_len(key, group))
+            .sum()
+    }
+
+    /// Index in the group of the record at the parent `position`
+    fn index_in_group(&self, position: usize, key: &Key) -> usize {
+        self.parent[..position].iter()
+            .filter(|(_, other)| other == key)
+            .count()
+    }
+
+    fn header_entry(&self, key: &Key, group: &Group<R, Key>) -> Entry<R, Key> {
+        Entry::Header{
+            id: group.header,
+            key: key.clone(),
+            len: group.records.len(),
+            collapsed: self.is_collapsed(key),
+        }
+    }
+}
+
+fn new_header_id<R, Key>() -> GroupedId<R, Key>
+where
+    R: Record,
+    Key: Clone,
+{
+    Id::from(<R::Allocator as TemporaryIdAllocator>::new_id())
+}
@@ -0,0 +1,337 @@
+//! Store which splits records of the parent store into groups
+mod grouped_record;
+mod index;
+
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Identifiable;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+use crate::DataStore;
+use crate::Position;
+use crate::StoreId;
+use crate::StoreViewMsg;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::handlers::translate_batch;
+use crate::math::Range;
+
+pub use grouped_record::GroupedRecord;
+
+use index::Entry;
+use index::GroupIndex;
+
+type GroupedMsg<R, Key> = StoreViewMsg<GroupedRecord<R, Key>>;
+
+/// Data store showing records of the parent store split into groups
+///
+/// Groups are shown in the order of their keys, records in the group are kept in the parent order.
+/// Before records of each group there is a header entry. Records of the group can be hidden with
+/// [`GroupedStore::collapse`], header of the collapsed group stays visible.
+///
+/// Records of the grouped store are [`GroupedRecord`]s so your [`StoreViewPrototype`](crate::StoreViewPrototype)
+/// can render headers and records differently.
+///
+/// ```text
+/// fn init_view(record: &GroupedRecord<Task, Priority>, position: Position, sender: Sender<TaskMsg>) -> Self::RecordWidgets {
+///     match record {
+///         GroupedRecord::Header{key, len, collapsed, ..} => ...,
+///         GroupedRecord::Record(task) => ...,
+///     }
+/// }
+/// ```
+///
+/// Since headers are entries of the store, pagination of the views on top of the grouped store takes
+/// them into account.
+pub struct GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    id: StoreId<Self>,
+    parent: Parent,
+    state: Rc<RefCell<GroupedStoreState<Parent::Record, Grouping>>>,
+    connections: Connections<Self, GroupedRecord<Parent::Record, Grouping::Key>>,
+}
+
+impl<Parent, Grouping> GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    /// Creates new instance of the grouped store
+    pub fn new(parent: Parent, grouping: Grouping) -> Self {
+        let id = StoreId::new();
+        let mut state = GroupedStoreState{
+            index: GroupIndex::new(),
+            grouping,
+        };
+        state.rebuild(&parent);
+
+        let state = Rc::new(RefCell::new(state));
+        let connections: Connections<Self, GroupedRecord<Parent::Record, Grouping::Key>> = Rc::new(RefCell::new(HashMap::new()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let handler_parent = parent.clone();
+        let handler_state = state.clone();
+        let handler_connections = connections.clone();
+
+        {
+            let context = glib::MainContext::default();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Parent::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    let replies = state.translate(&handler_parent, msg);
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow grouped store state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        parent.listen(id.transfer(), sender);
+
+        Self {
+            id,
+            parent,
+            state,
+            connections,
+        }
+    }
+
+    /// Hides records of the group, header of the group stays visible
+    pub fn collapse(&self, key: Grouping::Key) {
+        self.set_collapsed(key, true);
+    }
+
+    /// Shows records of the group
+    pub fn expand(&self, key: Grouping::Key) {
+        self.set_collapsed(key, false);
+    }
+
+    /// Returns `true` if records of the group are hidden
+    pub fn is_collapsed(&self, key: &Grouping::Key) -> bool {
+        self.state.borrow().index.is_collapsed(key)
+    }
+
+    fn set_collapsed(&self, key: Grouping::Key, collapsed: bool) {
+        let replies = self.state.borrow_mut().index.set_collapsed(key, collapsed);
+        fire_handlers(&self.connections, &replies);
+    }
+
+    fn to_record(&self, entry: Entry<Parent::Record, Grouping::Key>) -> Option<GroupedRecord<Parent::Record, Grouping::Key>> {
+        match entry {
+            Entry::Header{id, key, len, collapsed} => Some(GroupedRecord::Header{id, key, len, collapsed}),
+            Entry::Record(id) => self.parent.get(&id).map(GroupedRecord::Record),
+        }
+    }
+}
+
+/// Part of the grouped store shared with the handler listening to the parent store
+#[derive(Debug)]
+struct GroupedStoreState<R, Grouping>
+where
+    R: Record,
+    Grouping: crate::Grouping<R>,
+{
+    index: GroupIndex<R, Grouping::Key>,
+    grouping: Grouping,
+}
+
+impl<R, Grouping> GroupedStoreState<R, Grouping>
+where
+    R: Record + Debug + Clone + 'static,
+    Grouping: crate::Grouping<R>,
+{
+    fn rebuild<Parent: DataStore<Record=R>>(&mut self, parent: &Parent) {
+        let records = parent.get_range(&Range::new(0, parent.len()));
+        let grouping = &self.grouping;
+        self.index.rebuild(records.iter().map(|record| (record.get_id(), grouping.key(record))));
+    }
+
+    /// Translates message from the parent store into messages for listeners of the grouped store
+    fn translate<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, msg: StoreViewMsg<R>) -> Vec<GroupedMsg<R, Grouping::Key>> {
+        let replies = match msg {
+            StoreViewMsg::NewAt(Position(position)) => self.new_at(parent, position),
+            StoreViewMsg::Remove(Position(position)) => {
+                if position < self.index.parent_len() {
+                    Some(self.index.remove(position))
+                }
+                else {
+                    None
+                }
+            },
+            StoreViewMsg::Update(id) => {
+                match parent.get(&id) {
+                    Some(record) => self.index.update(id, self.grouping.key(&record)),
+                    // record is already gone from the parent, remove message will follow
+                    None => Some(vec![]),
+                }
+            },
+            StoreViewMsg::Move{from: Position(from), to: Position(to)} => {
+                if from < self.index.parent_len() && to < self.index.parent_len() {
+                    Some(self.index.move_record(from, to))
+                }
+                else {
+                    None
+                }
+            },
+            // reorder can shuffle records between many groups, it's simpler to start over
+            StoreViewMsg::Reorder{..} | StoreViewMsg::Reload => {
+                self.rebuild(parent);
+                Some(vec![StoreViewMsg::Reload])
+            },
+            msg @ (StoreViewMsg::NewRange{..} | StoreViewMsg::RemoveRange{..}) => {
+                Some(translate_batch(msg.expand(), |msg| self.translate(parent, msg)))
+            },
+            StoreViewMsg::Batch(messages) => Some(translate_batch(messages, |msg| self.translate(parent, msg))),
+            StoreViewMsg::Error(err) => Some(vec![StoreViewMsg::Error(err)]),
+            StoreViewMsg::IdChanged{old, new} => {
+                if self.index.change_id(&old, new) {
+                    Some(vec![StoreViewMsg::IdChanged{old: old.transfer(), new: new.transfer()}])
+                }
+                else {
+                    // record was already added with the new id
+                    Some(vec![])
+                }
+            },
+        };
+
+        replies.unwrap_or_else(|| {
+            log::warn!("Grouped store is out of sync with the parent store. Reloading");
+            self.rebuild(parent);
+            vec![StoreViewMsg::Reload]
+        })
+    }
+
+    fn new_at<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, position: usize) -> Option<Vec<GroupedMsg<R, Grouping::Key>>> {
+        if position > self.index.parent_len() {
+            return None
+        }
+
+        let record = parent.get_range(&Range::new(position, position+1)).pop()?;
+        let id = record.get_id();
+        if self.index.contains(&id) {
+            // parent store is already ahead of us, position doesn't point to the new record
+            return None
+        }
+
+        Some(self.index.insert(position, id, self.grouping.key(&record)))
+    }
+}
+
+impl<Parent, Grouping> Identifiable<Self, <Parent::Allocator as TemporaryIdAllocator>::Type> for GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    type Id = StoreId<Self>;
+
+    fn get_id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl<Parent, Grouping> DataStore for GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    type Record = GroupedRecord<Parent::Record, Grouping::Key>;
+    type Allocator = Parent::Allocator;
+    type Messages = Parent::Messages;
+
+    fn len(&self) -> usize {
+        self.state.borrow().index.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        let entry = {
+            let state = self.state.borrow();
+            match state.index.header(id) {
+                Some(header) => header,
+                None => {
+                    let id = id.transfer();
+                    if !state.index.is_visible(&id) {
+                        return None
+                    }
+                    Entry::Record(id)
+                }
+            }
+        };
+
+        self.to_record(entry)
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        let entries = self.state.borrow().index.entries(range);
+        entries.into_iter()
+            .filter_map(|entry| self.to_record(entry))
+            .collect()
+    }
+
+    fn listen(&self, id: StoreId<Self>, sender: Sender<GroupedMsg<Parent::Record, Grouping::Key>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    fn unlisten(&self, id: StoreId<Self>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn sender(&self) -> Sender<Self::Messages> {
+        self.parent.sender()
+    }
+
+    fn send(&self, msg: Self::Messages) {
+        self.parent.send(msg)
+    }
+}
+
+/// Implements shallow clone. Clone shares the groups and listeners with the original
+impl<Parent, Grouping> Clone for GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            parent: self.parent.clone(),
+            state: self.state.clone(),
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+/// Formats [`GroupedStore`] for debug format `{:?}`
+impl<Parent, Grouping> Debug for GroupedStore<Parent, Grouping>
+where
+    Parent: 'static + DataStore + Clone,
+    Grouping: 'static + crate::Grouping<Parent::Record>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("GroupedStore")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
@@ -0,0 +1,77 @@
+//! Bookkeeping of the listeners attached to the data stores
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Record;
+
+use crate::DataStore;
+use crate::StoreId;
+use crate::StoreViewMsg;
+
+/// Listeners attached to the data store
+pub(crate) type Connections<Store, Record> = Rc<RefCell<HashMap<StoreId<Store>, Sender<StoreViewMsg<Record>>>>>;
+
+/// Translates every message of the batch from the parent store
+///
+/// Translation stops at the first reload, since state of the translating store is already rebuilt from the parent store
+/// which contains effects of the whole batch. Returns the batch of translated messages, or nothing if none were produced.
+/// Translated inserts and removes of the neighbouring records are merged back into ranges, see
+/// [StoreViewMsg::merge_ranges].
+pub(crate) fn translate_batch<R, Out>(messages: Vec<StoreViewMsg<R>>, mut translate: impl FnMut(StoreViewMsg<R>) -> Vec<StoreViewMsg<Out>>) -> Vec<StoreViewMsg<Out>>
+where
+    R: Record,
+    Out: Record,
+{
+    let mut replies = Vec::new();
+    for msg in messages {
+        for reply in translate(msg) {
+            match reply {
+                StoreViewMsg::Reload => return vec![StoreViewMsg::Reload],
+                StoreViewMsg::Batch(mut inner) => replies.append(&mut inner),
+                reply => replies.push(reply),
+            }
+        }
+    }
+
+    if replies.is_empty() {
+        vec![]
+    }
+    else {
+        vec![StoreViewMsg::Batch(StoreViewMsg::merge_ranges(replies))]
+    }
+}
+
+/// Sends all `messages` to every listener
+///
+/// Listeners with broken channel (closed by other side) are removed.
+pub(crate) fn fire_handlers<Store, R>(connections: &RefCell<HashMap<StoreId<Store>, Sender<StoreViewMsg<R>>>>, messages: &[StoreViewMsg<R>])
+where
+    Store: ?Sized + DataStore,
+    R: Record + Clone,
+{
+    if let Ok(mut connections) = connections.try_borrow_mut() {
+        let mut to_remove = Vec::<StoreId<Store>>::new();
+        for (sid,c) in connections.iter() {
+            for msg in messages {
+                if c.send(msg.clone()).is_err() {
+                    // in case of broken channel (closed by other side), mark it for removal
+                    to_remove.push(*sid);
+                    break;
+                }
+            }
+        }
+
+        for sid in to_remove {
+            connections.remove(&sid);
+        }
+    }
+    else {
+        log::warn!("Can't borrow connections. Remember to release leases");
+    }
+}
@@ -0,0 +1,291 @@
+//! Backend wrapper keeping history of the changes
+use reexport::log;
+
+use std::collections::VecDeque;
+
+use record::Id;
+use record::Record;
+
+use crate::Backend;
+use crate::BackendError;
+use crate::OrderedBackend;
+use crate::Position;
+use crate::Replies;
+use crate::StoreMsg;
+use crate::StoreViewMsg;
+use crate::TreeBackend;
+use crate::UndoableBackend;
+use crate::math::Range;
+
+/// Replies of the backend and the message reverting the change
+type Applied<R> = (Vec<StoreViewMsg<R>>, Option<StoreMsg<R>>);
+
+/// Backend which remembers how to revert the changes applied to the wrapped backend
+///
+/// For every message handled by the backend history keeps the inverse message
+///
+/// - [`StoreMsg::Commit`] of the existing record is reverted by committing the previous value
+/// - [`StoreMsg::Commit`] of the new record is reverted by deleting it
+/// - [`StoreMsg::Delete`] is reverted by [`StoreMsg::Restore`] of the record at it's old position
+/// - [`StoreMsg::Transaction`] is reverted as a whole, by reverting it's messages in reverse order
+///
+/// [`StoreMsg::Reload`] clears the history since there is no way to tell what has changed.
+///
+/// Undo and redo go via the wrapped backend `inbox`, so views are updated with the usual [`StoreViewMsg`]s. If undo
+/// or redo changes more then one record, changes are reported as a single [`StoreViewMsg::Batch`].
+///
+/// ```text
+/// let store = Store::new(HistoryBackend::new(InMemoryBackend::<Tasks>::new(), 100));
+/// store.send(StoreMsg::Delete(task_id));
+/// ...
+/// store.undo();
+/// ```
+///
+/// **Warning** Only the deleted record can be restored. If backend removes more records for a single delete
+/// (like the tree backends removing whole subtree) other records are lost.
+#[derive(Debug)]
+pub struct HistoryBackend<B: Backend> {
+    backend: B,
+    /// Inverses of the applied changes, last change at the back
+    undo: VecDeque<StoreMsg<B::Record>>,
+    /// Inverses of the reverted changes, last reverted change at the back
+    redo: Vec<StoreMsg<B::Record>>,
+    depth: usize,
+}
+
+impl<B: Backend> HistoryBackend<B> {
+    /// Creates new instance of the history backend
+    ///
+    /// - **backend** backend which keeps the data
+    /// - **depth** maximal number of changes which can be reverted
+    pub fn new(backend: B, depth: usize) -> Self {
+        Self {
+            backend,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    /// Returns reference to the wrapped backend
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Maximal number of changes which can be reverted
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Removes all changes from the history
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn push_undo(&mut self, inverse: StoreMsg<B::Record>) {
+        self.undo.push_back(inverse);
+        while self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Applies message to the backend
+    ///
+    /// Returns replies of the backend and the message which reverts the change
+    fn apply(&mut self, msg: StoreMsg<B::Record>) -> Result<Applied<B::Record>, BackendError> {
+        match msg {
+            StoreMsg::Commit(record) => {
+                let previous = self.backend.get(&record.get_id());
+                let Replies{ replies } = self.backend.inbox(StoreMsg::Commit(record))?;
+                let inverse = self.revert_upsert(previous, &replies);
+                Ok((replies, inverse))
+            },
+            StoreMsg::Restore{record, position} => {
+                let previous = self.backend.get(&record.get_id());
+                let Replies{ replies } = self.backend.inbox(StoreMsg::Restore{record, position})?;
+                let inverse = self.revert_upsert(previous, &replies);
+                Ok((replies, inverse))
+            },
+            StoreMsg::Delete(id) => {
+                let record = self.backend.get(&id);
+                let Replies{ replies } = self.backend.inbox(StoreMsg::Delete(id))?;
+                let removed: Vec<Position> = replies.iter()
+                    .filter_map(|reply| match reply {
+                        StoreViewMsg::Remove(position) => Some(*position),
+                        _ => None,
+                    })
+                    .collect();
+
+                if removed.len() > 1 {
+                    log::warn!("Backend removed {} records while deleting {:?}. Only the deleted record can be restored", removed.len(), id);
+                }
+
+                let inverse = record.zip(removed.first().copied())
+                    .map(|(record, position)| StoreMsg::Restore{record, position});
+                Ok((replies, inverse))
+            },
+            StoreMsg::Reload => {
+                let Replies{ replies } = self.backend.inbox(StoreMsg::Reload)?;
+                self.clear();
+                Ok((replies, None))
+            },
+            StoreMsg::Transaction(messages) => {
+                // messages are applied one by one to collect the inverses, so rollback must be done here
+                let mut replies = Vec::new();
+                let mut inverses = Vec::new();
+                for msg in messages {
+                    match self.apply(msg) {
+                        Ok((mut msg_replies, inverse)) => {
+                            replies.append(&mut msg_replies);
+                            inverses.extend(inverse);
+                        },
+                        Err(err) => {
+                            for inverse in inverses.into_iter().rev() {
+                                if let Err(rollback_err) = self.apply(inverse) {
+                                    log::warn!("Unable to roll back the transaction: {}", rollback_err);
+                                }
+                            }
+                            return Err(err)
+                        }
+                    }
+                }
+                inverses.reverse();
+
+                let inverse = if inverses.is_empty() {
+                    None
+                }
+                else {
+                    Some(StoreMsg::Transaction(inverses))
+                };
+                Ok((replies, inverse))
+            },
+        }
+    }
+
+    /// Returns message reverting commit or restore of the record
+    fn revert_upsert(&self, previous: Option<B::Record>, replies: &[StoreViewMsg<B::Record>]) -> Option<StoreMsg<B::Record>> {
+        match previous {
+            Some(previous) => Some(StoreMsg::Commit(previous)),
+            None => {
+                // backend might have given the record a permanent id, so take the id from the store
+                let id: Option<Id<B::Record>> = replies.iter()
+                    .find_map(|reply| match reply {
+                        StoreViewMsg::NewAt(Position(position)) => {
+                            self.backend.get_range(&Range::new(*position, position+1))
+                                .first()
+                                .map(|record| record.get_id())
+                        },
+                        _ => None,
+                    });
+                id.map(StoreMsg::Delete)
+            }
+        }
+    }
+}
+
+/// Reports many replies as a single change
+fn batch<R>(replies: Vec<StoreViewMsg<R>>) -> Replies<R>
+where
+    R: Record + std::fmt::Debug + Clone + 'static,
+{
+    if replies.len() > 1 {
+        Replies{
+            replies: vec![StoreViewMsg::Batch(replies)]
+        }
+    }
+    else {
+        Replies{
+            replies
+        }
+    }
+}
+
+impl<B: Backend> Backend for HistoryBackend<B> {
+    type Record = B::Record;
+
+    fn len(&self) -> usize {
+        self.backend.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.backend.is_empty()
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        self.backend.get(id)
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        self.backend.get_range(range)
+    }
+
+    fn inbox(&mut self, msg: StoreMsg<Self::Record>) -> Result<Replies<Self::Record>, BackendError> {
+        let (replies, inverse) = self.apply(msg)?;
+        if let Some(inverse) = inverse {
+            self.push_undo(inverse);
+            self.redo.clear();
+        }
+
+        Ok(Replies{
+            replies
+        })
+    }
+}
+
+impl<B: Backend> UndoableBackend for HistoryBackend<B> {
+    fn undo(&mut self) -> Result<Replies<Self::Record>, BackendError> {
+        match self.undo.pop_back() {
+            Some(msg) => {
+                let (replies, inverse) = self.apply(msg)?;
+                self.redo.extend(inverse);
+                Ok(batch(replies))
+            },
+            None => Ok(Replies{
+                replies: vec![]
+            }),
+        }
+    }
+
+    fn redo(&mut self) -> Result<Replies<Self::Record>, BackendError> {
+        match self.redo.pop() {
+            Some(msg) => {
+                let (replies, inverse) = self.apply(msg)?;
+                if let Some(inverse) = inverse {
+                    self.push_undo(inverse);
+                }
+                Ok(batch(replies))
+            },
+            None => Ok(Replies{
+                replies: vec![]
+            }),
+        }
+    }
+
+    fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+impl<B, OrderBy> OrderedBackend<OrderBy> for HistoryBackend<B>
+where
+    B: OrderedBackend<OrderBy>,
+{
+    fn set_order(&mut self, order: OrderBy) -> Replies<Self::Record> {
+        self.backend.set_order(order)
+    }
+}
+
+impl<B: TreeBackend> TreeBackend for HistoryBackend<B> {
+    fn children_len(&self, node: Option<&Id<Self::Record>>) -> usize {
+        self.backend.children_len(node)
+    }
+
+    fn children(&self, node: Option<&Id<Self::Record>>, range: &Range) -> Vec<Self::Record> {
+        self.backend.children(node, range)
+    }
+}
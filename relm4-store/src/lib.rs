@@ -20,11 +20,19 @@
     unreachable_pub
 )]
 
+mod backend_error;
+mod detached_store;
 mod factory_prototype;
+mod filtered_store;
+mod grouped_store;
+mod handlers;
+mod history_backend;
 pub mod math;
 mod pagination;
 mod position;
 mod record_with_location;
+mod selection_model;
+mod sorted_store;
 pub mod redraw_messages;
 mod store_id;
 mod store_msg;
@@ -32,6 +40,7 @@ mod store_size;
 mod store_view_component;
 mod store_view_msg;
 mod store;
+mod tree_store;
 pub mod window;
 
 use reexport::relm4;
@@ -48,12 +57,23 @@ use record::TemporaryIdAllocator;
 
 use crate::math::Range;
 
+pub use backend_error::BackendError;
+pub use detached_store::CommitConflict;
+pub use detached_store::DetachedStore;
 pub use factory_prototype::StoreViewPrototype;
 pub use factory_prototype::FactoryContainerWidgets;
 pub use factory_prototype::StoreViewInnerComponent;
+pub use filtered_store::FilteredStore;
+pub use grouped_store::GroupedRecord;
+pub use grouped_store::GroupedStore;
+pub use history_backend::HistoryBackend;
+pub use pagination::PageOutOfRange;
 pub use pagination::Pagination;
 pub use position::Position;
 pub use record_with_location::RecordWithLocation;
+pub use selection_model::SelectionMode;
+pub use selection_model::SelectionModel;
+pub use sorted_store::SortedStore;
 pub use store::Store;
 pub use store_id::StoreId;
 pub use store_msg::StoreMsg;
@@ -61,6 +81,8 @@ pub use store_size::StoreSize;
 pub use store_view_component::StoreViewComponent;
 pub use store_view_component::StoreViewInterfaceError;
 pub use store_view_msg::StoreViewMsg;
+pub use tree_store::FlatTreeStore;
+pub use tree_store::TreeNode;
 
 /// DataStore is a trait describing collections specialized in housekeeping business model data
 /// 
@@ -225,6 +247,12 @@ pub trait StoreView: DataStore
     /// Moves the window to the new range
     fn set_window(&self, range: Range);
 
+    /// Changes the number of records in the window
+    /// 
+    /// Window behavior decides where the resized window starts, by default the first record of the window stays in
+    /// place. Records which stay in the window are not reloaded.
+    fn set_window_size(&self, size: usize);
+
     /// Returns vector with list of records in the current view
     /// 
     /// Returned records are **clones** of the actual records
@@ -252,6 +280,12 @@ pub trait StoreView: DataStore
     /// Goes to the last page of the data in the underlying store
     fn last_page(&self);
 
+    /// Goes to the given page of the data in the underlying store
+    /// 
+    /// Pages are counted from `1` like in [`Pagination::current_page`]. First page is always valid, even if the
+    /// store is empty. If page is past the [`Pagination::total_pages`] window is left untouched and error is returned.
+    fn to_page(&self, page: usize) -> Result<(), PageOutOfRange>;
+
     /// Returns current size of unhandled messages in the view
     fn inbox_queue_size(&self) -> usize;
 }
@@ -298,7 +332,9 @@ pub trait Backend {
 
 
     /// Handles messages
-    fn inbox(&mut self, msg: StoreMsg<Self::Record>) -> Replies<Self::Record>;
+    /// 
+    /// If message can't be applied backend returns an error and leaves the data as it was before the message.
+    fn inbox(&mut self, msg: StoreMsg<Self::Record>) -> Result<Replies<Self::Record>, BackendError>;
 }
 
 /// Default trait describing how the records should be sorted by backend
@@ -309,6 +345,25 @@ pub trait Sorter<Record: record::Record>: Copy + Debug {
     fn cmp(&self, lhs: &Record, rhs: &Record) -> std::cmp::Ordering;
 }
 
+/// Trait describing which records should be visible in the [`FilteredStore`]
+/// 
+/// Filter is evaluated every time record is added or updated in the parent store, so
+/// it should be cheap. If you need to change the filter criteria use [`FilteredStore::set_filter`].
+pub trait Filter<Record: record::Record>: Debug {
+    /// Returns `true` if `record` should be visible
+    fn matches(&self, record: &Record) -> bool;
+}
+
+/// Trait describing how records are split into groups by the [`GroupedStore`]
+pub trait Grouping<Record: record::Record>: Debug {
+    /// Key identifying the group
+    /// 
+    /// Groups are shown in the order of the keys
+    type Key: Ord + Clone + Debug + 'static;
+
+    /// Returns key of the group to which `record` belongs
+    fn key(&self, record: &Record) -> Self::Key;
+}
 
 /// Trait implemented by the data store which supports switching of the natural order
 /// 
@@ -334,4 +389,80 @@ pub trait OrderedStore<OrderBy>: DataStore {
 pub trait OrderedBackend<OrderBy>: Backend {
     /// sets natural order of the records
     fn set_order(&mut self, order: OrderBy) -> Replies<Self::Record>;
-}
\ No newline at end of file
+}
+
+/// Trait implemented by the data store which can undo the changes
+/// 
+/// If you use [`Store`] then it will use history kept by the backend. Easiest way to get it is to wrap your backend
+/// with [`HistoryBackend`].
+pub trait UndoableStore: DataStore {
+    /// Reverts the last change
+    /// 
+    /// Only changes already handled by the store can be undone. Messages which are still waiting in the store channel
+    /// are not part of the history yet.
+    fn undo(&self);
+
+    /// Applies again the last reverted change
+    fn redo(&self);
+
+    /// Returns `true` if there is a change which can be reverted
+    fn can_undo(&self) -> bool;
+
+    /// Returns `true` if there is a reverted change which can be applied again
+    fn can_redo(&self) -> bool;
+}
+
+/// Trait implemented by the data store backends which can undo the changes
+/// 
+/// Returned replies describe what happened to the records, the same way as if the change was sent via [`Backend::inbox`]
+pub trait UndoableBackend: Backend {
+    /// Reverts the last change
+    /// 
+    /// If the change can't be reverted it's dropped from the history and the error is returned
+    fn undo(&mut self) -> Result<Replies<Self::Record>, BackendError>;
+
+    /// Applies again the last reverted change
+    /// 
+    /// If the change can't be applied it's dropped from the history and the error is returned
+    fn redo(&mut self) -> Result<Replies<Self::Record>, BackendError>;
+
+    /// Returns `true` if there is a change which can be reverted
+    fn can_undo(&self) -> bool;
+
+    /// Returns `true` if there is a reverted change which can be applied again
+    fn can_redo(&self) -> bool;
+}
+
+/// Trait implemented by the data store which keeps records in a tree
+/// 
+/// Linear order of the store (the one used by [`DataStore::get_range`] and positions in the [`StoreViewMsg`]) is a depth first
+/// pre-order of the tree. So descendants of the node are always right after it. Usually records of such a store implement
+/// [`record::TreeRecord`].
+/// 
+/// If you use [`Store`] then it will use the tree defined by the backend
+pub trait TreeDataStore: DataStore {
+    /// Returns number of children of the `node`
+    /// 
+    /// If `node` is [None] returns number of roots
+    fn children_len(&self, node: Option<&Id<Self::Record>>) -> usize;
+
+    /// Returns children of the `node` in the given range
+    /// 
+    /// If `node` is [None] returns roots. If range is out of bounds returned vector will be empty.
+    fn children(&self, node: Option<&Id<Self::Record>>, range: &Range) -> Vec<Self::Record>;
+}
+
+/// Trait implemented by the data store backends keeping records in a tree
+/// 
+/// Linear order of the backend must be a depth first pre-order of the tree. See [`TreeDataStore`] for details.
+pub trait TreeBackend: Backend {
+    /// Returns number of children of the `node`
+    /// 
+    /// If `node` is [None] returns number of roots
+    fn children_len(&self, node: Option<&Id<Self::Record>>) -> usize;
+
+    /// Returns children of the `node` in the given range
+    /// 
+    /// If `node` is [None] returns roots. If range is out of bounds returned vector will be empty.
+    fn children(&self, node: Option<&Id<Self::Record>>, range: &Range) -> Vec<Self::Record>;
+}
@@ -77,6 +77,30 @@ impl Range {
     pub fn to_right(&self, r: usize) -> Range {
         self.slide(self.start() + r)
     }
+
+    /// Returns the smallest range outside of which `before` and `after` are equal
+    ///
+    /// Both slices must have the same length. Returns empty range if slices are equal. Useful to find the
+    /// [`crate::StoreViewMsg::Reorder`] region after the order of the store has changed.
+    pub fn changed<T: PartialEq>(before: &[T], after: &[T]) -> Range {
+        debug_assert_eq!(before.len(), after.len(), "Reorder can't change number of elements");
+
+        let start = before.iter()
+            .zip(after)
+            .position(|(lhs, rhs)| lhs != rhs);
+
+        match start {
+            Some(start) => {
+                let end = before.iter()
+                    .zip(after)
+                    .rposition(|(lhs, rhs)| lhs != rhs)
+                    .map(|end| end + 1)
+                    .unwrap_or(start);
+                Range::new(start, end)
+            },
+            None => Range::new(0, 0),
+        }
+    }
 }
 
 impl Display for Range {
@@ -1,5 +1,28 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
 use crate::StoreView;
 
+/// Error returned by [`StoreView::to_page`] when the page doesn't exist
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageOutOfRange {
+    /// Requested page
+    pub page: usize,
+    /// Total amount of pages at the time of the request
+    pub total_pages: usize,
+}
+
+/// Formats [`PageOutOfRange`] for empty format `{}`
+impl Display for PageOutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_fmt(format_args!("Page {} is out of range 1..={}", self.page, self.total_pages.max(1)))
+    }
+}
+
+impl Error for PageOutOfRange {}
+
 /// Generic pagination methods which could be carpet implemented for any store-view
 pub trait Pagination<SV> 
 where
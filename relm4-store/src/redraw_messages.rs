@@ -1,5 +1,7 @@
 //! Defines messages sent when redraw must be handled
 
+use crate::BackendError;
+
 /// Messages used to notify interrelated components that redraw is needed
 /// 
 /// They might be interesting for you if you are implementing custom store-view
@@ -7,4 +9,6 @@
 pub enum RedrawMessages {
     /// Message sent to redraw parts of ui
     Redraw,
+    /// Store reported an error, it's passed to the [StoreViewPrototype::on_error](crate::StoreViewPrototype::on_error)
+    Error(BackendError),
 }
\ No newline at end of file
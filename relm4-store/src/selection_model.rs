@@ -0,0 +1,288 @@
+//! Selection of the records in the data store
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Record;
+
+use crate::DataStore;
+use crate::Position;
+use crate::StoreId;
+use crate::StoreViewMsg;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::math::Range;
+
+/// Describes how many records can be selected at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// At most one record is selected, selecting a record unselects the previous one
+    Single,
+    /// Any number of records can be selected
+    Multiple,
+}
+
+/// Keeps track of the selected records of the data store
+///
+/// Selection is kept by the record [Id], so it doesn't depend on which records are currently shown by the store
+/// view. Selected record stays selected when the store view pages away from it and back. Selection model listens
+/// to the store, so removed records are dropped from the selection and records which got a new id stay selected.
+///
+/// Every time the selection of the record changes, listeners receive [StoreViewMsg::Update] for it. Attach
+/// the selection model to the store view so it can redraw selected records.
+///
+/// ```text
+/// let selection = SelectionModel::new(tasks.clone(), SelectionMode::Multiple);
+/// store_view.set_selection(Some(selection.clone()));
+///
+/// selection.select(&task_id);
+/// ```
+pub struct SelectionModel<Store>
+where
+    Store: 'static + DataStore + Clone,
+{
+    listener: Rc<Listener<Store>>,
+    state: Rc<RefCell<SelectionState<Store::Record>>>,
+    connections: Connections<Store, Store::Record>,
+}
+
+impl<Store> SelectionModel<Store>
+where
+    Store: 'static + DataStore + Clone,
+{
+    /// Creates new instance of the selection model with nothing selected
+    pub fn new(store: Store, mode: SelectionMode) -> Self {
+        let id = StoreId::new();
+        let state = Rc::new(RefCell::new(SelectionState{
+            mode,
+            selected: HashSet::new(),
+        }));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let handler_store = store.clone();
+        let handler_state = state.clone();
+
+        {
+            let context = glib::MainContext::default();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Store::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    state.follow(&handler_store, msg);
+                }
+                else {
+                    log::warn!("Can't borrow selection state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        store.listen(id, sender);
+
+        Self{
+            listener: Rc::new(Listener{
+                id,
+                store,
+            }),
+            state,
+            connections: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Returns selection mode of this model
+    pub fn mode(&self) -> SelectionMode {
+        self.state.borrow().mode
+    }
+
+    /// Returns `true` if record with given `id` is selected
+    pub fn is_selected(&self, id: &Id<Store::Record>) -> bool {
+        self.state.borrow().selected.contains(id)
+    }
+
+    /// Returns ids of all selected records
+    pub fn selected(&self) -> HashSet<Id<Store::Record>> {
+        self.state.borrow().selected.clone()
+    }
+
+    /// Selects the record
+    ///
+    /// In [SelectionMode::Single] previously selected record is unselected
+    pub fn select(&self, id: &Id<Store::Record>) {
+        let changed = {
+            let mut state = self.state.borrow_mut();
+            let mut changed = Vec::new();
+            if state.mode == SelectionMode::Single {
+                changed.extend(state.selected.iter().filter(|selected| *selected != id).copied());
+                state.selected.retain(|selected| selected == id);
+            }
+            if state.selected.insert(*id) {
+                changed.push(*id);
+            }
+            changed
+        };
+
+        self.notify(changed);
+    }
+
+    /// Unselects the record
+    pub fn unselect(&self, id: &Id<Store::Record>) {
+        let removed = self.state.borrow_mut().selected.remove(id);
+        if removed {
+            self.notify(vec![*id]);
+        }
+    }
+
+    /// Selects the record if it's not selected, unselects it otherwise
+    pub fn toggle(&self, id: &Id<Store::Record>) {
+        if self.is_selected(id) {
+            self.unselect(id);
+        }
+        else {
+            self.select(id);
+        }
+    }
+
+    /// Selects all records between positions `from` and `to` in the store, both inclusive
+    ///
+    /// Order of `from` and `to` doesn't matter. Records which were selected before stay selected. In
+    /// [SelectionMode::Single] only the record at `to` is selected. Positions after the end of the store are
+    /// ignored.
+    ///
+    /// Only the records in the range are read from the store. If you know the records but not their positions,
+    /// [StoreView::get_position](crate::StoreView::get_position) finds the visible ones.
+    pub fn select_range(&self, from: Position, to: Position) {
+        if self.mode() == SelectionMode::Single {
+            if let Some(record) = self.listener.store.get_range(&Range::new(to.0, to.0 + 1)).first() {
+                self.select(&record.get_id());
+            }
+            return
+        }
+
+        let range = Range::new(from.0.min(to.0), from.0.max(to.0) + 1);
+        let changed = {
+            let mut state = self.state.borrow_mut();
+            self.listener.store.get_range(&range)
+                .iter()
+                .map(|record| record.get_id())
+                .filter(|id| state.selected.insert(*id))
+                .collect()
+        };
+
+        self.notify(changed);
+    }
+
+    /// Unselects all records
+    pub fn clear(&self) {
+        let changed = self.state.borrow_mut().selected.drain().collect();
+        self.notify(changed);
+    }
+
+    /// Attaches sender to the selection model
+    ///
+    /// Sender receives [StoreViewMsg::Update] for every record which got selected or unselected
+    pub fn listen(&self, id: StoreId<Store>, sender: Sender<StoreViewMsg<Store::Record>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    /// Removes sender from the selection model
+    pub fn unlisten(&self, id: StoreId<Store>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn notify(&self, changed: Vec<Id<Store::Record>>) {
+        if changed.is_empty() {
+            return
+        }
+
+        let messages: Vec<StoreViewMsg<Store::Record>> = changed.into_iter()
+            .map(StoreViewMsg::Update)
+            .collect();
+        fire_handlers(&self.connections, &messages);
+    }
+}
+
+impl<Store> Clone for SelectionModel<Store>
+where
+    Store: 'static + DataStore + Clone,
+{
+    fn clone(&self) -> Self {
+        Self{
+            listener: self.listener.clone(),
+            state: self.state.clone(),
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+impl<Store> Debug for SelectionModel<Store>
+where
+    Store: 'static + DataStore + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SelectionModel")
+            .field("id", &self.listener.id)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Registration of the selection model as the listener of the store
+///
+/// It's shared by all clones of the selection model, so the last one dropped stops listening to the store
+struct Listener<Store>
+where
+    Store: 'static + DataStore,
+{
+    id: StoreId<Store>,
+    store: Store,
+}
+
+impl<Store> Drop for Listener<Store>
+where
+    Store: 'static + DataStore,
+{
+    fn drop(&mut self) {
+        self.store.unlisten(self.id);
+    }
+}
+
+/// Part of the selection model shared with the handler listening to the store
+#[derive(Debug)]
+struct SelectionState<R: Record> {
+    mode: SelectionMode,
+    selected: HashSet<Id<R>>,
+}
+
+impl<R: Record> SelectionState<R> {
+    /// Updates the selection after the change in the store
+    fn follow<Store: DataStore<Record=R>>(&mut self, store: &Store, msg: StoreViewMsg<R>) {
+        match msg {
+            StoreViewMsg::Remove(_) | StoreViewMsg::RemoveRange{..} | StoreViewMsg::Reload => {
+                // removed records are not known by id, so drop the ones which are gone from the store
+                self.selected.retain(|id| store.get(id).is_some());
+            },
+            StoreViewMsg::IdChanged{old, new} => {
+                if self.selected.remove(&old) {
+                    self.selected.insert(new);
+                }
+            },
+            StoreViewMsg::Batch(messages) => {
+                for msg in messages {
+                    self.follow(store, msg);
+                }
+            },
+            // selection is kept by id so records moved around stay selected
+            StoreViewMsg::NewAt(_) | StoreViewMsg::NewRange{..} | StoreViewMsg::Move{..} | StoreViewMsg::Reorder{..} |
+            StoreViewMsg::Update(_) | StoreViewMsg::Error(_) => {},
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+use std::cmp::min;
+use std::collections::HashMap;
+
+use record::Id;
+use record::Record;
+
+use crate::Position;
+use crate::Sorter;
+use crate::StoreViewMsg;
+use crate::math::Range;
+
+/// Copy of the record kept by the index
+#[derive(Debug)]
+struct Entry<R> {
+    record: R,
+    /// Order of arrival, decides the order of records which are equal according to the sorter
+    arrival: usize,
+}
+
+/// Keeps the records of the parent store in the order defined by the sorter
+///
+/// Records which are equal according to the sorter are kept in the order of arrival. Records present
+/// at the time of rebuild arrive in the parent order.
+#[derive(Debug)]
+pub(crate) struct SortIndex<R, OrderBy>
+where
+    R: Record,
+    OrderBy: Sorter<R>,
+{
+    /// Ids of the records in the parent store order
+    parent: Vec<Id<R>>,
+    /// Positions of the records in the parent store
+    positions: HashMap<Id<R>, usize>,
+    /// Ids of the records in the sorted order
+    order: Vec<Id<R>>,
+    /// Copies of the records used for sorting
+    records: HashMap<Id<R>, Entry<R>>,
+    /// Arrival of the next record
+    next: usize,
+    ordering: OrderBy,
+}
+
+impl<R, OrderBy> SortIndex<R, OrderBy>
+where
+    R: Record + Clone,
+    OrderBy: Sorter<R>,
+{
+    pub(crate) fn new(ordering: OrderBy) -> Self {
+        Self {
+            parent: Vec::new(),
+            positions: HashMap::new(),
+            order: Vec::new(),
+            records: HashMap::new(),
+            next: 0,
+            ordering,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub(crate) fn parent_len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub(crate) fn contains(&self, id: &Id<R>) -> bool {
+        self.records.contains_key(id)
+    }
+
+    pub(crate) fn get(&self, id: &Id<R>) -> Option<R> {
+        self.records.get(id).map(|entry| entry.record.clone())
+    }
+
+    /// Replaces `old` id of the record with the id of the `record`
+    ///
+    /// Record keeps it's position since only the id has changed. Returns `false` if `old` id is unknown to the index
+    pub(crate) fn change_id(&mut self, old: &Id<R>, record: R) -> bool {
+        let sorted = match self.records.get(old) {
+            Some(entry) => self.position(entry),
+            None => return false,
+        };
+        let entry = self.records.remove(old).expect("Record was just found");
+        let parent = self.positions.remove(old).expect("Record is missing in the parent order while it's in the index");
+
+        let new = record.get_id();
+        self.records.insert(new, Entry{
+            record,
+            arrival: entry.arrival,
+        });
+        self.positions.insert(new, parent);
+        self.parent[parent] = new;
+        self.order[sorted] = new;
+        true
+    }
+
+    /// Returns records at the given range of the sorted store
+    pub(crate) fn get_range(&self, range: &Range) -> Vec<R> {
+        let start = min(*range.start(), self.len());
+        let end = min(*range.end(), self.len());
+
+        self.order[start..end].iter()
+            .map(|id| self.records[id].record.clone())
+            .collect()
+    }
+
+    /// Replaces content of the index with `records` given in the parent order
+    pub(crate) fn rebuild(&mut self, records: Vec<R>) {
+        self.parent = records.iter().map(|r| r.get_id()).collect();
+        self.positions = self.parent.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
+        self.records = records.into_iter()
+            .enumerate()
+            .map(|(arrival, record)| (record.get_id(), Entry{record, arrival}))
+            .collect();
+        self.next = self.parent.len();
+        self.order = self.parent.clone();
+        self.sort();
+    }
+
+    /// Changes the order of records
+    ///
+    /// Returns the range of positions which have changed
+    pub(crate) fn set_order(&mut self, ordering: OrderBy) -> Range {
+        let before = self.order.clone();
+        self.ordering = ordering;
+        self.sort();
+
+        Range::changed(&before, &self.order)
+    }
+
+    /// Compares records by the sorter and by the order of arrival if sorter finds them equal
+    fn cmp(&self, lhs: &Entry<R>, rhs: &Entry<R>) -> Ordering {
+        self.ordering.cmp(&lhs.record, &rhs.record)
+            .then(lhs.arrival.cmp(&rhs.arrival))
+    }
+
+    fn sort(&mut self) {
+        let mut order = std::mem::take(&mut self.order);
+        order.sort_unstable_by(|lhs, rhs| self.cmp(&self.records[lhs], &self.records[rhs]));
+        self.order = order;
+    }
+
+    /// Position in the sorted order of the record already kept in the index
+    fn position(&self, entry: &Entry<R>) -> usize {
+        self.order.binary_search_by(|other| self.cmp(&self.records[other], entry))
+            .expect("Record is missing in the sorted order while it's in the index")
+    }
+
+    /// Position at which record should be inserted into sorted order
+    fn insert_position(&self, entry: &Entry<R>) -> usize {
+        self.order.partition_point(|other| self.cmp(&self.records[other], entry) == Ordering::Less)
+    }
+
+    /// Updates positions of the records in the parent store starting at `from`
+    fn reindex(&mut self, from: usize) {
+        for (idx, id) in self.parent[from..].iter().enumerate() {
+            self.positions.insert(*id, from + idx);
+        }
+    }
+
+    /// Record has been inserted into the parent store at `position`
+    ///
+    /// Returns position in the sorted store
+    pub(crate) fn insert(&mut self, position: usize, record: R) -> Position {
+        let id = record.get_id();
+        let entry = Entry{
+            record,
+            arrival: self.next,
+        };
+        self.next += 1;
+        let sorted = self.insert_position(&entry);
+
+        self.parent.insert(position, id);
+        self.reindex(position);
+        self.order.insert(sorted, id);
+        self.records.insert(id, entry);
+
+        Position(sorted)
+    }
+
+    /// Record has been removed from the parent store at `position`
+    ///
+    /// Returns position in the sorted store
+    pub(crate) fn remove(&mut self, position: usize) -> Position {
+        let id = self.parent.remove(position);
+        self.positions.remove(&id);
+        self.reindex(position);
+        let sorted = self.position(&self.records[&id]);
+
+        self.order.remove(sorted);
+        self.records.remove(&id);
+
+        Position(sorted)
+    }
+
+    /// Record kept in the index has been updated
+    ///
+    /// Returns [`StoreViewMsg::Update`] if record stays at the same place or [`StoreViewMsg::Move`] otherwise
+    pub(crate) fn update(&mut self, record: R) -> StoreViewMsg<R> {
+        let id = record.get_id();
+        let from = self.position(&self.records[&id]);
+        self.order.remove(from);
+
+        // record keeps it's arrival so it doesn't jump over equal records
+        let entry = Entry{
+            record,
+            arrival: self.records[&id].arrival,
+        };
+        let to = self.insert_position(&entry);
+        self.order.insert(to, id);
+        self.records.insert(id, entry);
+
+        if from == to {
+            StoreViewMsg::Update(id)
+        }
+        else {
+            StoreViewMsg::Move{
+                from: Position(from),
+                to: Position(to),
+            }
+        }
+    }
+
+    /// Record has been moved in the parent store
+    ///
+    /// Sorted order is not affected by the parent order
+    pub(crate) fn move_record(&mut self, from: usize, to: usize) {
+        let id = self.parent.remove(from);
+        self.parent.insert(to, id);
+        self.reindex(min(from, to));
+    }
+
+    /// Records in the parent store starting at `from` have been reordered
+    pub(crate) fn reorder(&mut self, from: usize, ids: Vec<Id<R>>) {
+        for (idx, id) in ids.into_iter().enumerate() {
+            self.parent[from + idx] = id;
+            self.positions.insert(id, from + idx);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//! Store which keeps its own order of the records independent from the parent store
+mod index;
+
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Identifiable;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+use crate::DataStore;
+use crate::OrderedStore;
+use crate::Position;
+use crate::Sorter;
+use crate::StoreId;
+use crate::StoreViewMsg;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::handlers::translate_batch;
+use crate::math::Range;
+
+use index::SortIndex;
+
+/// Data store showing records of the parent store in its own order
+///
+/// [`OrderedStore::set_order`] of the [`Store`](crate::Store) changes the order for every view of the store.
+/// Sorted store keeps its own order on top of the parent store so two views can show the same records
+/// sorted differently.
+///
+/// Sorted store listens to the parent store and translates positions from the parent store into its own.
+/// It keeps copies of the records so it doesn't need to ask the parent store every time it compares them.
+/// Messages sent to the sorted store are passed to the parent store.
+///
+/// ```text
+/// let by_name = SortedStore::new(tasks.clone(), OrderTasksBy::Name{ascending: true});
+/// let by_date = SortedStore::new(tasks.clone(), OrderTasksBy::Date{ascending: false});
+/// ```
+pub struct SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    id: StoreId<Self>,
+    parent: Parent,
+    index: Rc<RefCell<SortIndex<Parent::Record, OrderBy>>>,
+    connections: Connections<Self, Parent::Record>,
+}
+
+impl<Parent, OrderBy> SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    /// Creates new instance of the sorted store
+    pub fn new(parent: Parent, ordering: OrderBy) -> Self {
+        let id = StoreId::new();
+        let mut index = SortIndex::new(ordering);
+        index.rebuild(parent.get_range(&Range::new(0, parent.len())));
+
+        let index = Rc::new(RefCell::new(index));
+        let connections: Connections<Self, Parent::Record> = Rc::new(RefCell::new(HashMap::new()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let handler_parent = parent.clone();
+        let handler_index = index.clone();
+        let handler_connections = connections.clone();
+
+        {
+            let context = glib::MainContext::default();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Parent::Record>| {
+                if let Ok(mut index) = handler_index.try_borrow_mut() {
+                    let replies = translate(&mut index, &handler_parent, msg);
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow sorted store index. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        parent.listen(id.transfer(), sender);
+
+        Self {
+            id,
+            parent,
+            index,
+            connections,
+        }
+    }
+}
+
+/// Translates message from the parent store into messages for listeners of the sorted store
+fn translate<Parent, OrderBy>(index: &mut SortIndex<Parent::Record, OrderBy>, parent: &Parent, msg: StoreViewMsg<Parent::Record>) -> Vec<StoreViewMsg<Parent::Record>>
+where
+    Parent: DataStore,
+    OrderBy: Sorter<Parent::Record>,
+{
+    let replies = match msg {
+        StoreViewMsg::NewAt(Position(position)) => new_at(index, parent, position),
+        StoreViewMsg::Remove(Position(position)) => {
+            if position < index.parent_len() {
+                Some(vec![StoreViewMsg::Remove(index.remove(position))])
+            }
+            else {
+                None
+            }
+        },
+        StoreViewMsg::Update(id) => update(index, parent, id),
+        StoreViewMsg::Move{from: Position(from), to: Position(to)} => {
+            if from < index.parent_len() && to < index.parent_len() {
+                index.move_record(from, to);
+                Some(vec![])
+            }
+            else {
+                None
+            }
+        },
+        StoreViewMsg::Reorder{from: Position(from), to: Position(to)} => reorder(index, parent, from, to),
+        StoreViewMsg::Reload => {
+            index.rebuild(parent.get_range(&Range::new(0, parent.len())));
+            Some(vec![StoreViewMsg::Reload])
+        },
+        msg @ (StoreViewMsg::NewRange{..} | StoreViewMsg::RemoveRange{..}) => {
+            Some(translate_batch(msg.expand(), |msg| translate(index, parent, msg)))
+        },
+        StoreViewMsg::Batch(messages) => Some(translate_batch(messages, |msg| translate(index, parent, msg))),
+        StoreViewMsg::Error(err) => Some(vec![StoreViewMsg::Error(err)]),
+        StoreViewMsg::IdChanged{old, new} => {
+            match parent.get(&new) {
+                Some(record) if index.contains(&old) => {
+                    index.change_id(&old, record);
+                    Some(vec![StoreViewMsg::IdChanged{old, new}])
+                },
+                // record was already added with the new id or it's already gone from the parent
+                _ => Some(vec![]),
+            }
+        },
+    };
+
+    replies.unwrap_or_else(|| {
+        log::warn!("Sorted store is out of sync with the parent store. Reloading");
+        index.rebuild(parent.get_range(&Range::new(0, parent.len())));
+        vec![StoreViewMsg::Reload]
+    })
+}
+
+fn new_at<Parent, OrderBy>(index: &mut SortIndex<Parent::Record, OrderBy>, parent: &Parent, position: usize) -> Option<Vec<StoreViewMsg<Parent::Record>>>
+where
+    Parent: DataStore,
+    OrderBy: Sorter<Parent::Record>,
+{
+    if position > index.parent_len() {
+        return None
+    }
+
+    let record = parent.get_range(&Range::new(position, position+1)).pop()?;
+    if index.contains(&record.get_id()) {
+        // parent store is already ahead of us, position doesn't point to the new record
+        return None
+    }
+
+    Some(vec![StoreViewMsg::NewAt(index.insert(position, record))])
+}
+
+fn update<Parent, OrderBy>(index: &mut SortIndex<Parent::Record, OrderBy>, parent: &Parent, id: Id<Parent::Record>) -> Option<Vec<StoreViewMsg<Parent::Record>>>
+where
+    Parent: DataStore,
+    OrderBy: Sorter<Parent::Record>,
+{
+    if !index.contains(&id) {
+        return None
+    }
+
+    match parent.get(&id) {
+        Some(record) => Some(vec![index.update(record)]),
+        // record is already gone from the parent, remove message will follow
+        None => Some(vec![]),
+    }
+}
+
+fn reorder<Parent, OrderBy>(index: &mut SortIndex<Parent::Record, OrderBy>, parent: &Parent, from: usize, to: usize) -> Option<Vec<StoreViewMsg<Parent::Record>>>
+where
+    Parent: DataStore,
+    OrderBy: Sorter<Parent::Record>,
+{
+    if to > index.parent_len() {
+        return None
+    }
+
+    let ids: Vec<Id<Parent::Record>> = parent.get_range(&Range::new(from, to))
+        .iter()
+        .map(|record| record.get_id())
+        .collect();
+
+    if ids.len() != to - from || ids.iter().any(|id| !index.contains(id)) {
+        return None
+    }
+
+    // order of the parent doesn't affect the sorted store
+    index.reorder(from, ids);
+    Some(vec![])
+}
+
+impl<Parent, OrderBy> Identifiable<Self, <Parent::Allocator as TemporaryIdAllocator>::Type> for SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    type Id = StoreId<Self>;
+
+    fn get_id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl<Parent, OrderBy> DataStore for SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    type Record = Parent::Record;
+    type Allocator = Parent::Allocator;
+    type Messages = Parent::Messages;
+
+    fn len(&self) -> usize {
+        self.index.borrow().len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        self.index.borrow().get(id)
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        self.index.borrow().get_range(range)
+    }
+
+    fn listen(&self, id: StoreId<Self>, sender: Sender<StoreViewMsg<Self::Record>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    fn unlisten(&self, id: StoreId<Self>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn sender(&self) -> Sender<Self::Messages> {
+        self.parent.sender()
+    }
+
+    fn send(&self, msg: Self::Messages) {
+        self.parent.send(msg)
+    }
+}
+
+impl<Parent, OrderBy> OrderedStore<OrderBy> for SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    fn set_order(&self, order: OrderBy) {
+        let changed = self.index.borrow_mut().set_order(order);
+        if !changed.is_empty() {
+            fire_handlers(&self.connections, &[StoreViewMsg::Reorder{
+                from: Position(*changed.start()),
+                to: Position(*changed.end()),
+            }]);
+        }
+    }
+}
+
+/// Implements shallow clone. Clone shares the order and listeners with the original
+impl<Parent, OrderBy> Clone for SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            parent: self.parent.clone(),
+            index: self.index.clone(),
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+/// Formats [`SortedStore`] for debug format `{:?}`
+impl<Parent, OrderBy> Debug for SortedStore<Parent, OrderBy>
+where
+    Parent: 'static + DataStore + Clone,
+    OrderBy: 'static + Sorter<Parent::Record>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("SortedStore")
+            .field("id", &self.id)
+            .field("index", &self.index)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
@@ -14,13 +14,24 @@ use relm4::Sender;
 use record::Identifiable;
 use record::TemporaryIdAllocator;
 
+use crate::BackendError;
 use crate::DataStore;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
 use crate::OrderedBackend;
 use crate::OrderedStore;
 use crate::Replies;
 use crate::StoreId;
 use crate::StoreMsg;
 use crate::StoreViewMsg;
+use crate::TreeBackend;
+use crate::TreeDataStore;
+use crate::UndoableBackend;
+use crate::UndoableStore;
+use crate::math::Range;
+
+/// Message waiting in the store inbox together with the receiver of the outcome if there is one
+type Envelope<R> = (StoreMsg<R>, Option<Sender<Result<(), BackendError>>>);
 
 /// Generic implementation of the DataStore
 #[derive(Debug)]
@@ -31,9 +42,40 @@ where
 {
     id: StoreId<Self>,
     backend: Rc<RefCell<Backend>>,
-    #[allow(clippy::type_complexity)]
-    connections: Rc<RefCell<HashMap<StoreId<Self>, Sender<StoreViewMsg<Backend::Record>>>>>,
+    connections: Connections<Self, Backend::Record>,
     sender: Sender<StoreMsg<Backend::Record>>,
+    inbox: Sender<Envelope<Backend::Record>>,
+}
+
+/// Applies message to the backend and notifies the listeners about the outcome
+fn handle<Store, Backend>(backend: &RefCell<Backend>, connections: &Connections<Store, Backend::Record>, msg: StoreMsg<Backend::Record>) -> Result<(), BackendError>
+where
+    Store: DataStore,
+    Backend: crate::Backend,
+{
+    if let Ok(mut backend) = backend.try_borrow_mut() {
+        let transaction = matches!(msg, StoreMsg::Transaction(_));
+        match backend.inbox(msg) {
+            Ok(Replies{ replies }) => {
+                if !transaction {
+                    fire_handlers(connections, &replies);
+                }
+                else if !replies.is_empty() {
+                    fire_handlers(connections, &[StoreViewMsg::Batch(replies)]);
+                }
+                Ok(())
+            },
+            Err(err) => {
+                log::warn!("Backend failed to handle the message: {}", err);
+                fire_handlers(connections, &[StoreViewMsg::Error(err.clone())]);
+                Err(err)
+            }
+        }
+    }
+    else {
+        log::warn!("Can't borrow backend. Remember to release the leases");
+        Err(BackendError::Rejected(String::from("backend is borrowed")))
+    }
 }
 
 impl<Backend, StoreIdAllocator> Store<Backend, StoreIdAllocator> 
@@ -44,41 +86,32 @@ where
     /// Creates new instance of the Store
     pub fn new(backend: Backend) -> Self {
         let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let (inbox, inbox_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
 
         let id = StoreId::new();
         let shared_backed = Rc::new(RefCell::new(backend));
-        let handler_backend = shared_backed.clone();
-        #[allow(clippy::type_complexity)]
-        let connections: Rc<RefCell<HashMap<StoreId<Self>, Sender<StoreViewMsg<Backend::Record>>>>> = Rc::new(RefCell::new(HashMap::new()));
-        let handler_connections = connections.clone();
+        let connections: Connections<Self, Backend::Record> = Rc::new(RefCell::new(HashMap::new()));
 
         {
             let context = glib::MainContext::default();
-            receiver.attach(Some(&context), move |msg:StoreMsg<Backend::Record>| {
-                if let Ok(mut backend) = handler_backend.try_borrow_mut() {
-                    let replies = backend.inbox(msg);
-                    if let Ok(mut connections) = handler_connections.try_borrow_mut() { 
-                        let mut to_remove = Vec::<StoreId<Store<Backend, StoreIdAllocator>>>::new();
-                        for (sid,c) in connections.iter() {
-                            for msg in &replies.replies {
-                                if let Err(..) = c.send(msg.clone()) {
-                                    // in case of broken channel (closed by other side), mark it for removal
-                                    to_remove.push(*sid);
-                                    break;
-                                }
-                            }
-                        }
-                        
-                        for sid in to_remove {
-                            connections.remove(&sid);
-                        }
-                    }
-                    else {
-                        log::warn!("Can't borrow connections. Remember to release leases");
+
+            let handler_backend = shared_backed.clone();
+            let handler_connections = connections.clone();
+            inbox_receiver.attach(Some(&context), move |(msg, ack): Envelope<Backend::Record>| {
+                let result = handle(&handler_backend, &handler_connections, msg);
+                if let Some(ack) = ack {
+                    if ack.send(result).is_err() {
+                        log::warn!("Acknowledgement receiver is gone");
                     }
                 }
-                else {
-                    log::warn!("Can't borrow backend. Remember to release the leases");
+                glib::Continue(true)
+            });
+
+            // messages sent via `DataStore::sender` end up in the same inbox
+            let handler_inbox = inbox.clone();
+            receiver.attach(Some(&context), move |msg:StoreMsg<Backend::Record>| {
+                if handler_inbox.send((msg, None)).is_err() {
+                    log::warn!("Store inbox is gone");
                 }
                 glib::Continue(true)
             });
@@ -88,34 +121,41 @@ where
             id,
             backend: shared_backed,
             sender,
+            inbox,
             connections,
         }
     }
 
+    /// Sends message to the store and reports the outcome to `ack`
+    /// 
+    /// After the message is handled `ack` receives `Ok(())` or the error returned by the backend. Listeners of the
+    /// store are notified the same way as for [`DataStore::send`], including the [`StoreViewMsg::Error`].
+    /// 
+    /// Messages sent this way share the inbox with the ones sent via [`DataStore::send`], so these two are handled
+    /// in the order they were sent. Messages sent via [`DataStore::sender`] reach the inbox after an extra hop, so
+    /// they can be handled after the messages sent later this way. Returns [`BackendError::Rejected`] if the message
+    /// can't be put into the inbox.
+    pub fn send_with_ack(&self, msg: StoreMsg<Backend::Record>, ack: Sender<Result<(), BackendError>>) -> Result<(), BackendError> {
+        self.inbox.send((msg, Some(ack)))
+            .map_err(|_| BackendError::Rejected(String::from("store inbox is closed")))
+    }
+
     /// Allows to send message to all views attached to the store
     /// 
     /// Store is unable to check if your message would break the state of the store views. When you use this method
     /// please double check if you are not breaking something.
     pub fn fire_handlers(&self, messages: &[StoreViewMsg<Backend::Record>]) {
-        if let Ok(mut connections) = self.connections.try_borrow_mut() { 
-            let mut to_remove = Vec::<StoreId<Store<Backend, StoreIdAllocator>>>::new();
-            for (sid,c) in connections.iter() {
-                for msg in messages {
-                    if let Err(..) = c.send(msg.clone()) {
-                        // in case of broken channel (closed by other side), mark it for removal
-                        to_remove.push(*sid);
-                        break;
-                    }
-                }
-            }
-            
-            for sid in to_remove {
-                connections.remove(&sid);
+        fire_handlers(&self.connections, messages);
+    }
+
+    fn fire_result(&self, result: Result<Replies<Backend::Record>, BackendError>) {
+        match result {
+            Ok(Replies{ replies }) => self.fire_handlers(&replies),
+            Err(err) => {
+                log::warn!("Backend failed to handle the message: {}", err);
+                self.fire_handlers(&[StoreViewMsg::Error(err)]);
             }
         }
-        else {
-            log::warn!("Can't borrow connections. Remember to release leases");
-        }
     }
 
     /// Returns shared reference to backend
@@ -180,8 +220,10 @@ where
     }
 
     fn send(&self, msg: crate::StoreMsg<Self::Record>) {
-        // this shouldn't fail since receiver should still be there
-        self.sender.send(msg).unwrap();
+        if self.inbox.send((msg, None)).is_err() {
+            log::warn!("Store inbox is gone");
+            fire_handlers(&self.connections, &[StoreViewMsg::Error(BackendError::Rejected(String::from("store inbox is closed")))]);
+        }
     }
 }
 
@@ -197,6 +239,50 @@ where
     }
 }
 
+impl<Backend, StoreIdAllocator> TreeDataStore for Store<Backend, StoreIdAllocator> 
+where
+    Backend: 'static + TreeBackend,
+    StoreIdAllocator: 'static + TemporaryIdAllocator,
+{
+    fn children_len(&self, node: Option<&record::Id<Self::Record>>) -> usize {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        be.borrow().children_len(node)
+    }
+
+    fn children(&self, node: Option<&record::Id<Self::Record>>, range: &Range) -> Vec<Self::Record> {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        be.borrow().children(node, range)
+    }
+}
+
+impl<Backend, StoreIdAllocator> UndoableStore for Store<Backend, StoreIdAllocator> 
+where
+    Backend: 'static + UndoableBackend,
+    StoreIdAllocator: 'static + TemporaryIdAllocator,
+{
+    fn undo(&self) {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        let result = be.borrow_mut().undo();
+        self.fire_result(result);
+    }
+
+    fn redo(&self) {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        let result = be.borrow_mut().redo();
+        self.fire_result(result);
+    }
+
+    fn can_undo(&self) -> bool {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        be.borrow().can_undo()
+    }
+
+    fn can_redo(&self) -> bool {
+        let be: &RefCell<Backend> = self.backend.borrow();
+        be.borrow().can_redo()
+    }
+}
+
 impl<Backend, StoreIdAllocator> Clone for Store<Backend, StoreIdAllocator> 
 where
     Backend: crate::Backend,
@@ -210,6 +296,7 @@ where
             backend: self.backend.clone(),
             connections: self.connections.clone(),
             sender: self.sender.clone(),
+            inbox: self.inbox.clone(),
         }
     }
 }
\ No newline at end of file
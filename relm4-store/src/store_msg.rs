@@ -3,13 +3,37 @@ use std::fmt::Debug;
 use record::Id;
 use record::Record;
 
+use crate::Position;
+
 /// Messages sent to/between stores
 #[derive(Clone,Debug)]
 pub enum StoreMsg<T: Record> {
     /// Record was committed to the store
     Commit(T),
     /// Removes record from the store
+    /// 
+    /// Deleting the record which is not in the store is an error. Backend returns
+    /// [`BackendError::NotFound`](crate::BackendError::NotFound) and listeners receive
+    /// [`StoreViewMsg::Error`](crate::StoreViewMsg::Error), so for example second click on the delete button of the
+    /// same record doesn't go unnoticed.
     Delete(Id<T>),
+    /// Brings back the record which was removed from the store
+    /// 
+    /// Record should be inserted at the `position` if the store keeps the order of insertion. Stores which decide
+    /// about the order on their own (for example sorted ones) can ignore the position. If record is already in the
+    /// store it's handled like [`StoreMsg::Commit`].
+    Restore{
+        /// Record to bring back
+        record: T,
+        /// Position at which record was before it was removed
+        position: Position,
+    },
     /// Store should be reloaded fully, dump all data, indexes, etc... and reload the data
     Reload,
+    /// Applies all of the messages as a single unit
+    /// 
+    /// Backend applies messages in the given order, all or nothing. If any of the messages is rejected, changes made by
+    /// the previous ones are reverted and listeners are not notified. Listeners of the store receive all changes made by the transaction
+    /// as a single [`StoreViewMsg::Batch`](crate::StoreViewMsg::Batch), so views redraw once instead of once per record.
+    Transaction(Vec<StoreMsg<T>>),
 }
@@ -182,7 +182,12 @@ where
 
         {
             let context = glib::MainContext::default();
-            redraw_receiver.attach(Some(&context), move |_| {
+            redraw_receiver.attach(Some(&context), move |msg| {
+                if let RedrawMessages::Error(err) = msg {
+                    Configuration::on_error(&err, redraw_handler_sender.clone());
+                    return glib::Continue(true)
+                }
+
                 log::trace!("Received redraw message!");
                 if let Ok(view_model) = redraw_handler_view_model.try_borrow() {
                     if let Ok(mut container) = redraw_handler_container.try_borrow_mut() {
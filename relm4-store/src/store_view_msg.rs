@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use record::Id;
 use record::Record;
 
+use super::BackendError;
 use super::Position;
 
 /// Messages sent to/between stores
@@ -10,6 +11,15 @@ use super::Position;
 pub enum StoreViewMsg<T: Record> {
     /// New record was added at the given position
     NewAt(Position),
+    /// New records were added at the positions in the range
+    /// 
+    /// It's equivalent of `NewAt(from)`, `NewAt(from+1)`, ..., `NewAt(to-1)`
+    NewRange{
+        /// position of the first new record
+        from: Position,
+        /// end of the new records (exclusive)
+        to: Position,
+    },
     /// One record in store has been moved
     Move{
         /// Position at which record was
@@ -18,16 +28,179 @@ pub enum StoreViewMsg<T: Record> {
         to: Position,
     },
     /// There is big reorder and in the given region of the store
+    /// 
+    /// Set of records in the store doesn't change, records in the region can be in any order. Records outside of the
+    /// region keep their positions.
     Reorder{
         /// beginning of the region reorder
         from: Position,
-        /// end of the region of reorder
+        /// end of the region of reorder (exclusive)
         to: Position,
     },
     /// Record was removed from the store at given position
     Remove(Position),
+    /// Records were removed from the store at the positions in the range
+    /// 
+    /// Positions are the ones before removal. It's equivalent of sending `Remove(from)` `to-from` times
+    RemoveRange{
+        /// position of the first removed record
+        from: Position,
+        /// end of the removed records (exclusive)
+        to: Position,
+    },
     /// Record was committed to the store
     Update(Id<T>),
+    /// Record got a new id, usually when the store replaced the [`Id::New`] with the [`Id::Permanent`]
+    /// 
+    /// Position and content of the record don't change. Everyone who keeps track of the record by the `old` id should
+    /// use the `new` one from now on.
+    IdChanged{
+        /// Id under which record was known so far
+        old: Id<T>,
+        /// Id of the record from now on
+        new: Id<T>,
+    },
     /// Store should be reloaded fully, dump all data, indexes, etc... and reload the data
     Reload,
+    /// Changes which should be applied together, in the given order
+    /// 
+    /// Sent for the [`StoreMsg::Transaction`](crate::StoreMsg::Transaction). Batch is never empty and never contains
+    /// another batch.
+    Batch(Vec<StoreViewMsg<T>>),
+    /// Store was unable to apply the message
+    /// 
+    /// Data in the store didn't change, so views don't need to do anything. It's sent so the listeners can tell the user
+    /// what went wrong.
+    Error(BackendError),
+}
+
+impl<T: Record> StoreViewMsg<T> {
+    /// Replaces ranged messages by the equivalent sequence of single record messages
+    /// 
+    /// Other messages are returned as they are. It's useful if you can't handle many records at once
+    pub fn expand(self) -> Vec<StoreViewMsg<T>> {
+        match self {
+            StoreViewMsg::NewRange{from, to} => {
+                (from.0..to.0).map(|position| StoreViewMsg::NewAt(Position(position))).collect()
+            },
+            StoreViewMsg::RemoveRange{from, to} => {
+                (from.0..to.0).map(|_| StoreViewMsg::Remove(from)).collect()
+            },
+            msg => vec![msg],
+        }
+    }
+
+    /// Merges consecutive inserts and removes of the neighbouring records into the ranged messages
+    /// 
+    /// Result describes the same change as the `messages`. Removal of the records which were just inserted cancels
+    /// the insert. [StoreViewMsg::Update] and [StoreViewMsg::IdChanged] don't change positions of the records, so
+    /// they are moved after the range they are interrupting.
+    pub fn merge_ranges(messages: Vec<StoreViewMsg<T>>) -> Vec<StoreViewMsg<T>> {
+        let mut merged = Vec::with_capacity(messages.len());
+        let mut pending = PendingRange::None;
+        let mut deferred = Vec::new();
+
+        for msg in messages {
+            let (kind, from, to) = match msg {
+                StoreViewMsg::NewAt(Position(at)) => (PendingKind::Insert, at, at + 1),
+                StoreViewMsg::NewRange{from, to} => (PendingKind::Insert, from.0, to.0),
+                StoreViewMsg::Remove(Position(at)) => (PendingKind::Remove, at, at + 1),
+                StoreViewMsg::RemoveRange{from, to} => (PendingKind::Remove, from.0, to.0),
+                msg @ (StoreViewMsg::Update(_) | StoreViewMsg::IdChanged{..}) if !matches!(pending, PendingRange::None) => {
+                    deferred.push(msg);
+                    continue;
+                },
+                msg => {
+                    pending.flush(&mut merged);
+                    merged.append(&mut deferred);
+                    merged.push(msg);
+                    continue;
+                },
+            };
+
+            if from == to {
+                // empty range doesn't change anything
+                continue;
+            }
+
+            if !pending.extend(kind, from, to) {
+                pending.flush(&mut merged);
+                merged.append(&mut deferred);
+                pending = PendingRange::new(kind, from, to);
+            }
+        }
+
+        pending.flush(&mut merged);
+        merged.append(&mut deferred);
+        merged
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PendingKind {
+    Insert,
+    Remove,
+}
+
+/// Range of inserted or removed records collected by [StoreViewMsg::merge_ranges]
+enum PendingRange {
+    None,
+    /// New records are at `[from, to)`
+    Insert{
+        from: usize,
+        to: usize,
+    },
+    /// Records at `[from, to)` before removal were removed
+    Remove{
+        from: usize,
+        to: usize,
+    },
+}
+
+impl PendingRange {
+    fn new(kind: PendingKind, from: usize, to: usize) -> Self {
+        match kind {
+            PendingKind::Insert => PendingRange::Insert{from, to},
+            PendingKind::Remove => PendingRange::Remove{from, to},
+        }
+    }
+
+    /// Tries to add the change to the range, returns `false` if the result wouldn't be a single range
+    fn extend(&mut self, kind: PendingKind, at: usize, end: usize) -> bool {
+        let len = end - at;
+        match (self, kind) {
+            // new records inserted anywhere inside of the new records or right after them
+            (PendingRange::Insert{from, to}, PendingKind::Insert) if *from <= at && at <= *to => {
+                *to += len;
+                true
+            },
+            // new records were removed before anyone had a chance to see them
+            (PendingRange::Insert{from, to}, PendingKind::Remove) if *from <= at && end <= *to => {
+                *to -= len;
+                true
+            },
+            // records right after removed ones
+            (PendingRange::Remove{from, to}, PendingKind::Remove) if at == *from => {
+                *to += len;
+                true
+            },
+            // records right before removed ones
+            (PendingRange::Remove{from, ..}, PendingKind::Remove) if end == *from => {
+                *from = at;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn flush<T: Record>(&mut self, merged: &mut Vec<StoreViewMsg<T>>) {
+        match std::mem::replace(self, PendingRange::None) {
+            PendingRange::None => {},
+            PendingRange::Insert{from, to} if from == to => {},
+            PendingRange::Insert{from, to} if to - from == 1 => merged.push(StoreViewMsg::NewAt(Position(from))),
+            PendingRange::Insert{from, to} => merged.push(StoreViewMsg::NewRange{from: Position(from), to: Position(to)}),
+            PendingRange::Remove{from, to} if to - from == 1 => merged.push(StoreViewMsg::Remove(Position(from))),
+            PendingRange::Remove{from, to} => merged.push(StoreViewMsg::RemoveRange{from: Position(from), to: Position(to)}),
+        }
+    }
 }
@@ -0,0 +1,477 @@
+//! Store which flattens the tree into the list of visible nodes
+mod tree_node;
+
+use reexport::glib;
+use reexport::log;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use record::Id;
+use record::Identifiable;
+use record::Record;
+use record::TemporaryIdAllocator;
+use record::TreeRecord;
+
+use crate::DataStore;
+use crate::Position;
+use crate::StoreId;
+use crate::StoreViewMsg;
+use crate::TreeDataStore;
+use crate::filtered_store::index::FilterIndex;
+use crate::handlers::Connections;
+use crate::handlers::fire_handlers;
+use crate::handlers::translate_batch;
+use crate::math::Range;
+
+pub use tree_node::TreeNode;
+
+type TreeMsg<R> = StoreViewMsg<TreeNode<R>>;
+
+/// Data store showing the tree kept by the parent store as a list
+///
+/// Parent store keeps the records in the depth first pre-order (see [`TreeDataStore`]). Flat tree store
+/// shows only the nodes for which all of the ancestors are expanded, so the view on top of it sees a flat list
+/// and all of the [`WindowBehavior`](crate::window::WindowBehavior) and pagination machinery works as usual.
+///
+/// At the beginning all nodes are collapsed so only roots are visible. When node is expanded or collapsed
+/// listeners receive messages about the rows which appeared or disappeared, followed by the update of the node
+/// itself.
+///
+/// Records of the flat tree store are [`TreeNode`]s which carry the depth of the node and information about it's
+/// children, so your [`StoreViewPrototype`](crate::StoreViewPrototype) can indent the rows and show the expanders.
+pub struct FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    id: StoreId<Self>,
+    parent: Parent,
+    state: Rc<RefCell<FlatTreeState<Parent::Record>>>,
+    connections: Connections<Self, TreeNode<Parent::Record>>,
+}
+
+impl<Parent> FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    /// Creates new instance of the flat tree store
+    pub fn new(parent: Parent) -> Self {
+        let id = StoreId::new();
+        let mut state = FlatTreeState{
+            index: FilterIndex::new(),
+            parents: HashMap::new(),
+            expanded: HashSet::new(),
+        };
+        state.rebuild(&parent);
+
+        let state = Rc::new(RefCell::new(state));
+        let connections: Connections<Self, TreeNode<Parent::Record>> = Rc::new(RefCell::new(HashMap::new()));
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let handler_parent = parent.clone();
+        let handler_state = state.clone();
+        let handler_connections = connections.clone();
+
+        {
+            let context = glib::MainContext::default();
+            receiver.attach(Some(&context), move |msg: StoreViewMsg<Parent::Record>| {
+                if let Ok(mut state) = handler_state.try_borrow_mut() {
+                    let replies = state.translate(&handler_parent, msg);
+                    fire_handlers(&handler_connections, &replies);
+                }
+                else {
+                    log::warn!("Can't borrow flat tree store state. Remember to release the leases");
+                }
+                glib::Continue(true)
+            });
+        }
+
+        parent.listen(id.transfer(), sender);
+
+        Self {
+            id,
+            parent,
+            state,
+            connections,
+        }
+    }
+
+    /// Shows children of the node
+    pub fn expand(&self, id: &Id<Parent::Record>) {
+        self.set_expanded(id, true);
+    }
+
+    /// Hides all descendants of the node
+    ///
+    /// Expanded state of the descendants is remembered, so expanding the node again shows the subtree the
+    /// way it was before
+    pub fn collapse(&self, id: &Id<Parent::Record>) {
+        self.set_expanded(id, false);
+    }
+
+    /// Returns `true` if children of the node are visible
+    pub fn is_expanded(&self, id: &Id<Parent::Record>) -> bool {
+        self.state.borrow().expanded.contains(id)
+    }
+
+    fn set_expanded(&self, id: &Id<Parent::Record>, expanded: bool) {
+        let replies = self.state.borrow_mut().set_expanded(*id, expanded);
+        fire_handlers(&self.connections, &replies);
+    }
+
+    fn to_node(&self, record: Parent::Record) -> TreeNode<Parent::Record> {
+        let id = record.get_id();
+        let (depth, expanded) = {
+            let state = self.state.borrow();
+            (state.ancestors(&id).len(), state.expanded.contains(&id))
+        };
+
+        TreeNode{
+            children: self.parent.children_len(Some(&id)),
+            record,
+            depth,
+            expanded,
+        }
+    }
+}
+
+/// Part of the flat tree store shared with the handler listening to the parent store
+#[derive(Debug)]
+struct FlatTreeState<R: Record> {
+    /// Records of the parent store, record is matching if it's visible
+    index: FilterIndex<R>,
+    /// Parent of each record known to the store
+    parents: HashMap<Id<R>, Option<Id<R>>>,
+    expanded: HashSet<Id<R>>,
+}
+
+impl<R> FlatTreeState<R>
+where
+    R: TreeRecord + Debug + Clone + 'static,
+{
+    /// Returns ancestors of the record starting from it's parent
+    fn ancestors(&self, id: &Id<R>) -> Vec<Id<R>> {
+        let mut result = Vec::new();
+        let mut current = self.parents.get(id).copied().flatten();
+
+        while let Some(parent) = current {
+            if result.len() >= self.parents.len() {
+                log::warn!("Cycle detected in the tree at {:?}", id);
+                break;
+            }
+            result.push(parent);
+            current = self.parents.get(&parent).copied().flatten();
+        }
+
+        result
+    }
+
+    /// Node is visible if all of it's ancestors are expanded
+    fn is_visible(&self, id: &Id<R>) -> bool {
+        self.parents.contains_key(id) &&
+            self.ancestors(id).iter().all(|ancestor| self.expanded.contains(ancestor))
+    }
+
+    fn rebuild<Parent: DataStore<Record=R>>(&mut self, parent: &Parent) {
+        let records = parent.get_range(&Range::new(0, parent.len()));
+        self.parents = records.iter()
+            .map(|record| (record.get_id(), record.get_parent()))
+            .collect();
+
+        let parents = &self.parents;
+        self.expanded.retain(|id| parents.contains_key(id));
+
+        let visible: Vec<(Id<R>, bool)> = records.iter()
+            .map(|record| {
+                let id = record.get_id();
+                (id, self.is_visible(&id))
+            })
+            .collect();
+        self.index.rebuild(visible.into_iter());
+    }
+
+    /// Translates message from the parent store into messages for listeners of the flat tree store
+    fn translate<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, msg: StoreViewMsg<R>) -> Vec<TreeMsg<R>> {
+        let replies = match msg {
+            StoreViewMsg::NewAt(Position(position)) => self.new_at(parent, position),
+            StoreViewMsg::Remove(Position(position)) => self.remove(position),
+            StoreViewMsg::Update(id) => self.update(parent, id),
+            // moving records around in the pre-order changes the shape of the tree, it's simpler to start over
+            StoreViewMsg::Move{..} | StoreViewMsg::Reorder{..} | StoreViewMsg::Reload => {
+                self.rebuild(parent);
+                Some(vec![StoreViewMsg::Reload])
+            },
+            msg @ (StoreViewMsg::NewRange{..} | StoreViewMsg::RemoveRange{..}) => {
+                Some(translate_batch(msg.expand(), |msg| self.translate(parent, msg)))
+            },
+            StoreViewMsg::Batch(messages) => Some(translate_batch(messages, |msg| self.translate(parent, msg))),
+            StoreViewMsg::Error(err) => Some(vec![StoreViewMsg::Error(err)]),
+            StoreViewMsg::IdChanged{old, new} => Some(self.change_id(old, new)),
+        };
+
+        replies.unwrap_or_else(|| {
+            log::warn!("Flat tree store is out of sync with the parent store. Reloading");
+            self.rebuild(parent);
+            vec![StoreViewMsg::Reload]
+        })
+    }
+
+    fn new_at<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, position: usize) -> Option<Vec<TreeMsg<R>>> {
+        if position > self.index.parent_len() {
+            return None
+        }
+
+        let record = parent.get_range(&Range::new(position, position+1)).pop()?;
+        let id = record.get_id();
+        if self.parents.contains_key(&id) {
+            // parent store is already ahead of us, position doesn't point to the new record
+            return None
+        }
+
+        let parent_id = record.get_parent();
+        self.parents.insert(id, parent_id);
+        let visible = self.is_visible(&id);
+
+        let mut replies: Vec<TreeMsg<R>> = self.index.insert(position, id, visible)
+            .map(StoreViewMsg::NewAt)
+            .into_iter()
+            .collect();
+        replies.extend(self.children_changed(parent_id));
+
+        Some(replies)
+    }
+
+    fn remove(&mut self, position: usize) -> Option<Vec<TreeMsg<R>>> {
+        let id = self.index.parent_id(position)?;
+        let parent_id = self.parents.remove(&id).flatten();
+        self.expanded.remove(&id);
+
+        let mut replies: Vec<TreeMsg<R>> = self.index.remove(position)
+            .map(StoreViewMsg::Remove)
+            .into_iter()
+            .collect();
+        replies.extend(self.children_changed(parent_id));
+
+        Some(replies)
+    }
+
+    fn update<Parent: DataStore<Record=R>>(&mut self, parent: &Parent, id: Id<R>) -> Option<Vec<TreeMsg<R>>> {
+        let position = self.index.parent_position(&id)?;
+
+        match parent.get(&id) {
+            Some(record) => {
+                if self.parents.get(&id) != Some(&record.get_parent()) {
+                    // backend is expected to move the subtree with remove and insert messages
+                    return None
+                }
+
+                let visible = self.is_visible(&id);
+                Some(self.index.update(position, id, visible).map(transfer).into_iter().collect())
+            },
+            // record is already gone from the parent, remove message will follow
+            None => Some(vec![]),
+        }
+    }
+
+    /// Replaces `old` id of the node with the `new` one, keeping the expanded state
+    fn change_id(&mut self, old: Id<R>, new: Id<R>) -> Vec<TreeMsg<R>> {
+        if !self.index.change_id(&old, new) {
+            // record was already added with the new id
+            return vec![]
+        }
+
+        if let Some(parent) = self.parents.remove(&old) {
+            self.parents.insert(new, parent);
+        }
+        for parent in self.parents.values_mut().flatten().filter(|parent| **parent == old) {
+            *parent = new;
+        }
+        if self.expanded.remove(&old) {
+            self.expanded.insert(new);
+        }
+
+        vec![StoreViewMsg::IdChanged{old: old.transfer(), new: new.transfer()}]
+    }
+
+    /// Returns update of the node if it's visible, so the view can refresh the expander
+    fn children_changed(&self, node: Option<Id<R>>) -> Option<TreeMsg<R>> {
+        node.filter(|node| self.is_visible(node))
+            .map(|node| StoreViewMsg::Update(node.transfer()))
+    }
+
+    /// Shows or hides descendants of the node
+    fn set_expanded(&mut self, id: Id<R>, expanded: bool) -> Vec<TreeMsg<R>> {
+        if !self.parents.contains_key(&id) || self.expanded.contains(&id) == expanded {
+            return vec![]
+        }
+
+        if expanded {
+            self.expanded.insert(id);
+        }
+        else {
+            self.expanded.remove(&id);
+        }
+
+        if !self.is_visible(&id) {
+            // node itself is hidden so nothing changes in the view
+            return vec![]
+        }
+
+        let mut replies = Vec::new();
+        if let Some(position) = self.index.parent_position(&id) {
+            // descendants of the node are right after it in the pre-order and parent always comes before
+            // the child, so single pass finds all of them together with their visibility
+            let mut shows_children = HashMap::new();
+            shows_children.insert(id, expanded);
+            let mut descendants = Vec::new();
+            while let Some(other) = self.index.parent_id(position + 1 + descendants.len()) {
+                let visible = match self.parents.get(&other).copied().flatten().and_then(|parent| shows_children.get(&parent)) {
+                    Some(visible) => *visible,
+                    None => break,
+                };
+                shows_children.insert(other, visible && self.expanded.contains(&other));
+                descendants.push((other, visible));
+            }
+
+            // ids stay where they are, only visibility of the subtree changes
+            let (range, count) = self.index.reorder(position + 1, descendants);
+            let start = *range.start();
+            if !range.is_empty() {
+                replies.push(StoreViewMsg::RemoveRange{
+                    from: Position(start),
+                    to: Position(*range.end()),
+                });
+            }
+            if count > 0 {
+                replies.push(StoreViewMsg::NewRange{
+                    from: Position(start),
+                    to: Position(start + count),
+                });
+            }
+        }
+
+        replies.push(StoreViewMsg::Update(id.transfer()));
+        StoreViewMsg::merge_ranges(replies)
+    }
+}
+
+/// Translates message about the record into the message about the node
+fn transfer<R: Record>(msg: StoreViewMsg<R>) -> TreeMsg<R> {
+    match msg {
+        StoreViewMsg::NewAt(position) => StoreViewMsg::NewAt(position),
+        StoreViewMsg::NewRange{from, to} => StoreViewMsg::NewRange{from, to},
+        StoreViewMsg::Move{from, to} => StoreViewMsg::Move{from, to},
+        StoreViewMsg::Reorder{from, to} => StoreViewMsg::Reorder{from, to},
+        StoreViewMsg::Remove(position) => StoreViewMsg::Remove(position),
+        StoreViewMsg::RemoveRange{from, to} => StoreViewMsg::RemoveRange{from, to},
+        StoreViewMsg::Update(id) => StoreViewMsg::Update(id.transfer()),
+        StoreViewMsg::Reload => StoreViewMsg::Reload,
+        StoreViewMsg::Batch(messages) => StoreViewMsg::Batch(messages.into_iter().map(transfer).collect()),
+        StoreViewMsg::Error(err) => StoreViewMsg::Error(err),
+        StoreViewMsg::IdChanged{old, new} => StoreViewMsg::IdChanged{old: old.transfer(), new: new.transfer()},
+    }
+}
+
+impl<Parent> Identifiable<Self, <Parent::Allocator as TemporaryIdAllocator>::Type> for FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    type Id = StoreId<Self>;
+
+    fn get_id(&self) -> Self::Id {
+        self.id
+    }
+}
+
+impl<Parent> DataStore for FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    type Record = TreeNode<Parent::Record>;
+    type Allocator = Parent::Allocator;
+    type Messages = Parent::Messages;
+
+    fn len(&self) -> usize {
+        self.state.borrow().index.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, id: &Id<Self::Record>) -> Option<Self::Record> {
+        let id = id.transfer();
+        if !self.state.borrow().is_visible(&id) {
+            return None
+        }
+
+        self.parent.get(&id).map(|record| self.to_node(record))
+    }
+
+    fn get_range(&self, range: &Range) -> Vec<Self::Record> {
+        let ids = self.state.borrow().index.ids(range);
+        ids.iter()
+            .filter_map(|id| self.parent.get(id))
+            .map(|record| self.to_node(record))
+            .collect()
+    }
+
+    fn listen(&self, id: StoreId<Self>, sender: Sender<TreeMsg<Parent::Record>>) {
+        self.connections.borrow_mut().insert(id, sender);
+    }
+
+    fn unlisten(&self, id: StoreId<Self>) {
+        self.connections.borrow_mut().remove(&id);
+    }
+
+    fn sender(&self) -> Sender<Self::Messages> {
+        self.parent.sender()
+    }
+
+    fn send(&self, msg: Self::Messages) {
+        self.parent.send(msg)
+    }
+}
+
+/// Implements shallow clone. Clone shares the expanded nodes and listeners with the original
+impl<Parent> Clone for FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            parent: self.parent.clone(),
+            state: self.state.clone(),
+            connections: self.connections.clone(),
+        }
+    }
+}
+
+/// Formats [`FlatTreeStore`] for debug format `{:?}`
+impl<Parent> Debug for FlatTreeStore<Parent>
+where
+    Parent: 'static + TreeDataStore + Clone,
+    Parent::Record: TreeRecord,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("FlatTreeStore")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
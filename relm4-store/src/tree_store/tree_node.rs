@@ -0,0 +1,31 @@
+use record::Id;
+use record::IdentityError;
+use record::Record;
+use record::TemporaryIdAllocator;
+
+/// Entry of the [`FlatTreeStore`](crate::FlatTreeStore)
+///
+/// Wraps the record of the tree with the information needed to render it as a row of the flattened tree.
+#[derive(Debug, Clone)]
+pub struct TreeNode<R: Record> {
+    /// Record from the parent store
+    pub record: R,
+    /// Number of ancestors of the record, roots have depth `0`
+    pub depth: usize,
+    /// `true` if children of this node are visible
+    pub expanded: bool,
+    /// Number of children of this node
+    pub children: usize,
+}
+
+impl<R: Record> Record for TreeNode<R> {
+    type Allocator = R::Allocator;
+
+    fn get_id(&self) -> Id<Self> {
+        self.record.get_id().transfer()
+    }
+
+    fn set_permanent_id(&mut self, value: <Self::Allocator as TemporaryIdAllocator>::Type) -> Result<(), IdentityError> {
+        self.record.set_permanent_id(value)
+    }
+}
@@ -1,6 +1,10 @@
+use std::cmp::min;
+
 use crate::Range;
 use crate::math::Point;
 
+use super::move_in_page;
+use super::reorder_in_page;
 use super::StoreState;
 use super::WindowBehavior;
 use super::WindowTransition;
@@ -10,11 +14,18 @@ use super::WindowTransition;
 /// If you implement kind of logging tool it might be a thing.
 /// For example if you implement git client list of git command
 /// issued might use this window behavior.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct KeepOnBottom {}
 
+impl KeepOnBottom {
+    /// Creates new instance of the KeepOnBottom
+    pub fn new() -> Self {
+        Self{}
+    }
+}
+
 impl WindowBehavior for KeepOnBottom {
-    fn insert(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn insert(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p < state.page.start() {
             WindowTransition::Identity
         }
@@ -30,7 +41,7 @@ impl WindowBehavior for KeepOnBottom {
         }
     }
 
-    fn remove(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn remove(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p < state.page.start() {
             WindowTransition::Identity
         }
@@ -42,9 +53,61 @@ impl WindowBehavior for KeepOnBottom {
         }
     }
 
+    /// Records inserted after the last page slide the window, so it stays at the bottom
+    fn insert_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() < state.page.start() {
+            WindowTransition::Identity
+        }
+        else if range.start() >= state.page.end() {
+            WindowTransition::SlideRight(range.len())
+        }
+        else {
+            WindowTransition::InsertRight{
+                pos: *range.start(),
+                by: min(range.len(), state.page.end() - range.start()),
+            }
+        }
+    }
+
+    /// Removed records are taken out of the last page and the page is filled from the left.
+    /// If removal spans over the start of the last page, the window slides to the left.
+    fn remove_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.end() <= state.page.start() {
+            WindowTransition::Identity
+        }
+        else if range.start() < state.page.start() {
+            WindowTransition::SlideLeft(range.len())
+        }
+        else {
+            let end = min(*range.end(), *state.page.end());
+            WindowTransition::RemoveLeft{
+                pos: end,
+                by: end - range.start(),
+            }
+        }
+    }
+
     /// Does nothing. You can't slide away from the top of the window while
     /// use this view
-    fn slide(_state: &StoreState<'_>, _moved: &Range) -> WindowTransition {
+    fn slide(&mut self, _state: &StoreState<'_>, _moved: &Range) -> WindowTransition {
         WindowTransition::Identity
     }
-}
\ No newline at end of file
+
+    /// Moving the record doesn't change the number of records, so window stays at the bottom and records within
+    /// the last page are shifted
+    fn move_record(&mut self, state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        move_in_page(state, from, to)
+    }
+
+    /// Only the part of the range within the last page is reordered
+    fn reorder(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        reorder_in_page(state, range)
+    }
+
+    /// Last record of the window stays in place, so the window stays at the bottom
+    fn resize(&mut self, state: &StoreState<'_>, size: usize) -> Range {
+        let end = state.page.start() + state.view;
+        let start = end.saturating_sub(size);
+        Range::new(start, start.saturating_add(size))
+    }
+}
@@ -1,6 +1,10 @@
+use std::cmp::min;
+
 use crate::Range;
 use crate::math::Point;
 
+use super::move_in_page;
+use super::reorder_in_page;
 use super::StoreState;
 use super::WindowBehavior;
 use super::WindowTransition;
@@ -9,11 +13,18 @@ use super::WindowTransition;
 /// 
 /// If you implement reporting tool and would like to show
 /// top 10 records in the store and nothing else.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct KeepOnTop {}
 
+impl KeepOnTop {
+    /// Creates new instance of the KeepOnTop
+    pub fn new() -> Self {
+        Self{}
+    }
+}
+
 impl WindowBehavior for KeepOnTop {
-    fn insert(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn insert(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p >= state.page.end() {
             WindowTransition::Identity
         } 
@@ -34,7 +45,7 @@ impl WindowBehavior for KeepOnTop {
     /// If removal is out of range, it's ignored. Otherwise
     /// remove right is returned since all possible data can
     /// only come from right side
-    fn remove(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn remove(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p >= state.page.end() {
             WindowTransition::Identity
         }
@@ -46,9 +57,47 @@ impl WindowBehavior for KeepOnTop {
         }
     }
 
+    /// Records inserted after the first page are ignored. Otherwise records which fit into
+    /// the first page are inserted
+    fn insert_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() >= state.page.end() {
+            WindowTransition::Identity
+        }
+        else {
+            WindowTransition::InsertRight{
+                pos: *range.start(),
+                by: min(range.len(), state.page.end() - range.start()),
+            }
+        }
+    }
+
+    /// If removal is out of range, it's ignored. Otherwise visible part
+    /// of the removed records is removed and the page is filled from the right
+    fn remove_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() >= state.page.end() {
+            WindowTransition::Identity
+        }
+        else {
+            WindowTransition::RemoveRight{
+                pos: *range.start(),
+                by: min(range.end(), state.page.end()) - range.start(),
+            }
+        }
+    }
+
     /// Does nothing. You can't slide away from the top of the window while
     /// use this view
-    fn slide(_state: &StoreState<'_>, _moved: &Range) -> WindowTransition {
+    fn slide(&mut self, _state: &StoreState<'_>, _moved: &Range) -> WindowTransition {
         WindowTransition::Identity
     }
-}
\ No newline at end of file
+
+    /// Records moved within the first page are shifted, everything else is ignored
+    fn move_record(&mut self, state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        move_in_page(state, from, to)
+    }
+
+    /// Only the part of the range within the first page is reordered
+    fn reorder(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        reorder_in_page(state, range)
+    }
+}
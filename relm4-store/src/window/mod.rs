@@ -20,45 +20,191 @@
 //! 
 //! If you implement some kind of reporting dashboard you might find [KeepOnTop] useful. It
 //! will lock the view to the first page of records. 
+//! 
+//! If user edits some record and you don't want it to run away when the store changes use
+//! [RecordTrackingWindow]. It will keep the chosen record at the same place on the screen.
 
 mod keep_on_bottom;
 mod keep_on_top;
 mod position_tracking_window;
+mod record_tracking_window;
 mod store_state;
 mod value_tracking_window;
 mod window_transition;
 
+use std::any::Any;
+use std::cmp::max;
+use std::fmt::Debug;
+use std::cmp::min;
+
 use super::Range;
 use super::math::Point;
 
 pub use keep_on_bottom::KeepOnBottom;
 pub use keep_on_top::KeepOnTop;
 pub use position_tracking_window::PositionTrackingWindow;
+pub use record_tracking_window::RecordTrackingWindow;
 pub use store_state::StoreState;
 pub use value_tracking_window::ValueTrackingWindow;
 pub use window_transition::WindowTransition;
 
 /// Describes how the window view should behave in presence of changes
 /// 
+/// Instance of the behavior is owned by the store view and is created by
+/// [StoreViewPrototype::init_window](crate::StoreViewPrototype::init_window). Since the behavior is a value
+/// it can carry it's own configuration and state. You can replace it at any time, for example to stop following
+/// new entries when the user scrolls up. If you need to switch between different kinds of behaviors use
+/// `Box<dyn WindowBehavior>` as your window type.
+/// 
 /// If you do custom implementation of the trait make sure you return [WindowTransition::Identity]
 /// as often as possible. Otherwise you might find your view to work extremely slow since it will try
 /// to compute updates for elements which are out of the view.
-pub trait WindowBehavior {
+pub trait WindowBehavior: Debug {
     /// Computes change in order of indexes of elements on the line due to insert
     /// 
     /// - `r` is a current store view range
     /// - `p` is an insertion point
-    fn insert(r: &StoreState<'_>, p: &Point) -> WindowTransition;
+    fn insert(&mut self, r: &StoreState<'_>, p: &Point) -> WindowTransition;
 
     /// Computes change in order of indexes of element on the line due to removal
     /// 
     /// - `r` is a current store view range
     /// - `p` is an insertion point
-    fn remove(r: &StoreState<'_>, p: &Point) -> WindowTransition;
+    fn remove(&mut self, r: &StoreState<'_>, p: &Point) -> WindowTransition;
+
+    /// Computes change in order of indexes of elements on the line due to insert of many records
+    /// 
+    /// - `r` is a current store view range
+    /// - `range` is the range of positions of the new records
+    fn insert_range(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition;
+
+    /// Computes change in order of indexes of elements on the line due to removal of many records
+    /// 
+    /// - `r` is a current store view range
+    /// - `range` is the range of positions of the removed records before the removal
+    fn remove_range(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition;
 
     /// Computes change in order of indexes of elements due to moving the range
     /// 
     /// - `r` is a current store view range
     /// - `moved` is the new range to which the view would be moved to
-    fn slide(r: &StoreState<'_>, moved: &Range) -> WindowTransition;
+    fn slide(&mut self, r: &StoreState<'_>, moved: &Range) -> WindowTransition;
+
+    /// Computes change in order of indexes of elements due to moving a record
+    /// 
+    /// - `r` is a current store view range
+    /// - `from` is the position of the record before the move
+    /// - `to` is the position of the record after the move
+    fn move_record(&mut self, r: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition;
+
+    /// Computes change in order of indexes of elements due to reordering records in the range
+    /// 
+    /// - `r` is a current store view range
+    /// - `range` is the range of positions which changed their order, records outside of it kept their positions
+    fn reorder(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition;
+
+    /// Notifies the behavior that the record changed it's id, usually because new record got the permanent id
+    /// 
+    /// - `old` and `new` are the [Id](record::Id) of the records of the store. Behavior doesn't know the type of
+    ///   the records, so the ids are passed as [Any] for the behaviors which track records by id to downcast them
+    /// 
+    /// Id change doesn't move the record, so default implementation does nothing
+    fn id_changed(&mut self, _old: &dyn Any, _new: &dyn Any) {}
+
+    /// Computes the range of the window after it's size was changed
+    /// 
+    /// - `r` is a current store view range
+    /// - `size` is the new size of the window
+    /// 
+    /// Default implementation keeps the first record of the window in place
+    fn resize(&mut self, r: &StoreState<'_>, size: usize) -> Range {
+        let start = *r.page.start();
+        Range::new(start, start.saturating_add(size))
+    }
+
+    /// Computes the range of the window after the store was reloaded
+    /// 
+    /// - `r` is a current store view range
+    /// 
+    /// After the reload all records in the window are fetched again. Default implementation keeps the window in place
+    fn reload(&mut self, r: &StoreState<'_>) -> Range {
+        *r.page
+    }
+}
+
+/// Allows to switch between different kinds of window behaviors at runtime
+impl WindowBehavior for Box<dyn WindowBehavior> {
+    fn insert(&mut self, r: &StoreState<'_>, p: &Point) -> WindowTransition {
+        self.as_mut().insert(r, p)
+    }
+
+    fn remove(&mut self, r: &StoreState<'_>, p: &Point) -> WindowTransition {
+        self.as_mut().remove(r, p)
+    }
+
+    fn insert_range(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition {
+        self.as_mut().insert_range(r, range)
+    }
+
+    fn remove_range(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition {
+        self.as_mut().remove_range(r, range)
+    }
+
+    fn slide(&mut self, r: &StoreState<'_>, moved: &Range) -> WindowTransition {
+        self.as_mut().slide(r, moved)
+    }
+
+    fn move_record(&mut self, r: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        self.as_mut().move_record(r, from, to)
+    }
+
+    fn reorder(&mut self, r: &StoreState<'_>, range: &Range) -> WindowTransition {
+        self.as_mut().reorder(r, range)
+    }
+
+    fn id_changed(&mut self, old: &dyn Any, new: &dyn Any) {
+        self.as_mut().id_changed(old, new)
+    }
+
+    fn resize(&mut self, r: &StoreState<'_>, size: usize) -> Range {
+        self.as_mut().resize(r, size)
+    }
+
+    fn reload(&mut self, r: &StoreState<'_>) -> Range {
+        self.as_mut().reload(r)
+    }
+}
+
+/// Returns transition which moves the record if the move is visible in the page
+/// 
+/// Move is invisible if the record was and stays on the same side of the page
+fn move_in_page(state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+    let before = from < state.page.start() && to < state.page.start();
+    let after = from >= state.page.end() && to >= state.page.end();
+
+    if before || after || from.value() == to.value() {
+        WindowTransition::Identity
+    }
+    else {
+        WindowTransition::Move{
+            from: from.value(),
+            to: to.value(),
+        }
+    }
+}
+
+/// Returns transition which reorders the part of the `range` visible in the page
+fn reorder_in_page(state: &StoreState<'_>, range: &Range) -> WindowTransition {
+    let from = max(*range.start(), *state.page.start());
+    let to = min(*range.end(), *state.page.end());
+
+    if from < to {
+        WindowTransition::Reorder{
+            from,
+            to,
+        }
+    }
+    else {
+        WindowTransition::Identity
+    }
 }
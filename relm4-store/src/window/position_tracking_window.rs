@@ -3,6 +3,8 @@ use std::cmp::min;
 use crate::Range;
 use crate::math::Point;
 
+use super::move_in_page;
+use super::reorder_in_page;
 use super::StoreState;
 use super::WindowBehavior;
 use super::WindowTransition;
@@ -12,9 +14,16 @@ use super::WindowTransition;
 ///
 /// This window will be stable in terms of pagination. If your store
 /// is at third page of data it will stay there as much as possible
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct PositionTrackingWindow{}
 
+impl PositionTrackingWindow {
+    /// Creates new instance of the PositionTrackingWindow
+    pub fn new() -> Self {
+        Self{}
+    }
+}
+
 impl WindowBehavior for PositionTrackingWindow  {
     /// Computes change in order of indexed elements on the line due to addition of element
     /// 
@@ -35,7 +44,7 @@ impl WindowBehavior for PositionTrackingWindow  {
     /// Position of value in range `P = p.pos - range.start`. 
     /// Indexes from `[range.start, P)` are kept. Value is inserted at P.
     /// Indexes from `[P+1, range.end)` are increased
-    fn insert(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn insert(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p >= state.page.end() { // Case 2
             WindowTransition::Identity
         } 
@@ -70,7 +79,7 @@ impl WindowBehavior for PositionTrackingWindow  {
     /// Position of value in range is `P = p.pos - range.start`.
     /// Indexes from `[range.start, P) are kept. Value is removed at P.
     /// Indexes from `[P+1, range.end) are decreased by 1.
-    fn remove(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn remove(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p >= state.page.end() { // Case 2
             WindowTransition::Identity
         }
@@ -88,6 +97,72 @@ impl WindowBehavior for PositionTrackingWindow  {
         }
     }
 
+    /// Computes change in order of indexed elements on the line due to addition of many elements
+    ///
+    /// There are 3 cases to take care of
+    ///
+    /// ## Case 1: Values have been inserted before range
+    ///
+    /// Take `range.len()` items from left, at most whole page is replaced
+    ///
+    /// ## Case 2: Values have been inserted after range
+    ///
+    /// Nothing changes
+    ///
+    /// ## Case 3: Values are inserted in the range
+    ///
+    /// Same as for single value but only values which fit into the range are inserted
+    fn insert_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() >= state.page.end() { // Case 2
+            WindowTransition::Identity
+        }
+        else if range.start() < state.page.start() { // Case 1
+            WindowTransition::InsertRight{
+                pos: *state.page.start(),
+                by: min(range.len(), state.page.len()),
+            }
+        }
+        else { // Case 3
+            WindowTransition::InsertRight{
+                pos: *range.start(),
+                by: min(range.len(), state.page.end() - range.start()),
+            }
+        }
+    }
+
+    /// Computes change in order of indexed elements on the line due to removal of many elements
+    ///
+    /// There are 3 cases to take care of
+    ///
+    /// ## Case 1: Removal starts before range
+    ///
+    /// Remove `range.len()` first elements, at most all elements in the view. Fill up from the right
+    ///
+    /// ## Case 2: Removal starts after range
+    ///
+    /// Nothing to do
+    ///
+    /// ## Case 3: Removal starts in the range
+    ///
+    /// Remove elements from `range.start` up to the end of the range or page whichever comes first. Fill up from the right
+    fn remove_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() >= state.page.end() { // Case 2
+            WindowTransition::Identity
+        }
+        else if range.start() < state.page.start() { // Case 1
+            WindowTransition::RemoveRight{
+                pos: *state.page.start(),
+                by: min(range.len(), state.view),
+            }
+        }
+        else { // Case 3
+            WindowTransition::RemoveRight{
+                pos: *range.start(),
+                by: min(range.end(), state.page.end()) - range.start(),
+            }
+        }
+    }
+
     /// Computes change in order of indexes of elements due to moving the range
     ///
     /// There are 6 cases to take care of
@@ -115,7 +190,7 @@ impl WindowBehavior for PositionTrackingWindow  {
     /// ## Case 6: moved range is subset of `r`
     ///
     /// It's equivalent of inserting `moved.len()` at `moved.start()`
-    fn slide(state: &StoreState<'_>, moved: &Range) -> WindowTransition {
+    fn slide(&mut self, state: &StoreState<'_>, moved: &Range) -> WindowTransition {
         if state.page.end() <= moved.start() { // Case 2
             WindowTransition::Identity
         }
@@ -142,4 +217,29 @@ impl WindowBehavior for PositionTrackingWindow  {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Window keeps it's position, so only the records visible in the window are affected
+    ///
+    /// If record was and stays on the same side of the window nothing changes. Otherwise records between `from`
+    /// and `to` which are visible in the window are shifted by one
+    fn move_record(&mut self, state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        move_in_page(state, from, to)
+    }
+
+    /// Window keeps it's position, so only the part of the range visible in the window is reordered
+    fn reorder(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        reorder_in_page(state, range)
+    }
+
+    /// Window snaps to the page of the new size containing the first record of the window
+    ///
+    /// If the window is at the third page of `10` records (`20..30`) and is resized to `15`, it moves to the
+    /// second page (`15..30`), so pagination stays stable
+    fn resize(&mut self, state: &StoreState<'_>, size: usize) -> Range {
+        let start = match size {
+            0 => *state.page.start(),
+            size => state.page.start() / size * size,
+        };
+        Range::new(start, start.saturating_add(size))
+    }
+}
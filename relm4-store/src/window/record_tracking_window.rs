@@ -0,0 +1,377 @@
+use std::any::Any;
+use std::cmp::Ordering;
+use std::cmp::min;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use record::Id;
+use record::Record;
+
+use crate::DataStore;
+use crate::Position;
+use crate::Range;
+use crate::math::Point;
+
+use super::PositionTrackingWindow;
+use super::StoreState;
+use super::WindowBehavior;
+use super::WindowTransition;
+use super::move_in_page;
+use super::reorder_in_page;
+
+/// Implements window in such a way that chosen record keeps it's place on
+/// the screen
+///
+/// This window tracks one record, the anchor. Whatever happens to the store
+/// the window slides so the anchor keeps it's offset from the start of the
+/// window. If the anchor is removed, the record which took it's place becomes
+/// the new anchor, or the last record if the anchor was at the end of the
+/// store.
+///
+/// Position of the anchor is tracked from the changes sent by the store. The
+/// `store` is read only when the anchor is removed, to find the new one, and
+/// when the view is reloaded, to find where the anchor is. It's the store for
+/// which the view was created. If there is no anchor window behaves like
+/// [PositionTrackingWindow].
+pub struct RecordTrackingWindow<Store>
+where
+    Store: DataStore,
+{
+    store: Store,
+    /// Id of the anchor, `None` if there is no anchor
+    anchor: Option<Id<Store::Record>>,
+    /// Position of the anchor in the store, `None` if there is no anchor
+    position: Option<usize>,
+}
+
+impl<Store> RecordTrackingWindow<Store>
+where
+    Store: DataStore,
+{
+    /// Creates new instance of the RecordTrackingWindow without the anchor
+    pub fn new(store: Store) -> Self {
+        Self{
+            store,
+            anchor: None,
+            position: None,
+        }
+    }
+
+    /// Creates new instance of the RecordTrackingWindow anchored on the record with given `id` at the `position`
+    pub fn with_anchor(store: Store, id: Id<Store::Record>, position: Position) -> Self {
+        let mut window = Self::new(store);
+        window.set_anchor(Some((id, position)));
+        window
+    }
+
+    /// Returns id of the anchor
+    pub fn anchor(&self) -> Option<Id<Store::Record>> {
+        self.anchor
+    }
+
+    /// Changes the anchor to the record with given id at the position in the store
+    ///
+    /// If the position is wrong, it's fixed on the next reload of the view
+    pub fn set_anchor(&mut self, anchor: Option<(Id<Store::Record>, Position)>) {
+        self.anchor = anchor.map(|(id, _)| id);
+        self.position = anchor.map(|(_, Position(position))| position);
+    }
+
+    /// Id of the record at the `position` in the store
+    fn id_at(&self, position: usize) -> Option<Id<Store::Record>> {
+        self.store.get_range(&Range::new(position, position+1))
+            .first()
+            .map(|record| record.get_id())
+    }
+
+    /// Anchors the window on the record which took place of the removed anchor
+    ///
+    /// It's the record at the `position` or the last record if the anchor was at the end of the store. If the
+    /// store is empty there is no anchor anymore.
+    fn reanchor(&mut self, position: usize) {
+        match self.store.len().checked_sub(1) {
+            Some(last) => {
+                let position = min(position, last);
+                self.anchor = self.id_at(position);
+                self.position = Some(position);
+            },
+            None => {
+                self.anchor = None;
+                self.position = None;
+            },
+        }
+    }
+
+    /// Finds position of the record with given `id`
+    ///
+    /// Anchor is usually visible, so the `page` is checked before scanning the whole store
+    fn find(&self, id: Id<Store::Record>, page: &Range) -> Option<usize> {
+        let in_page = self.store.get_range(page)
+            .iter()
+            .position(|record| record.get_id() == id)
+            .map(|position| page.start() + position);
+
+        in_page.or_else(|| {
+            self.store.get_range(&Range::new(0, self.store.len()))
+                .iter()
+                .position(|record| record.get_id() == id)
+        })
+    }
+
+    /// Slides the window so anchor keeps it's offset after moving from `before` to `after`
+    fn follow(before: usize, after: usize) -> WindowTransition {
+        match after.cmp(&before) {
+            Ordering::Less => WindowTransition::SlideLeft(before - after),
+            Ordering::Equal => WindowTransition::Identity,
+            Ordering::Greater => WindowTransition::SlideRight(after - before),
+        }
+    }
+}
+
+impl<Store> WindowBehavior for RecordTrackingWindow<Store>
+where
+    Store: DataStore,
+{
+    /// Computes change in order of indexes of elements on the line due to insert
+    ///
+    /// There are 3 cases to take care of
+    ///
+    /// ## Case 1: Element inserted after the range and after the anchor
+    ///
+    /// Nothing to do
+    ///
+    /// ## Case 2: Element inserted after the anchor
+    ///
+    /// Anchor keeps it's position. Records after the insertion point are moved to the right.
+    ///
+    /// ## Case 3: Element inserted before or at the anchor
+    ///
+    /// Anchor moves to the right, so the window slides right by one. If insertion point is in the range
+    /// records before it are moved to the left.
+    fn insert(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return PositionTrackingWindow::new().insert(state, p),
+        };
+
+        if p > &anchor { // Case 1 and 2
+            PositionTrackingWindow::new().insert(state, p)
+        }
+        else { // Case 3
+            self.position = Some(anchor + 1);
+            if p < state.page.start() || p >= state.page.end() {
+                WindowTransition::SlideRight(1)
+            }
+            else {
+                WindowTransition::InsertLeft{
+                    pos: p.value(),
+                    by: 1,
+                }
+            }
+        }
+    }
+
+    /// Computes change in order of indexes of elements on the line due to removal
+    ///
+    /// There are 3 cases to take care of
+    ///
+    /// ## Case 1: Element removed after the anchor
+    ///
+    /// Anchor keeps it's position. Records after the removed one are moved to the left.
+    ///
+    /// ## Case 2: Anchor was removed
+    ///
+    /// Record which took it's place becomes an anchor. If the anchor was the last record, the one before it
+    /// becomes an anchor. Window keeps it's position.
+    ///
+    /// ## Case 3: Element removed before the anchor
+    ///
+    /// Anchor moves to the left, so the window slides left by one. If removed element was in the range
+    /// records before it are moved to the right.
+    fn remove(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return PositionTrackingWindow::new().remove(state, p),
+        };
+
+        if p > &anchor { // Case 1
+            PositionTrackingWindow::new().remove(state, p)
+        }
+        else if p == &anchor { // Case 2
+            self.reanchor(anchor);
+            PositionTrackingWindow::new().remove(state, p)
+        }
+        else { // Case 3
+            self.position = Some(anchor - 1);
+            if p < state.page.start() || p >= state.page.end() {
+                WindowTransition::SlideLeft(1)
+            }
+            else {
+                // pos points to the first not deleted record so +1
+                WindowTransition::RemoveLeft{
+                    pos: p.value() + 1,
+                    by: 1,
+                }
+            }
+        }
+    }
+
+    /// Computes change in order of indexes of elements on the line due to insert of many records
+    ///
+    /// Same as for the single record, if records are inserted before or at the anchor window slides right by the
+    /// number of new records
+    fn insert_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return PositionTrackingWindow::new().insert_range(state, range),
+        };
+
+        if range.start() > &anchor {
+            PositionTrackingWindow::new().insert_range(state, range)
+        }
+        else {
+            self.position = Some(anchor + range.len());
+            if range.start() < state.page.start() || range.start() >= state.page.end() {
+                WindowTransition::SlideRight(range.len())
+            }
+            else {
+                WindowTransition::InsertLeft{
+                    pos: *range.start(),
+                    by: range.len(),
+                }
+            }
+        }
+    }
+
+    /// Computes change in order of indexes of elements on the line due to removal of many records
+    ///
+    /// Same as for the single record. If the anchor was removed, the record which took it's place becomes
+    /// an anchor. If records before the anchor were removed window slides left by the number of removed records.
+    fn remove_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return PositionTrackingWindow::new().remove_range(state, range),
+        };
+
+        if range.start() > &anchor {
+            PositionTrackingWindow::new().remove_range(state, range)
+        }
+        else if range.end() > &anchor {
+            self.reanchor(*range.start());
+            PositionTrackingWindow::new().remove_range(state, range)
+        }
+        else {
+            self.position = Some(anchor - range.len());
+            if range.start() < state.page.start() || range.end() > state.page.end() {
+                WindowTransition::SlideLeft(range.len())
+            }
+            else {
+                // pos points to the first not deleted record
+                WindowTransition::RemoveLeft{
+                    pos: *range.end(),
+                    by: range.len(),
+                }
+            }
+        }
+    }
+
+    /// Slide is the user's decision to look at the other part of the store, so anchor doesn't
+    /// change anything
+    fn slide(&mut self, state: &StoreState<'_>, moved: &Range) -> WindowTransition {
+        PositionTrackingWindow::new().slide(state, moved)
+    }
+
+    /// Computes change in order of indexes of elements due to moving a record
+    ///
+    /// If the anchor keeps it's position records visible in the window are shifted. Otherwise window
+    /// slides so the anchor keeps it's offset.
+    fn move_record(&mut self, state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return move_in_page(state, from, to),
+        };
+
+        let moved = if from.value() == anchor {
+            to.value()
+        }
+        else {
+            let mut moved = anchor;
+            if from.value() < moved {
+                moved -= 1;
+            }
+            if to.value() <= moved {
+                moved += 1;
+            }
+            moved
+        };
+
+        self.position = Some(moved);
+        if moved == anchor {
+            move_in_page(state, from, to)
+        }
+        else {
+            Self::follow(anchor, moved)
+        }
+    }
+
+    /// Computes change in order of indexes of elements due to reordering records in the range
+    ///
+    /// Reorder doesn't tell where the records went, so the anchor keeps it's position until the next reload
+    /// finds it. Part of the range visible in the window is reordered.
+    fn reorder(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        reorder_in_page(state, range)
+    }
+
+    /// Keeps the anchor when it gets the new id
+    fn id_changed(&mut self, old: &dyn Any, new: &dyn Any) {
+        if let (Some(old), Some(new)) = (old.downcast_ref::<Id<Store::Record>>(), new.downcast_ref::<Id<Store::Record>>()) {
+            if self.anchor.as_ref() == Some(old) {
+                self.anchor = Some(*new);
+            }
+        }
+    }
+
+    /// Finds the anchor in the store and slides the window so the anchor keeps it's offset
+    ///
+    /// If the anchor is not in the store anymore, record at it's position becomes the anchor. If anchor was
+    /// after the last record, then the last record becomes the anchor.
+    fn reload(&mut self, state: &StoreState<'_>) -> Range {
+        let anchor = match self.position {
+            Some(anchor) => anchor,
+            None => return *state.page,
+        };
+
+        match self.anchor.and_then(|id| self.find(id, state.page)) {
+            Some(moved) => self.position = Some(moved),
+            None => self.reanchor(anchor),
+        }
+
+        let moved = match self.position {
+            Some(moved) => moved,
+            // store is empty, so there is nothing to anchor on
+            None => return *state.page,
+        };
+
+        let start = *state.page.start();
+        if moved >= anchor {
+            state.page.slide(start + (moved - anchor))
+        }
+        else {
+            state.page.slide(start.saturating_sub(anchor - moved))
+        }
+    }
+}
+
+/// Formats [`RecordTrackingWindow`] for debug format `{:?}`
+impl<Store> Debug for RecordTrackingWindow<Store>
+where
+    Store: DataStore,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("RecordTrackingWindow")
+            .field("anchor", &self.anchor)
+            .field("position", &self.position)
+            .finish()
+    }
+}
@@ -3,6 +3,8 @@ use std::cmp::min;
 use crate::Range;
 use crate::math::Point;
 
+use super::move_in_page;
+use super::reorder_in_page;
 use super::StoreState;
 use super::WindowBehavior;
 use super::WindowTransition;
@@ -13,8 +15,48 @@ use super::WindowTransition;
 /// This window tries to keep given set of data in view. So if
 /// you add 100 of elements before the view range it will keep
 /// current dataset in view
-#[derive(Debug)]
-pub struct ValueTrackingWindow{}
+///
+/// When record is added or removed inside of the range, records before the threshold are moved to the left
+/// and records after it are moved to the right. By default threshold is in the middle of the range.
+#[derive(Clone, Copy, Debug)]
+pub struct ValueTrackingWindow{
+    threshold: f64,
+}
+
+impl ValueTrackingWindow {
+    /// Creates new instance of the ValueTrackingWindow with threshold in the middle of the range
+    pub fn new() -> Self {
+        Self::with_threshold(0.5)
+    }
+
+    /// Creates new instance of the ValueTrackingWindow with given threshold
+    ///
+    /// `threshold` is the fraction of the range length, so `1.0/3.0` means first third of the range
+    ///
+    /// **Panics** if threshold is not in `[0, 1]`
+    pub fn with_threshold(threshold: f64) -> Self {
+        assert!((0.0..=1.0).contains(&threshold), "Threshold must be in [0, 1]");
+        Self{
+            threshold,
+        }
+    }
+
+    /// Returns the threshold of this window
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Position in the store where the threshold is
+    fn pivot(&self, state: &StoreState<'_>) -> usize {
+        state.page.start() + (state.page.len() as f64 * self.threshold) as usize
+    }
+}
+
+impl Default for ValueTrackingWindow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl WindowBehavior for ValueTrackingWindow {
     /// Computes change in order of indexes of elements on the line
@@ -32,13 +74,13 @@ impl WindowBehavior for ValueTrackingWindow {
     /// ## Case 3: Element inserted inside the range
     ///
     /// ```text
-    /// half = (r.start + r.end)/2
-    /// If p < half then remove first element.
+    /// pivot = r.start + r.len * threshold
+    /// If p < pivot then remove first element.
     ///   Reduce index of elements from start to p by 1, insert p 
     /// Else
     ///   Increase index of elements from `p` by 1. Insert p at `p.pos`
     /// ```
-    fn insert(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn insert(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p >= state.page.end() {
             WindowTransition::Identity
         }
@@ -46,8 +88,8 @@ impl WindowBehavior for ValueTrackingWindow {
             WindowTransition::SlideRight(1)
         }
         else {
-            let half: usize = (state.page.start() + state.page.end())/2;
-            if p < &half {
+            let pivot = self.pivot(state);
+            if p < &pivot {
                 WindowTransition::InsertLeft{
                     pos: p.value(),
                     by: 1,
@@ -78,19 +120,19 @@ impl WindowBehavior for ValueTrackingWindow {
     /// ## Case 3: Element removed inside the range
     ///
     /// ```text
-    /// half = (r.start + r.end)/2
-    /// If p < half then remove first element.
+    /// pivot = r.start + r.len * threshold
+    /// If p < pivot then remove first element.
     ///   Reduce index of elements from start to p by 1, insert p 
     /// Else
     ///   Increase index of elements from `p` by 1. Insert p at `p`
     /// ```
-    fn remove(state: &StoreState<'_>, p: &Point) -> WindowTransition {
+    fn remove(&mut self, state: &StoreState<'_>, p: &Point) -> WindowTransition {
         if p < state.page.start() || p >= state.page.end() {
             WindowTransition::Identity
         }
         else {
-            let half: usize = (state.page.start() + state.page.end())/2;
-            if p < &half {
+            let pivot = self.pivot(state);
+            if p < &pivot {
                 // p is at the deletion point and pos should point to first not deleted record so +1
                 WindowTransition::RemoveLeft{
                     pos: p.value() + 1,
@@ -106,6 +148,77 @@ impl WindowBehavior for ValueTrackingWindow {
         }
     }
 
+    /// Computes change in order of indexes of elements on the line due to insert of many records
+    ///
+    /// Same as for the single record, if range starts before the pivot records before it are moved to the left,
+    /// otherwise records after it are moved to the right
+    fn insert_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.start() >= state.page.end() {
+            WindowTransition::Identity
+        }
+        else if range.start() <= state.page.start() && state.page.len() == state.view {
+            WindowTransition::SlideRight(range.len())
+        }
+        else if range.start() < &self.pivot(state) {
+            WindowTransition::InsertLeft{
+                pos: *range.start(),
+                by: range.len(),
+            }
+        }
+        else {
+            WindowTransition::InsertRight{
+                pos: *range.start(),
+                by: min(
+                    range.len(),
+                    state.page.end() - range.start()
+                ),
+            }
+        }
+    }
+
+    /// Computes change in order of indexes of elements on the line due to removal of many records
+    ///
+    /// There are 3 cases to handle
+    ///
+    /// ## Case 1: Removed range is outside of the current range
+    ///
+    /// Nothing to do
+    ///
+    /// ## Case 2: Removed range contains start of the current range
+    ///
+    /// Records before the start are gone so the records are moved to the left and page is filled up from the right
+    ///
+    /// ## Case 3: Removed range starts inside the current range
+    ///
+    /// Same as for the single record. Only records inside the current range are removed
+    fn remove_range(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        if range.end() <= state.page.start() || range.start() >= state.page.end() { // Case 1
+            WindowTransition::Identity
+        }
+        else if range.start() < state.page.start() { // Case 2
+            WindowTransition::RemoveRight{
+                pos: *state.page.start(),
+                by: min(range.len(), state.view),
+            }
+        }
+        else { // Case 3
+            let end = min(*range.end(), *state.page.end());
+            if range.start() < &self.pivot(state) {
+                // pos should point to the first not deleted record
+                WindowTransition::RemoveLeft{
+                    pos: end,
+                    by: end - range.start(),
+                }
+            }
+            else {
+                WindowTransition::RemoveRight{
+                    pos: *range.start(),
+                    by: end - range.start(),
+                }
+            }
+        }
+    }
+
     /// Computes change in order of indexes of elements due to moving the range
     ///
     /// There are 3 cases to consider
@@ -123,7 +236,7 @@ impl WindowBehavior for ValueTrackingWindow {
     /// Insert at moved range start, at most current range end - moved range start items
     ///
     /// Nothing to do, cos we can move the window around and keep the data visible
-    fn slide(state: &StoreState<'_>, moved: &Range) -> WindowTransition {
+    fn slide(&mut self, state: &StoreState<'_>, moved: &Range) -> WindowTransition {
         if state.page.end() <= moved.start() {
             WindowTransition::Identity
         }
@@ -140,4 +253,41 @@ impl WindowBehavior for ValueTrackingWindow {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Computes change in order of indexes of elements due to moving a record
+    ///
+    /// There are 4 cases to consider
+    ///
+    /// ## Case 1: Record was and stays on the same side of the range
+    ///
+    /// Nothing to do
+    ///
+    /// ## Case 2: Record jumped from before the range to after the range
+    ///
+    /// All records in the range moved by one to the left, slide the window to the left by one
+    ///
+    /// ## Case 3: Record jumped from after the range to before the range
+    ///
+    /// All records in the range moved by one to the right, slide the window to the right by one
+    ///
+    /// ## Case 4: Record moved into, out of or within the range
+    ///
+    /// Records between `from` and `to` visible in the range are shifted by one
+    fn move_record(&mut self, state: &StoreState<'_>, from: &Point, to: &Point) -> WindowTransition {
+        if from < state.page.start() && to >= state.page.end() { // Case 2
+            WindowTransition::SlideLeft(1)
+        }
+        else if from >= state.page.end() && to <= state.page.start() { // Case 3
+            WindowTransition::SlideRight(1)
+        }
+        else { // Case 1 and 4
+            move_in_page(state, from, to)
+        }
+    }
+
+    /// Records in the range changed their position, so there is nothing to track. Part of the range visible in
+    /// the window is reordered
+    fn reorder(&mut self, state: &StoreState<'_>, range: &Range) -> WindowTransition {
+        reorder_in_page(state, range)
+    }
+}
@@ -50,4 +50,24 @@ pub enum WindowTransition {
     /// Move window to the left. No data changes
     SlideLeft(usize),
 
+    /// Record moved from one position of the store to another, window keeps showing the same positions of the store
+    ///
+    /// Records between `from` and `to` are shifted by one towards `from`. Records which left the window are
+    /// removed and records which entered it are fetched.
+    Move{
+        /// position of the record before the move
+        from: usize,
+        /// position of the record after the move
+        to: usize,
+    },
+
+    /// Records in the window changed their order, window keeps showing the same positions of the store
+    ///
+    /// Records in `[from, to)` must be fetched again. Widgets of the records which are still visible are reused.
+    Reorder{
+        /// first position in the store
+        from: usize,
+        /// end of the reordered positions (exclusive)
+        to: usize,
+    },
 }
\ No newline at end of file
@@ -0,0 +1,182 @@
+use reexport::glib;
+use reexport::gtk;
+use reexport::relm4;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use relm4::Sender;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_dummy::test_cases::TestTreeRecord;
+use record::Record;
+use backend_inmemory::InMemoryBackend;
+use backend_inmemory::InMemoryBackendConfiguration;
+use backend_inmemory::SortedInMemoryBackend;
+use backend_inmemory::SortedInMemoryBackendConfiguration;
+use backend_inmemory::TreeInMemoryBackend;
+use backend_inmemory::TreeInMemoryBackendConfiguration;
+use relm4_store::DataStore;
+use relm4_store::Filter;
+use relm4_store::Grouping;
+use relm4_store::Sorter;
+use relm4_store::Store;
+use relm4_store::StoreId;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+pub struct TestConfig {}
+
+impl InMemoryBackendConfiguration for TestConfig {
+    type Record = TestRecord;
+
+    fn initial_data() -> Vec<Self::Record> {
+        vec![
+            TestRecord::constant("a1").permanent(),
+            TestRecord::constant("b1").permanent(),
+            TestRecord::constant("a2").permanent(),
+            TestRecord::constant("b2").permanent(),
+            TestRecord::constant("a3").permanent(),
+        ]
+    }
+}
+
+pub type TestStore = Store<InMemoryBackend<TestConfig>>;
+
+pub struct TestTreeConfig {}
+
+/// Tree used by the tests
+///
+/// ```text
+/// 1
+/// ├ 1.1
+/// │ └ 1.1.1
+/// └ 1.2
+/// 2
+/// └ 2.1
+/// ```
+///
+/// Initial data is not in pre-order on purpose, backend must sort it out
+impl TreeInMemoryBackendConfiguration for TestTreeConfig {
+    type Record = TestTreeRecord;
+
+    fn initial_data() -> Vec<Self::Record> {
+        let r1 = TestTreeRecord::root("1").permanent();
+        let r2 = TestTreeRecord::root("2").permanent();
+        let r11 = TestTreeRecord::child("1.1", &r1).permanent();
+        let r12 = TestTreeRecord::child("1.2", &r1).permanent();
+        let r21 = TestTreeRecord::child("2.1", &r2).permanent();
+        let r111 = TestTreeRecord::child("1.1.1", &r11).permanent();
+
+        vec![r1, r2, r11, r12, r21, r111]
+    }
+}
+
+pub type TestTreeStore = Store<TreeInMemoryBackend<TestTreeConfig>>;
+
+/// Shows only records which label starts with given prefix
+#[derive(Debug)]
+pub struct LabelStartsWith(pub &'static str);
+
+impl Filter<TestRecord> for LabelStartsWith {
+    fn matches(&self, record: &TestRecord) -> bool {
+        record.label.starts_with(self.0)
+    }
+}
+
+/// Groups records by the first letter of the label
+#[derive(Debug)]
+pub struct FirstLetter {}
+
+impl Grouping<TestRecord> for FirstLetter {
+    type Key = char;
+
+    fn key(&self, record: &TestRecord) -> char {
+        record.label.chars().next().unwrap_or(' ')
+    }
+}
+
+/// Orders records by label
+#[derive(Debug, Clone, Copy)]
+pub struct OrderByLabel {
+    pub ascending: bool,
+}
+
+impl Sorter<TestRecord> for OrderByLabel {
+    fn cmp(&self, lhs: &TestRecord, rhs: &TestRecord) -> std::cmp::Ordering {
+        if self.ascending {
+            lhs.label.cmp(&rhs.label)
+        }
+        else {
+            lhs.label.cmp(&rhs.label).reverse()
+        }
+    }
+}
+
+pub struct TestSortedConfig {}
+
+impl SortedInMemoryBackendConfiguration for TestSortedConfig {
+    type Record = TestRecord;
+    type OrderBy = OrderByLabel;
+
+    fn initial_data() -> Vec<Self::Record> {
+        TestConfig::initial_data()
+    }
+
+    fn initial_order() -> Self::OrderBy {
+        OrderByLabel{ ascending: true }
+    }
+}
+
+pub type TestSortedBackendStore = Store<SortedInMemoryBackend<TestSortedConfig>>;
+
+/// Returns record with given label from the store
+pub fn find(store: &TestStore, label: &str) -> TestRecord {
+    store.get_range(&Range::new(0, store.len()))
+        .into_iter()
+        .find(|r| r.label == label)
+        .unwrap()
+}
+
+/// Returns labels of the records in order
+pub fn labels(records: &[TestRecord]) -> Vec<&str> {
+    records.iter().map(|r| r.label.as_str()).collect()
+}
+
+pub type Messages<R> = Rc<RefCell<Vec<StoreViewMsg<R>>>>;
+
+/// Creates sender which collects all received messages
+pub fn collector<R: 'static + Record>() -> (Sender<StoreViewMsg<R>>, Messages<R>) {
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    let messages = Rc::new(RefCell::new(vec![]));
+    let handler_messages = messages.clone();
+
+    let context = glib::MainContext::default();
+    receiver.attach(Some(&context), move |msg| {
+        handler_messages.borrow_mut().push(msg);
+        glib::Continue(true)
+    });
+
+    (sender, messages)
+}
+
+/// Registers listener collecting all messages sent by the store
+pub fn listen<S: DataStore>(store: &S) -> Messages<S::Record> {
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+    messages
+}
+
+/// Initializes gtk and runs the test while holding the default main context
+pub fn run(test: impl FnOnce(&glib::MainContext)) {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    test(&context);
+}
+
+/// Runs the main loop until there are no more pending events
+pub fn flush(context: &glib::MainContext) {
+    while context.iteration(false) {}
+}
@@ -0,0 +1,142 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::DetachedStore;
+use relm4_store::Position;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::labels;
+use crate::common::listen;
+use crate::common::run;
+
+type TestDetachedStore = DetachedStore<TestStore>;
+
+fn all<Store: DataStore>(store: &Store) -> Vec<Store::Record> {
+    store.get_range(&Range::new(0, store.len()))
+}
+
+/// Edits the detached store: updates `a1`, deletes `b1` and adds `c1`
+fn edit(detached: &TestDetachedStore) {
+    let mut a1 = find_in(detached, "a1");
+    a1.label = String::from("a1 - edited");
+    detached.send(StoreMsg::Commit(a1));
+    detached.send(StoreMsg::Delete(find_in(detached, "b1").get_id()));
+    detached.send(StoreMsg::Commit(TestRecord::since("c1", 0)));
+}
+
+fn find_in(store: &TestDetachedStore, label: &str) -> TestRecord {
+    all(store).into_iter().find(|r| r.label == label).unwrap()
+}
+
+#[test]
+#[serial(gtk)]
+fn local_edits_do_not_touch_parent() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let detached = TestDetachedStore::new(store.clone(), &Range::new(0, 3));
+        let messages = listen(&detached);
+
+        assert_eq!(labels(&all(&detached)), vec!["a1", "b1", "a2"]);
+
+        edit(&detached);
+        flush(context);
+
+        assert!(detached.has_changes());
+        assert_eq!(labels(&all(&detached)), vec!["a1 - edited", "a2", "c1"]);
+        assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3"], "Parent store must not see local changes");
+
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], StoreViewMsg::Update(_)));
+        assert!(matches!(messages[1], StoreViewMsg::Remove(Position(1))));
+        assert!(matches!(messages[2], StoreViewMsg::NewAt(Position(2))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn commit_sends_changes_to_parent() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let detached = TestDetachedStore::new(store.clone(), &Range::new(0, 3));
+
+        edit(&detached);
+        flush(context);
+        assert!(detached.commit().is_ok());
+        flush(context);
+
+        assert!(!detached.has_changes());
+        assert_eq!(labels(&all(&store)), vec!["a1 - edited", "a2", "b2", "a3", "c1"]);
+
+        // parent echoes committed records back, that's not a conflict
+        let mut a1 = find_in(&detached, "a1 - edited");
+        a1.label = String::from("a1 - edited twice");
+        detached.send(StoreMsg::Commit(a1));
+        flush(context);
+        assert!(detached.conflicts().is_empty());
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn discard_restores_snapshot() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let detached = TestDetachedStore::new(store.clone(), &Range::new(0, 3));
+        edit(&detached);
+        flush(context);
+
+        let messages = listen(&detached);
+        detached.discard();
+        flush(context);
+
+        assert!(!detached.has_changes());
+        assert_eq!(labels(&all(&detached)), vec!["a1", "b1", "a2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Reload));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn conflicting_changes_block_commit() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let detached = TestDetachedStore::new(store.clone(), &Range::new(0, 3));
+
+        let mut local = find(&store, "a1");
+        local.label = String::from("a1 - local");
+        detached.send(StoreMsg::Commit(local.clone()));
+
+        let mut remote = find(&store, "a1");
+        remote.label = String::from("a1 - remote");
+        store.send(StoreMsg::Commit(remote));
+
+        // parent change of the record which wasn't edited locally is not a conflict
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("a2 - remote");
+        store.send(StoreMsg::Commit(a2));
+        flush(context);
+
+        assert_eq!(detached.conflicts(), vec![local.get_id()]);
+        let conflict = detached.commit().unwrap_err();
+        assert_eq!(conflict.ids, vec![local.get_id()]);
+        flush(context);
+        assert_eq!(find(&store, "a1 - remote").get_id(), local.get_id(), "Nothing can be sent when there are conflicts");
+
+        detached.resolve(&local.get_id());
+        assert!(detached.commit().is_ok());
+        flush(context);
+        assert_eq!(store.get(&local.get_id()).unwrap().label, "a1 - local");
+    });
+}
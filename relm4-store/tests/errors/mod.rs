@@ -0,0 +1,180 @@
+use reexport::glib;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Id;
+use record::Record;
+use relm4_store::BackendError;
+use relm4_store::DataStore;
+use relm4_store::HistoryBackend;
+use relm4_store::Store;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::UndoableStore;
+use relm4_store::math::Range;
+
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::labels;
+use crate::common::listen;
+use crate::common::run;
+
+type Acks = Rc<RefCell<Vec<Result<(), BackendError>>>>;
+
+fn acks(context: &glib::MainContext) -> (glib::Sender<Result<(), BackendError>>, Acks) {
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    let acks: Acks = Rc::new(RefCell::new(vec![]));
+    let handler_acks = acks.clone();
+    receiver.attach(Some(context), move |ack| {
+        handler_acks.borrow_mut().push(ack);
+        glib::Continue(true)
+    });
+    (sender, acks)
+}
+
+fn missing_id() -> Id<TestRecord> {
+    TestRecord::since("missing", 0).get_id()
+}
+
+#[test]
+#[serial(gtk)]
+fn failed_message_is_reported_to_listeners() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let messages = listen(&store);
+
+        store.send(StoreMsg::Delete(missing_id()));
+        flush(context);
+
+        assert_eq!(store.len(), 5);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Error(BackendError::NotFound(_))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn send_with_ack_reports_outcome() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let (sender, acks) = acks(context);
+
+        assert!(store.send_with_ack(StoreMsg::Commit(TestRecord::since("c1", 0)), sender.clone()).is_ok());
+        assert!(store.send_with_ack(StoreMsg::Delete(missing_id()), sender).is_ok());
+        flush(context);
+
+        let acks = acks.borrow();
+        assert_eq!(acks.len(), 2);
+        assert_eq!(acks[0], Ok(()));
+        assert!(matches!(acks[1], Err(BackendError::NotFound(_))));
+        assert_eq!(store.len(), 6);
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn send_with_ack_keeps_order_with_send() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let (sender, acks) = acks(context);
+        let b1 = find(&store, "b1").get_id();
+
+        store.send(StoreMsg::Delete(b1));
+        assert!(store.send_with_ack(StoreMsg::Delete(b1), sender).is_ok());
+        store.sender().send(StoreMsg::Commit(TestRecord::since("c1", 0))).unwrap();
+        flush(context);
+
+        let acks = acks.borrow();
+        assert_eq!(acks.len(), 1);
+        assert!(matches!(acks[0], Err(BackendError::NotFound(_))), "Record must be already deleted by the message sent before");
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "a2", "b2", "a3", "c1"]);
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn deleting_record_twice_is_reported() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let messages = listen(&store);
+        let b1 = find(&store, "b1").get_id();
+
+        store.send(StoreMsg::Delete(b1));
+        store.send(StoreMsg::Delete(b1));
+        flush(context);
+
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "a2", "b2", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(_)));
+        assert!(matches!(messages[1], StoreViewMsg::Error(BackendError::NotFound(_))), "Second delete must not be silently ignored");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn committing_unknown_record_is_reported() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let messages = listen(&store);
+
+        store.send(StoreMsg::Commit(TestRecord::since("c1", 0).permanent()));
+        flush(context);
+
+        assert_eq!(store.len(), 5);
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "b1", "a2", "b2", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Error(BackendError::NotFound(_))), "Record must not be added without a position");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn failed_transaction_is_rolled_back() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let messages = listen(&store);
+
+        let mut a1 = find(&store, "a1");
+        a1.label = String::from("a1 - edited");
+        store.send(StoreMsg::Transaction(vec![
+            StoreMsg::Commit(a1),
+            StoreMsg::Delete(find(&store, "b1").get_id()),
+            StoreMsg::Delete(missing_id()),
+        ]));
+        flush(context);
+
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "b1", "a2", "b2", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1, "Listeners should see only the error");
+        assert!(matches!(messages[0], StoreViewMsg::Error(_)));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn failed_transaction_is_not_remembered_by_history() {
+    run(|context| {
+        let store: Store<HistoryBackend<InMemoryBackend<TestConfig>>> = Store::new(HistoryBackend::new(InMemoryBackend::new(), 10));
+        let records = store.get_range(&Range::new(0, 10));
+
+        store.send(StoreMsg::Transaction(vec![
+            StoreMsg::Delete(records[1].get_id()),
+            StoreMsg::Commit(TestRecord::since("c1", 0)),
+            StoreMsg::Delete(missing_id()),
+        ]));
+        flush(context);
+
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "b1", "a2", "b2", "a3"]);
+        assert!(!store.can_undo());
+    });
+}
@@ -0,0 +1,220 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::FilteredStore;
+use relm4_store::Position;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::LabelStartsWith;
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::labels;
+use crate::common::listen;
+use crate::common::run;
+
+type TestFilteredStore = FilteredStore<TestStore, LabelStartsWith>;
+
+#[test]
+#[serial(gtk)]
+fn initial_data() {
+    run(|_| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+
+        assert_eq!(filtered.len(), 3);
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3"]);
+        assert_eq!(labels(&filtered.get_range(&Range::new(1, 2))), vec!["a2"]);
+
+        let b1 = find(&store, "b1");
+        assert!(filtered.get(&b1.get_id()).is_none(), "Record not matching the filter must not be visible");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_matching_record() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        store.send(StoreMsg::Commit(TestRecord::since("a4", 0)));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "a4"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(3))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_not_matching_record() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        store.send(StoreMsg::Commit(TestRecord::since("b3", 0)));
+        flush(context);
+
+        assert_eq!(store.len(), 6);
+        assert_eq!(filtered.len(), 3);
+        assert!(messages.borrow().is_empty(), "Listeners must not be notified about invisible records");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_matching_record() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        let b1 = find(&store, "b1");
+        let a2 = find(&store, "a2");
+        store.send(StoreMsg::Delete(b1.get_id()));
+        store.send(StoreMsg::Delete(a2.get_id()));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(1))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_visible_record() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("a2 updated");
+        store.send(StoreMsg::Commit(a2.clone()));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2 updated", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], StoreViewMsg::Update(id) if *id == a2.get_id()));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_leaving_filter() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("b2 - a2 moved to b");
+        store.send(StoreMsg::Commit(a2));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(1))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_entering_filter() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        let mut b2 = find(&store, "b2");
+        b2.label = String::from("a - b2 moved to a");
+        store.send(StoreMsg::Commit(b2));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a - b2 moved to a", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(2))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn set_filter() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        filtered.set_filter(LabelStartsWith("b"));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["b1", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Reload));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_after_remove() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        store.send(StoreMsg::Delete(find(&store, "b1").get_id()));
+        let mut a3 = find(&store, "a3");
+        a3.label = String::from("b3 - a3 moved to b");
+        store.send(StoreMsg::Commit(a3));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(2))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_after_inserts() {
+    run(|context| {
+        let store = TestStore::new(backend_inmemory::InMemoryBackend::<TestConfig>::new());
+        let filtered = TestFilteredStore::new(store.clone(), LabelStartsWith("a"));
+        let messages = listen(&filtered);
+
+        for label in ["a4", "b4", "a5"] {
+            store.send(StoreMsg::Commit(TestRecord::since(label, 0)));
+            flush(context);
+        }
+        store.send(StoreMsg::Delete(find(&store, "a1").get_id()));
+        flush(context);
+
+        let mut a3 = find(&store, "a3");
+        a3.label = String::from("b3 - a3 moved to b");
+        store.send(StoreMsg::Commit(a3));
+        flush(context);
+
+        assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a2", "a4", "a5"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[2], StoreViewMsg::Remove(Position(0))));
+        assert!(matches!(messages[3], StoreViewMsg::Remove(Position(1))));
+    });
+}
@@ -0,0 +1,203 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::GroupedRecord;
+use relm4_store::GroupedStore;
+use relm4_store::Position;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::FirstLetter;
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::listen;
+use crate::common::run;
+
+type TestGroupedStore = GroupedStore<TestStore, FirstLetter>;
+
+/// Describes entries of the grouped store, headers are shown as `[key:len]` or `[key:len+]` if collapsed
+fn entries(store: &TestGroupedStore) -> Vec<String> {
+    store.get_range(&Range::new(0, store.len()))
+        .iter()
+        .map(|entry| match entry {
+            GroupedRecord::Header{key, len, collapsed, ..} => format!("[{}:{}{}]", key, len, if *collapsed {"+"} else {""}),
+            GroupedRecord::Record(record) => record.label.clone(),
+        })
+        .collect()
+}
+
+fn header_id(store: &TestGroupedStore, position: usize) -> record::Id<GroupedRecord<TestRecord, char>> {
+    store.get_range(&Range::new(position, position+1))[0].get_id()
+}
+
+#[test]
+#[serial(gtk)]
+fn initial_data() {
+    run(|_| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+
+        assert_eq!(grouped.len(), 7, "Headers must be counted as entries");
+        assert_eq!(entries(&grouped), vec!["[a:3]", "a1", "a2", "a3", "[b:2]", "b1", "b2"]);
+
+        let b1 = find(&store, "b1");
+        assert!(matches!(grouped.get(&b1.get_id().transfer()), Some(GroupedRecord::Record(r)) if r == b1));
+
+        let header = header_id(&grouped, 4);
+        assert!(matches!(grouped.get(&header), Some(GroupedRecord::Header{key: 'b', len: 2, ..})));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_record_to_existing_group() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        let messages = listen(&grouped);
+        let header = header_id(&grouped, 0);
+
+        store.send(StoreMsg::Commit(TestRecord::since("a4", 0)));
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[a:4]", "a1", "a2", "a3", "a4", "[b:2]", "b1", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(4))));
+        assert!(matches!(&messages[1], StoreViewMsg::Update(id) if *id == header), "Header must be updated since record count changed");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_record_to_new_group() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        let messages = listen(&grouped);
+
+        store.send(StoreMsg::Commit(TestRecord::since("0 - first group", 0)));
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[0:1]", "0 - first group", "[a:3]", "a1", "a2", "a3", "[b:2]", "b1", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(0))));
+        assert!(matches!(messages[1], StoreViewMsg::NewAt(Position(1))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_last_record_of_the_group() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        let messages = listen(&grouped);
+
+        store.send(StoreMsg::Delete(find(&store, "b1").get_id()));
+        store.send(StoreMsg::Delete(find(&store, "b2").get_id()));
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[a:3]", "a1", "a2", "a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(5))));
+        assert!(matches!(messages[1], StoreViewMsg::Update(_)));
+        assert!(matches!(messages[2], StoreViewMsg::Remove(Position(5))));
+        assert!(matches!(messages[3], StoreViewMsg::Remove(Position(4))), "Header of empty group must be removed");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_changing_group() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        let messages = listen(&grouped);
+
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("b - was a2");
+        store.send(StoreMsg::Commit(a2));
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[a:2]", "a1", "a3", "[b:3]", "b1", "b - was a2", "b2"], "Records in the group must keep the parent order");
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(2))));
+        assert!(matches!(messages[1], StoreViewMsg::Update(_)));
+        assert!(matches!(messages[2], StoreViewMsg::NewAt(Position(5))));
+        assert!(matches!(messages[3], StoreViewMsg::Update(_)));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_after_remove() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        store.send(StoreMsg::Delete(find(&store, "b1").get_id()));
+        flush(context);
+        let messages = listen(&grouped);
+
+        let mut a3 = find(&store, "a3");
+        a3.label = String::from("b - was a3");
+        store.send(StoreMsg::Commit(a3));
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[a:2]", "a1", "a2", "[b:2]", "b2", "b - was a3"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(3))));
+        assert!(matches!(messages[1], StoreViewMsg::Update(_)));
+        assert!(matches!(messages[2], StoreViewMsg::NewAt(Position(5))));
+        assert!(matches!(messages[3], StoreViewMsg::Update(_)));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn collapse_and_expand() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let grouped = TestGroupedStore::new(store.clone(), FirstLetter{});
+        let messages = listen(&grouped);
+
+        grouped.collapse('a');
+        flush(context);
+
+        assert!(grouped.is_collapsed(&'a'));
+        assert_eq!(grouped.len(), 4);
+        assert_eq!(entries(&grouped), vec!["[a:3+]", "[b:2]", "b1", "b2"]);
+        assert!(grouped.get(&find(&store, "a1").get_id().transfer()).is_none(), "Records of collapsed group are not visible");
+        {
+            let messages = messages.borrow();
+            assert_eq!(messages.len(), 4);
+            assert!(messages[0..3].iter().all(|m| matches!(m, StoreViewMsg::Remove(Position(1)))));
+            assert!(matches!(messages[3], StoreViewMsg::Update(_)));
+        }
+
+        // records added to collapsed group stay hidden
+        store.send(StoreMsg::Commit(TestRecord::since("a4", 0)));
+        flush(context);
+        assert_eq!(entries(&grouped), vec!["[a:4+]", "[b:2]", "b1", "b2"]);
+
+        messages.borrow_mut().clear();
+        grouped.expand('a');
+        flush(context);
+
+        assert_eq!(entries(&grouped), vec!["[a:4]", "a1", "a2", "a3", "a4", "[b:2]", "b1", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 5);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(1))));
+        assert!(matches!(messages[3], StoreViewMsg::NewAt(Position(4))));
+    });
+}
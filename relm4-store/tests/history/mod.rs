@@ -0,0 +1,179 @@
+use reexport::glib;
+use reexport::gtk;
+
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::HistoryBackend;
+use relm4_store::Position;
+use relm4_store::Store;
+use relm4_store::StoreId;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::UndoableStore;
+use relm4_store::math::Range;
+
+use crate::common::TestConfig;
+use crate::common::collector;
+use crate::common::flush;
+use crate::common::labels;
+
+type TestHistoryStore = Store<HistoryBackend<InMemoryBackend<TestConfig>>>;
+
+fn new_store(depth: usize) -> TestHistoryStore {
+    TestHistoryStore::new(HistoryBackend::new(InMemoryBackend::new(), depth))
+}
+
+fn all(store: &TestHistoryStore) -> Vec<TestRecord> {
+    store.get_range(&Range::new(0, store.len()))
+}
+
+fn find(store: &TestHistoryStore, label: &str) -> TestRecord {
+    all(store).into_iter().find(|r| r.label == label).unwrap()
+}
+
+fn rename(store: &TestHistoryStore, from: &str, to: &str) {
+    let mut record = find(store, from);
+    record.label = String::from(to);
+    store.send(StoreMsg::Commit(record));
+}
+
+#[test]
+#[serial(gtk)]
+fn undo_and_redo_update() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(10);
+    assert!(!store.can_undo());
+
+    rename(&store, "a1", "a1 - edited");
+    flush(&context);
+    assert!(store.can_undo());
+
+    store.undo();
+    assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3"]);
+    assert!(!store.can_undo());
+    assert!(store.can_redo());
+
+    store.redo();
+    assert_eq!(labels(&all(&store)), vec!["a1 - edited", "b1", "a2", "b2", "a3"]);
+    assert!(!store.can_redo());
+}
+
+#[test]
+#[serial(gtk)]
+fn undo_delete_restores_record_at_old_position() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(10);
+    let b1 = find(&store, "b1");
+    store.send(StoreMsg::Delete(b1.get_id()));
+    flush(&context);
+
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+    store.undo();
+    flush(&context);
+
+    assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3"]);
+    assert_eq!(store.get(&b1.get_id()), Some(b1));
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 1);
+    assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(1))));
+}
+
+#[test]
+#[serial(gtk)]
+fn undo_add_removes_record() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(10);
+    store.send(StoreMsg::Commit(TestRecord::since("c1", 0)));
+    flush(&context);
+    assert_eq!(store.len(), 6);
+
+    store.undo();
+    assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3"]);
+
+    store.redo();
+    assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3", "c1"]);
+}
+
+#[test]
+#[serial(gtk)]
+fn transaction_is_undone_in_single_step() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(10);
+    let mut a1 = find(&store, "a1");
+    a1.label = String::from("a1 - edited");
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(a1),
+        StoreMsg::Delete(find(&store, "b2").get_id()),
+        StoreMsg::Commit(TestRecord::since("c1", 0)),
+    ]));
+    flush(&context);
+    assert_eq!(labels(&all(&store)), vec!["a1 - edited", "b1", "a2", "a3", "c1"]);
+
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+    store.undo();
+    flush(&context);
+
+    assert_eq!(labels(&all(&store)), vec!["a1", "b1", "a2", "b2", "a3"]);
+    assert!(!store.can_undo());
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 1);
+    assert!(matches!(&messages[0], StoreViewMsg::Batch(batch) if batch.len() == 3));
+}
+
+#[test]
+#[serial(gtk)]
+fn history_is_bounded() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(2);
+    rename(&store, "a1", "x1");
+    flush(&context);
+    rename(&store, "a2", "x2");
+    flush(&context);
+    rename(&store, "a3", "x3");
+    flush(&context);
+
+    store.undo();
+    store.undo();
+    assert!(!store.can_undo(), "Only two last changes are remembered");
+    store.undo();
+    assert_eq!(labels(&all(&store)), vec!["x1", "b1", "a2", "b2", "a3"]);
+}
+
+#[test]
+#[serial(gtk)]
+fn new_change_clears_redo() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = new_store(10);
+    rename(&store, "a1", "x1");
+    flush(&context);
+    store.undo();
+    assert!(store.can_redo());
+
+    rename(&store, "a2", "x2");
+    flush(&context);
+    assert!(!store.can_redo());
+}
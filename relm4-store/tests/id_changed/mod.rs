@@ -0,0 +1,84 @@
+use reexport::glib;
+use reexport::gtk;
+
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::DetachedStore;
+use relm4_store::Position;
+use relm4_store::StoreId;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::TestSortedBackendStore;
+use crate::common::collector;
+use crate::common::flush;
+use crate::common::labels;
+
+fn all<Store: DataStore>(store: &Store) -> Vec<Store::Record> {
+    store.get_range(&Range::new(0, store.len()))
+}
+
+#[test]
+#[serial(gtk)]
+fn permanent_id_is_reported() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestSortedBackendStore::new(Default::default());
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+
+    let record = TestRecord::since("a0", 0);
+    let old = record.get_id();
+    store.send(StoreMsg::Commit(record));
+    flush(&context);
+
+    let added = store.get_range(&Range::new(0, 1)).pop().unwrap();
+    assert_eq!(added.label, "a0");
+    assert!(!added.get_id().is_new());
+    assert!(store.get(&old).is_none());
+
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(0))));
+    match &messages[1] {
+        StoreViewMsg::IdChanged{old: from, new: to} => {
+            assert_eq!(*from, old);
+            assert_eq!(*to, added.get_id());
+        },
+        msg => panic!("Expected id change, got {:?}", msg),
+    }
+}
+
+#[test]
+#[serial(gtk)]
+fn detached_store_follows_permanent_id() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestSortedBackendStore::new(Default::default());
+    let detached = DetachedStore::new(store.clone(), &Range::new(0, store.len()));
+
+    detached.send(StoreMsg::Commit(TestRecord::since("c1", 0)));
+    flush(&context);
+
+    let (sender, messages) = collector();
+    detached.listen(StoreId::new(), sender);
+    detached.commit().unwrap();
+    flush(&context);
+
+    let committed = all(&store).into_iter().find(|r| r.label == "c1").unwrap();
+    let local = detached.get(&committed.get_id());
+    assert_eq!(local.map(|r| r.label), Some(String::from("c1")));
+    assert!(!detached.has_changes());
+    assert!(messages.borrow().iter().any(|msg| matches!(msg, StoreViewMsg::IdChanged{..})));
+
+    detached.discard();
+    assert_eq!(labels(&all(&detached)), vec!["a1", "a2", "a3", "b1", "b2", "c1"], "Committed record is part of the snapshot");
+}
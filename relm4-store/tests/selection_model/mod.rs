@@ -0,0 +1,242 @@
+use reexport::glib;
+use reexport::gtk;
+
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::Position;
+use relm4_store::SelectionMode;
+use relm4_store::SelectionModel;
+use relm4_store::StoreId;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::TestSortedBackendStore;
+use crate::common::TestStore;
+use crate::common::collector;
+use crate::common::find;
+use crate::common::flush;
+
+#[test]
+#[serial(gtk)]
+fn multiple_selection_keeps_all_selected_records() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+    let a1 = find(&store, "a1").get_id();
+    let b1 = find(&store, "b1").get_id();
+
+    selection.select(&a1);
+    selection.select(&b1);
+
+    assert!(selection.is_selected(&a1));
+    assert!(selection.is_selected(&b1));
+    assert_eq!(selection.selected().len(), 2);
+}
+
+#[test]
+#[serial(gtk)]
+fn single_selection_unselects_previous_record() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Single);
+    let (sender, messages) = collector();
+    selection.listen(StoreId::new(), sender);
+    let a1 = find(&store, "a1").get_id();
+    let b1 = find(&store, "b1").get_id();
+
+    selection.select(&a1);
+    selection.select(&b1);
+    flush(&context);
+
+    assert!(!selection.is_selected(&a1));
+    assert!(selection.is_selected(&b1));
+
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 3, "Every change of the selection must be reported");
+    assert!(matches!(messages[0], StoreViewMsg::Update(id) if id == a1));
+    assert!(messages[1..].iter().any(|msg| matches!(msg, StoreViewMsg::Update(id) if *id == a1)));
+    assert!(messages[1..].iter().any(|msg| matches!(msg, StoreViewMsg::Update(id) if *id == b1)));
+}
+
+#[test]
+#[serial(gtk)]
+fn selecting_selected_record_is_not_reported() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Single);
+    let (sender, messages) = collector();
+    selection.listen(StoreId::new(), sender);
+    let a1 = find(&store, "a1").get_id();
+
+    selection.select(&a1);
+    selection.select(&a1);
+    selection.unselect(&find(&store, "b1").get_id());
+    flush(&context);
+
+    assert!(selection.is_selected(&a1));
+    assert_eq!(messages.borrow().len(), 1);
+}
+
+#[test]
+#[serial(gtk)]
+fn toggle_flips_the_selection() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+    let a1 = find(&store, "a1").get_id();
+
+    selection.toggle(&a1);
+    assert!(selection.is_selected(&a1));
+
+    selection.toggle(&a1);
+    assert!(!selection.is_selected(&a1));
+}
+
+#[test]
+#[serial(gtk)]
+fn range_selects_records_between_ends() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+
+    // a1, b1, a2, b2, a3
+    selection.select_range(Position(3), Position(1));
+
+    let expected = ["b1", "a2", "b2"].iter()
+        .map(|label| find(&store, label).get_id())
+        .collect();
+    assert_eq!(selection.selected(), expected);
+}
+
+#[test]
+#[serial(gtk)]
+fn range_ignores_positions_after_the_end() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+
+    selection.select_range(Position(3), Position(10));
+
+    let expected = ["b2", "a3"].iter()
+        .map(|label| find(&store, label).get_id())
+        .collect();
+    assert_eq!(selection.selected(), expected);
+}
+
+#[test]
+#[serial(gtk)]
+fn range_in_single_mode_selects_the_last_record() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Single);
+
+    selection.select_range(Position(0), Position(2));
+
+    assert_eq!(selection.selected().into_iter().collect::<Vec<_>>(), vec![find(&store, "a2").get_id()]);
+}
+
+#[test]
+#[serial(gtk)]
+fn clear_unselects_everything() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+    let (sender, messages) = collector();
+    selection.listen(StoreId::new(), sender);
+    selection.select_range(Position(0), Position(4));
+
+    selection.clear();
+    flush(&context);
+
+    assert!(selection.selected().is_empty());
+    assert_eq!(messages.borrow().len(), 10, "Selecting and unselecting every record must be reported");
+}
+
+#[test]
+#[serial(gtk)]
+fn removed_record_is_unselected() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+    let a1 = find(&store, "a1").get_id();
+    let b1 = find(&store, "b1").get_id();
+    selection.select(&a1);
+    selection.select(&b1);
+
+    store.send(StoreMsg::Delete(a1));
+    flush(&context);
+
+    assert!(!selection.is_selected(&a1));
+    assert!(selection.is_selected(&b1));
+}
+
+#[test]
+#[serial(gtk)]
+fn selection_follows_permanent_id() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestSortedBackendStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+
+    let record = TestRecord::since("a0", 0);
+    selection.select(&record.get_id());
+    store.send(StoreMsg::Commit(record));
+    flush(&context);
+
+    let added = store.get_range(&Range::new(0, 1)).pop().unwrap();
+    assert_eq!(added.label, "a0");
+    assert_eq!(selection.selected().into_iter().collect::<Vec<_>>(), vec![added.get_id()]);
+}
+
+#[test]
+#[serial(gtk)]
+fn clone_follows_the_store_after_original_is_dropped() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(Default::default());
+    let selection = SelectionModel::new(store.clone(), SelectionMode::Multiple);
+    let clone = selection.clone();
+    let a1 = find(&store, "a1").get_id();
+    selection.select(&a1);
+    drop(selection);
+
+    store.send(StoreMsg::Delete(a1));
+    flush(&context);
+
+    assert!(!clone.is_selected(&a1), "Selection model stops listening only when the last clone is dropped");
+}
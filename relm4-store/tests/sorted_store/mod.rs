@@ -0,0 +1,210 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::OrderedStore;
+use relm4_store::Position;
+use relm4_store::SortedStore;
+use relm4_store::Sorter;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::OrderByLabel;
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::labels;
+use crate::common::listen;
+use crate::common::run;
+
+type TestSortedStore = SortedStore<TestStore, OrderByLabel>;
+
+const ASC: OrderByLabel = OrderByLabel{ascending: true};
+const DESC: OrderByLabel = OrderByLabel{ascending: false};
+
+/// Orders records by the first letter of the label only, so records can be equal
+#[derive(Debug, Clone, Copy)]
+struct OrderByFirstLetter {
+    ascending: bool,
+}
+
+impl Sorter<TestRecord> for OrderByFirstLetter {
+    fn cmp(&self, lhs: &TestRecord, rhs: &TestRecord) -> std::cmp::Ordering {
+        let ordering = lhs.label[..1].cmp(&rhs.label[..1]);
+        if self.ascending {
+            ordering
+        }
+        else {
+            ordering.reverse()
+        }
+    }
+}
+
+#[test]
+#[serial(gtk)]
+fn initial_data() {
+    run(|_| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let desc = TestSortedStore::new(store.clone(), DESC);
+
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "b1", "a2", "b2", "a3"], "Parent store order must not change");
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "b1", "b2"]);
+        assert_eq!(labels(&desc.get_range(&Range::new(0, 10))), vec!["b2", "b1", "a3", "a2", "a1"]);
+        assert_eq!(labels(&asc.get_range(&Range::new(1, 3))), vec!["a2", "a3"]);
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_record() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let desc = TestSortedStore::new(store.clone(), DESC);
+        let asc_messages = listen(&asc);
+        let desc_messages = listen(&desc);
+
+        store.send(StoreMsg::Commit(TestRecord::since("a25", 0)));
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a25", "a3", "b1", "b2"]);
+        assert_eq!(labels(&desc.get_range(&Range::new(0, 10))), vec!["b2", "b1", "a3", "a25", "a2", "a1"]);
+
+        let asc_messages = asc_messages.borrow();
+        assert_eq!(asc_messages.len(), 1);
+        assert!(matches!(asc_messages[0], StoreViewMsg::NewAt(Position(2))));
+
+        let desc_messages = desc_messages.borrow();
+        assert_eq!(desc_messages.len(), 1);
+        assert!(matches!(desc_messages[0], StoreViewMsg::NewAt(Position(3))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn remove_record() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let messages = listen(&asc);
+
+        let b1 = find(&store, "b1");
+        store.send(StoreMsg::Delete(b1.get_id()));
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(3))));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_in_place() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let messages = listen(&asc);
+
+        let mut a2 = find(&store, "a2");
+        a2.label = String::from("a2 updated");
+        store.send(StoreMsg::Commit(a2.clone()));
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a1", "a2 updated", "a3", "b1", "b2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], StoreViewMsg::Update(id) if *id == a2.get_id()));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn update_record_moving_it() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let messages = listen(&asc);
+
+        let mut a1 = find(&store, "a1");
+        a1.label = String::from("c1");
+        store.send(StoreMsg::Commit(a1));
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a2", "a3", "b1", "b2", "c1"]);
+        assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["c1", "b1", "a2", "b2", "a3"], "Parent store order must not change");
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Move{from: Position(0), to: Position(4)}));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn set_order() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let other = TestSortedStore::new(store.clone(), ASC);
+        let messages = listen(&asc);
+
+        asc.set_order(DESC);
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["b2", "b1", "a3", "a2", "a1"]);
+        assert_eq!(labels(&other.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "b1", "b2"], "Other sorted stores must keep their order");
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0], StoreViewMsg::Reorder{from: Position(0), to: Position(5)}));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn set_same_order() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let asc = TestSortedStore::new(store.clone(), ASC);
+        let messages = listen(&asc);
+
+        asc.set_order(ASC);
+        flush(context);
+
+        assert_eq!(labels(&asc.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "b1", "b2"]);
+        assert!(messages.borrow().is_empty(), "Nothing moved so there is nothing to report");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn equal_records_keep_order_of_arrival() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+        let sorted = SortedStore::new(store.clone(), OrderByFirstLetter{ascending: true});
+        let messages = listen(&sorted);
+
+        store.send(StoreMsg::Commit(TestRecord::since("a0", 0)));
+        let mut a1 = find(&store, "a1");
+        a1.label = String::from("a1 - edited");
+        store.send(StoreMsg::Commit(a1.clone()));
+        flush(context);
+
+        assert_eq!(labels(&sorted.get_range(&Range::new(0, 10))), vec!["a1 - edited", "a2", "a3", "a0", "b1", "b2"]);
+        {
+            let messages = messages.borrow();
+            assert_eq!(messages.len(), 2);
+            assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(3))));
+            assert!(matches!(&messages[1], StoreViewMsg::Update(id) if *id == a1.get_id()), "Updated record must not jump over equal records");
+        }
+
+        sorted.set_order(OrderByFirstLetter{ascending: false});
+        sorted.set_order(OrderByFirstLetter{ascending: true});
+        assert_eq!(labels(&sorted.get_range(&Range::new(0, 10))), vec!["a1 - edited", "a2", "a3", "a0", "b1", "b2"]);
+    });
+}
@@ -0,0 +1,88 @@
+use backend_dummy::test_cases::TestRecord;
+use record::Id;
+use relm4_store::Position;
+use relm4_store::StoreViewMsg;
+
+type Msg = StoreViewMsg<TestRecord>;
+
+#[test]
+fn consecutive_inserts_are_merged() {
+    let merged = Msg::merge_ranges(vec![
+        StoreViewMsg::NewAt(Position(3)),
+        StoreViewMsg::NewAt(Position(4)),
+        StoreViewMsg::NewAt(Position(3)),
+        StoreViewMsg::NewAt(Position(6)),
+    ]);
+
+    assert_eq!(merged.len(), 1);
+    assert!(matches!(merged[0], StoreViewMsg::NewRange{from: Position(3), to: Position(7)}));
+}
+
+#[test]
+fn consecutive_removes_are_merged() {
+    let merged = Msg::merge_ranges(vec![
+        StoreViewMsg::Remove(Position(5)),
+        StoreViewMsg::Remove(Position(5)),
+        StoreViewMsg::Remove(Position(4)),
+        StoreViewMsg::Remove(Position(4)),
+    ]);
+
+    assert_eq!(merged.len(), 1);
+    assert!(matches!(merged[0], StoreViewMsg::RemoveRange{from: Position(4), to: Position(8)}));
+}
+
+#[test]
+fn separated_changes_are_not_merged() {
+    let merged = Msg::merge_ranges(vec![
+        StoreViewMsg::NewAt(Position(1)),
+        StoreViewMsg::NewAt(Position(5)),
+        StoreViewMsg::Remove(Position(6)),
+        StoreViewMsg::Reload,
+        StoreViewMsg::Remove(Position(5)),
+    ]);
+
+    assert_eq!(merged.len(), 5);
+    assert!(matches!(merged[0], StoreViewMsg::NewAt(Position(1))));
+    assert!(matches!(merged[1], StoreViewMsg::NewAt(Position(5))));
+    assert!(matches!(merged[2], StoreViewMsg::Remove(Position(6))));
+    assert!(matches!(merged[3], StoreViewMsg::Reload));
+    assert!(matches!(merged[4], StoreViewMsg::Remove(Position(5))));
+}
+
+#[test]
+fn removed_new_records_cancel_out() {
+    let merged = Msg::merge_ranges(vec![
+        StoreViewMsg::NewRange{from: Position(2), to: Position(6)},
+        StoreViewMsg::Update(Id::new()),
+        StoreViewMsg::RemoveRange{from: Position(3), to: Position(5)},
+    ]);
+
+    assert_eq!(merged.len(), 2);
+    assert!(matches!(merged[0], StoreViewMsg::NewRange{from: Position(2), to: Position(4)}));
+    assert!(matches!(merged[1], StoreViewMsg::Update(_)));
+}
+
+#[test]
+fn id_change_is_moved_after_the_range() {
+    let merged = Msg::merge_ranges(vec![
+        StoreViewMsg::NewAt(Position(0)),
+        StoreViewMsg::IdChanged{old: Id::new(), new: Id::new()},
+        StoreViewMsg::NewAt(Position(1)),
+    ]);
+
+    assert_eq!(merged.len(), 2);
+    assert!(matches!(merged[0], StoreViewMsg::NewRange{from: Position(0), to: Position(2)}));
+    assert!(matches!(merged[1], StoreViewMsg::IdChanged{..}));
+}
+
+#[test]
+fn ranges_are_expanded() {
+    let inserted = Msg::expand(StoreViewMsg::NewRange{from: Position(2), to: Position(5)});
+    assert_eq!(inserted.len(), 3);
+    assert!(matches!(inserted[0], StoreViewMsg::NewAt(Position(2))));
+    assert!(matches!(inserted[2], StoreViewMsg::NewAt(Position(4))));
+
+    let removed = Msg::expand(StoreViewMsg::RemoveRange{from: Position(2), to: Position(5)});
+    assert_eq!(removed.len(), 3);
+    assert!(removed.iter().all(|m| matches!(m, StoreViewMsg::Remove(Position(2)))));
+}
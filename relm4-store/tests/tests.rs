@@ -0,0 +1,14 @@
+mod common;
+
+mod detached_store;
+mod errors;
+mod filtered_store;
+mod grouped_store;
+mod history;
+mod id_changed;
+mod selection_model;
+mod sorted_store;
+mod store_view_msg;
+mod transaction;
+mod tree_store;
+mod window;
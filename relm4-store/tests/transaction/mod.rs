@@ -0,0 +1,131 @@
+use reexport::glib;
+use reexport::gtk;
+
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::FilteredStore;
+use relm4_store::Position;
+use relm4_store::StoreId;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::LabelStartsWith;
+use crate::common::TestConfig;
+use crate::common::TestStore;
+use crate::common::collector;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::labels;
+
+#[test]
+#[serial(gtk)]
+fn transaction_is_reported_as_single_batch() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+
+    let mut a1 = find(&store, "a1");
+    a1.label = String::from("a1 - edited");
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(TestRecord::since("c1", 0)),
+        StoreMsg::Delete(find(&store, "b1").get_id()),
+        StoreMsg::Commit(a1),
+    ]));
+    flush(&context);
+
+    assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1 - edited", "a2", "b2", "a3", "c1"]);
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 1, "Listeners should receive single message for the whole transaction");
+    match &messages[0] {
+        StoreViewMsg::Batch(batch) => {
+            assert_eq!(batch.len(), 3);
+            assert!(matches!(batch[0], StoreViewMsg::NewAt(Position(5))));
+            assert!(matches!(batch[1], StoreViewMsg::Remove(Position(1))));
+            assert!(matches!(batch[2], StoreViewMsg::Update(_)));
+        },
+        msg => panic!("Expected batch, got {:?}", msg),
+    }
+}
+
+#[test]
+#[serial(gtk)]
+fn filtered_store_translates_batch() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+    let filtered = FilteredStore::new(store.clone(), LabelStartsWith("a"));
+    let (sender, messages) = collector();
+    filtered.listen(StoreId::new(), sender);
+
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(TestRecord::since("a4", 0)),
+        StoreMsg::Commit(TestRecord::since("b3", 0)),
+    ]));
+    // nothing in this transaction is visible in the filtered store
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(TestRecord::since("b4", 0)),
+    ]));
+    flush(&context);
+
+    assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a2", "a3", "a4"]);
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 1);
+    match &messages[0] {
+        StoreViewMsg::Batch(batch) => {
+            assert_eq!(batch.len(), 1);
+            assert!(matches!(batch[0], StoreViewMsg::NewAt(Position(3))));
+        },
+        msg => panic!("Expected batch, got {:?}", msg),
+    }
+}
+
+#[test]
+#[serial(gtk)]
+fn bulk_changes_are_reported_as_ranges() {
+    gtk::init().unwrap();
+    let context = glib::MainContext::default();
+    let _guard = context.acquire().unwrap();
+
+    let store = TestStore::new(InMemoryBackend::<TestConfig>::new());
+    let filtered = FilteredStore::new(store.clone(), LabelStartsWith("a"));
+    let (sender, messages) = collector();
+    store.listen(StoreId::new(), sender);
+    let (filtered_sender, filtered_messages) = collector();
+    filtered.listen(StoreId::new(), filtered_sender);
+
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Commit(TestRecord::since("a4", 0)),
+        StoreMsg::Commit(TestRecord::since("b3", 0)),
+        StoreMsg::Commit(TestRecord::since("a5", 0)),
+    ]));
+    flush(&context);
+    store.send(StoreMsg::Transaction(vec![
+        StoreMsg::Delete(find(&store, "b1").get_id()),
+        StoreMsg::Delete(find(&store, "a2").get_id()),
+    ]));
+    flush(&context);
+
+    assert_eq!(labels(&store.get_range(&Range::new(0, 10))), vec!["a1", "b2", "a3", "a4", "b3", "a5"]);
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(&messages[0], StoreViewMsg::Batch(batch) if matches!(batch[..], [StoreViewMsg::NewRange{from: Position(5), to: Position(8)}])));
+    assert!(matches!(&messages[1], StoreViewMsg::Batch(batch) if matches!(batch[..], [StoreViewMsg::RemoveRange{from: Position(1), to: Position(3)}])));
+
+    // filtered store translates the ranges record by record and merges the results back into ranges
+    assert_eq!(labels(&filtered.get_range(&Range::new(0, 10))), vec!["a1", "a3", "a4", "a5"]);
+    let filtered_messages = filtered_messages.borrow();
+    assert_eq!(filtered_messages.len(), 2);
+    assert!(matches!(&filtered_messages[0], StoreViewMsg::Batch(batch) if matches!(batch[..], [StoreViewMsg::NewRange{from: Position(3), to: Position(5)}])));
+    assert!(matches!(&filtered_messages[1], StoreViewMsg::Batch(batch) if matches!(batch[..], [StoreViewMsg::Remove(Position(1))])));
+}
@@ -0,0 +1,163 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestTreeRecord;
+use backend_inmemory::TreeInMemoryBackend;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::FlatTreeStore;
+use relm4_store::Position;
+use relm4_store::StoreMsg;
+use relm4_store::StoreViewMsg;
+use relm4_store::math::Range;
+
+use crate::common::TestTreeConfig;
+use crate::common::TestTreeStore;
+use crate::common::flush;
+use crate::common::listen;
+use crate::common::run;
+
+type TestFlatTreeStore = FlatTreeStore<TestTreeStore>;
+
+/// Describes visible rows, each row is indented by its depth
+fn rows(store: &TestFlatTreeStore) -> Vec<String> {
+    store.get_range(&Range::new(0, store.len()))
+        .iter()
+        .map(|node| format!("{}{}", " ".repeat(node.depth), node.record.label))
+        .collect()
+}
+
+fn find(store: &TestTreeStore, label: &str) -> TestTreeRecord {
+    store.get_range(&Range::new(0, store.len()))
+        .into_iter()
+        .find(|r| r.label == label)
+        .unwrap()
+}
+
+#[test]
+#[serial(gtk)]
+fn only_roots_are_visible_at_start() {
+    run(|_| {
+        let store = TestTreeStore::new(TreeInMemoryBackend::<TestTreeConfig>::new());
+        let tree = TestFlatTreeStore::new(store.clone());
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(rows(&tree), vec!["1", "2"]);
+
+        let r1 = find(&store, "1");
+        let node = tree.get(&r1.get_id().transfer()).unwrap();
+        assert_eq!(node.children, 2);
+        assert!(!node.expanded);
+        assert!(tree.get(&find(&store, "1.1").get_id().transfer()).is_none(), "Children of collapsed node are not visible");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn expand_and_collapse() {
+    run(|context| {
+        let store = TestTreeStore::new(TreeInMemoryBackend::<TestTreeConfig>::new());
+        let tree = TestFlatTreeStore::new(store.clone());
+        let messages = listen(&tree);
+
+        let r1 = find(&store, "1");
+        let r11 = find(&store, "1.1");
+
+        tree.expand(&r1.get_id());
+        flush(context);
+        assert!(tree.is_expanded(&r1.get_id()));
+        assert_eq!(rows(&tree), vec!["1", " 1.1", " 1.2", "2"]);
+        {
+            let messages = messages.borrow();
+            assert_eq!(messages.len(), 2);
+            assert!(matches!(messages[0], StoreViewMsg::NewRange{from: Position(1), to: Position(3)}));
+            assert!(matches!(&messages[1], StoreViewMsg::Update(id) if *id == r1.get_id().transfer()));
+        }
+
+        tree.expand(&r11.get_id());
+        flush(context);
+        assert_eq!(rows(&tree), vec!["1", " 1.1", "  1.1.1", " 1.2", "2"]);
+
+        messages.borrow_mut().clear();
+        tree.collapse(&r1.get_id());
+        flush(context);
+        assert_eq!(rows(&tree), vec!["1", "2"]);
+        {
+            let messages = messages.borrow();
+            assert_eq!(messages.len(), 2);
+            assert!(matches!(messages[0], StoreViewMsg::RemoveRange{from: Position(1), to: Position(4)}), "Whole visible subtree must be removed at once");
+            assert!(matches!(messages[1], StoreViewMsg::Update(_)));
+        }
+
+        tree.expand(&r1.get_id());
+        flush(context);
+        assert_eq!(rows(&tree), vec!["1", " 1.1", "  1.1.1", " 1.2", "2"], "Expanded state of descendants must be remembered");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_child_to_collapsed_node() {
+    run(|context| {
+        let store = TestTreeStore::new(TreeInMemoryBackend::<TestTreeConfig>::new());
+        let tree = TestFlatTreeStore::new(store.clone());
+        let messages = listen(&tree);
+
+        let r2 = find(&store, "2");
+        store.send(StoreMsg::Commit(TestTreeRecord::child("2.2", &r2)));
+        flush(context);
+
+        assert_eq!(rows(&tree), vec!["1", "2"]);
+        assert_eq!(tree.get(&r2.get_id().transfer()).unwrap().children, 2);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(&messages[0], StoreViewMsg::Update(id) if *id == r2.get_id().transfer()), "Node must be updated since children count changed");
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn add_child_to_expanded_node() {
+    run(|context| {
+        let store = TestTreeStore::new(TreeInMemoryBackend::<TestTreeConfig>::new());
+        let tree = TestFlatTreeStore::new(store.clone());
+        let r1 = find(&store, "1");
+        tree.expand(&r1.get_id());
+        flush(context);
+
+        let messages = listen(&tree);
+        store.send(StoreMsg::Commit(TestTreeRecord::child("1.3", &r1)));
+        flush(context);
+
+        assert_eq!(rows(&tree), vec!["1", " 1.1", " 1.2", " 1.3", "2"]);
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0], StoreViewMsg::NewAt(Position(3))));
+        assert!(matches!(messages[1], StoreViewMsg::Update(_)));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn delete_expanded_subtree() {
+    run(|context| {
+        let store = TestTreeStore::new(TreeInMemoryBackend::<TestTreeConfig>::new());
+        let tree = TestFlatTreeStore::new(store.clone());
+        let r1 = find(&store, "1");
+        let r11 = find(&store, "1.1");
+        tree.expand(&r1.get_id());
+        tree.expand(&r11.get_id());
+        flush(context);
+
+        let messages = listen(&tree);
+        store.send(StoreMsg::Delete(r11.get_id()));
+        flush(context);
+
+        assert_eq!(rows(&tree), vec!["1", " 1.2", "2"]);
+        assert!(!tree.is_expanded(&r11.get_id()));
+        let messages = messages.borrow();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], StoreViewMsg::Remove(Position(1))));
+        assert!(matches!(&messages[1], StoreViewMsg::Update(id) if *id == r1.get_id().transfer()));
+        assert!(matches!(messages[2], StoreViewMsg::Remove(Position(1))));
+    });
+}
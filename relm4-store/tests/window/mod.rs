@@ -0,0 +1,244 @@
+use serial_test::serial;
+
+use backend_dummy::test_cases::TestRecord;
+use backend_inmemory::InMemoryBackend;
+use record::Id;
+use record::Record;
+use relm4_store::DataStore;
+use relm4_store::Position;
+use relm4_store::StoreMsg;
+use relm4_store::math::Point;
+use relm4_store::math::Range;
+use relm4_store::window::KeepOnBottom;
+use relm4_store::window::PositionTrackingWindow;
+use relm4_store::window::RecordTrackingWindow;
+use relm4_store::window::StoreState;
+use relm4_store::window::ValueTrackingWindow;
+use relm4_store::window::WindowBehavior;
+use relm4_store::window::WindowTransition;
+
+use crate::common::TestStore;
+use crate::common::find;
+use crate::common::flush;
+use crate::common::run;
+
+fn full_page(page: &Range) -> StoreState<'_> {
+    StoreState{
+        page,
+        view: page.len(),
+    }
+}
+
+#[test]
+fn value_tracking_default_threshold_is_in_the_middle() {
+    let page = Range::new(10, 22);
+    let mut window = ValueTrackingWindow::default();
+
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(15)),
+        WindowTransition::InsertLeft{pos: 15, by: 1}
+    ));
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(16)),
+        WindowTransition::InsertRight{pos: 16, by: 1}
+    ));
+}
+
+#[test]
+fn value_tracking_threshold_is_configurable() {
+    let page = Range::new(10, 22);
+    let mut window = ValueTrackingWindow::with_threshold(1.0/3.0);
+
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(13)),
+        WindowTransition::InsertLeft{pos: 13, by: 1}
+    ));
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(14)),
+        WindowTransition::InsertRight{pos: 14, by: 1}
+    ));
+    assert!(matches!(
+        window.remove(&full_page(&page), &Point::new(14)),
+        WindowTransition::RemoveRight{pos: 14, by: 1}
+    ));
+}
+
+#[test]
+#[should_panic]
+fn value_tracking_threshold_out_of_range() {
+    ValueTrackingWindow::with_threshold(1.5);
+}
+
+#[test]
+fn boxed_behavior_can_be_switched() {
+    let page = Range::new(0, 10);
+    let mut window: Box<dyn WindowBehavior> = Box::new(KeepOnBottom::new());
+
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(10)),
+        WindowTransition::SlideRight(1)
+    ));
+
+    window = Box::new(PositionTrackingWindow::new());
+
+    assert!(matches!(
+        window.insert(&full_page(&page), &Point::new(10)),
+        WindowTransition::Identity
+    ));
+}
+
+#[test]
+fn position_tracking_inserts_range_in_one_step() {
+    let page = Range::new(10, 20);
+    let mut window = PositionTrackingWindow::new();
+
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(2, 5)),
+        WindowTransition::InsertRight{pos: 10, by: 3}
+    ));
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(15, 30)),
+        WindowTransition::InsertRight{pos: 15, by: 5}
+    ));
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(20, 25)),
+        WindowTransition::Identity
+    ));
+}
+
+#[test]
+fn position_tracking_removes_range_in_one_step() {
+    let page = Range::new(10, 20);
+    let mut window = PositionTrackingWindow::new();
+
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(5, 12)),
+        WindowTransition::RemoveRight{pos: 10, by: 7}
+    ));
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(12, 15)),
+        WindowTransition::RemoveRight{pos: 12, by: 3}
+    ));
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(18, 25)),
+        WindowTransition::RemoveRight{pos: 18, by: 2}
+    ));
+}
+
+#[test]
+fn value_tracking_ranges_follow_the_threshold() {
+    let page = Range::new(10, 20);
+    let mut window = ValueTrackingWindow::new();
+
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(2, 5)),
+        WindowTransition::SlideRight(3)
+    ));
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(12, 15)),
+        WindowTransition::InsertLeft{pos: 12, by: 3}
+    ));
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(12, 15)),
+        WindowTransition::RemoveLeft{pos: 15, by: 3}
+    ));
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(16, 25)),
+        WindowTransition::RemoveRight{pos: 16, by: 4}
+    ));
+}
+
+#[test]
+fn keep_on_bottom_slides_by_the_range() {
+    let page = Range::new(10, 20);
+    let mut window = KeepOnBottom::new();
+
+    assert!(matches!(
+        window.insert_range(&full_page(&page), &Range::new(20, 24)),
+        WindowTransition::SlideRight(4)
+    ));
+    assert!(matches!(
+        window.remove_range(&full_page(&page), &Range::new(15, 20)),
+        WindowTransition::RemoveLeft{pos: 20, by: 5}
+    ));
+}
+
+#[test]
+#[serial(gtk)]
+fn record_tracking_anchors_on_the_next_record_when_anchor_is_removed() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::new());
+        let page = Range::new(0, 5);
+        let mut window = RecordTrackingWindow::with_anchor(store.clone(), find(&store, "b1").get_id(), Position(1));
+
+        store.send(StoreMsg::Delete(find(&store, "b1").get_id()));
+        flush(context);
+        window.remove(&full_page(&page), &Point::new(1));
+
+        assert_eq!(window.anchor(), Some(find(&store, "a2").get_id()));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn record_tracking_anchors_on_the_last_record_when_last_anchor_is_removed() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::new());
+        let page = Range::new(0, 5);
+        let mut window = RecordTrackingWindow::with_anchor(store.clone(), find(&store, "a3").get_id(), Position(4));
+
+        store.send(StoreMsg::Delete(find(&store, "a3").get_id()));
+        flush(context);
+        window.remove(&full_page(&page), &Point::new(4));
+
+        assert_eq!(window.anchor(), Some(find(&store, "b2").get_id()));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn record_tracking_anchors_on_the_next_record_when_range_with_anchor_is_removed() {
+    run(|context| {
+        let store = TestStore::new(InMemoryBackend::new());
+        let page = Range::new(0, 5);
+        let mut window = RecordTrackingWindow::with_anchor(store.clone(), find(&store, "a2").get_id(), Position(2));
+
+        store.send(StoreMsg::Transaction(vec![
+            StoreMsg::Delete(find(&store, "b1").get_id()),
+            StoreMsg::Delete(find(&store, "a2").get_id()),
+        ]));
+        flush(context);
+        window.remove_range(&full_page(&page), &Range::new(1, 3));
+
+        assert_eq!(window.anchor(), Some(find(&store, "b2").get_id()));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn record_tracking_keeps_anchor_when_it_gets_permanent_id() {
+    run(|_| {
+        let store = TestStore::new(InMemoryBackend::new());
+        let old = Id::new();
+        let new = find(&store, "b1").get_id();
+        let mut window = RecordTrackingWindow::with_anchor(store, old, Position(1));
+
+        window.id_changed(&old, &new);
+
+        assert_eq!(window.anchor(), Some(new));
+    });
+}
+
+#[test]
+#[serial(gtk)]
+fn record_tracking_ignores_id_change_of_other_records() {
+    run(|_| {
+        let store = TestStore::new(InMemoryBackend::new());
+        let anchor = find(&store, "b1").get_id();
+        let mut window = RecordTrackingWindow::with_anchor(store, anchor, Position(1));
+
+        window.id_changed(&Id::<TestRecord>::new(), &Id::<TestRecord>::new());
+
+        assert_eq!(window.anchor(), Some(anchor));
+    });
+}